@@ -1,7 +1,7 @@
 use criterion::{criterion_group, criterion_main, Criterion};
 use rand::{Rng, SeedableRng};
 use rand_chacha::ChaCha8Rng;
-use simdeez::{prelude::*, simd_runtime_generate};
+use simdeez::{parse, prelude::*, simd_runtime_generate};
 
 struct NumberParser<'a> {
     string: &'a [u8],
@@ -45,100 +45,31 @@ impl<'a> NumberParser<'a> {
 }
 
 struct NumberParserSimd<'a, S: Simd> {
-    string: &'a [i8],
+    string: &'a [u8],
     _simd: std::marker::PhantomData<S>,
 }
 
 impl<'a, S: Simd> NumberParserSimd<'a, S> {
     fn new(string: &'a str) -> Self {
         Self {
-            // Transmuting here is safe because we're converting from u8 to i8
-            // This is necessary to make loading easier below
-            string: unsafe { core::mem::transmute::<&[u8], &[i8]>(string.as_bytes()) },
-
+            string: string.as_bytes(),
             _simd: std::marker::PhantomData,
         }
     }
 
     fn get_next(&mut self) -> Option<u64> {
         simd_invoke!(S, {
-            let zero_char = S::Vi8::set1(b'0' as i8);
-            let nine_char = S::Vi8::set1(b'9' as i8);
-
-            let next_simd_match_vec =
-                |chars: S::Vi8| chars.cmp_gte(zero_char) & chars.cmp_lte(nine_char);
-
-            if self.string.is_empty() {
-                return None;
-            }
-
-            // Locate the first number byte
-            loop {
-                if self.string.is_empty() {
-                    return None;
-                }
-
-                let chars = S::Vi8::load_from_slice(self.string);
-                let mask = next_simd_match_vec(chars);
-
-                if let Some(index) = mask.index_of_first_truthy() {
-                    self.string = &self.string[index..];
-                    break;
-                } else {
-                    if self.string.len() < S::Vi8::WIDTH {
-                        self.string = &self.string[0..0];
-                        return None;
-                    }
-                    self.string = &self.string[S::Vi8::WIDTH..];
-                }
-            }
-
-            let mut number_end_str = self.string;
-            let mut num_length = 0;
-
-            // Locate the end of the number
-            loop {
-                let chars = S::Vi8::load_from_slice(number_end_str);
-                let mask = next_simd_match_vec(chars);
-
-                if let Some(index) = mask.index_of_first_falsy() {
-                    number_end_str = &number_end_str[index..];
-                    num_length += index;
-                    break;
-                } else if self.string.len() <= S::Vi8::WIDTH {
-                    number_end_str = &number_end_str[0..0];
-                    num_length += self.string.len();
-                    break;
-                } else {
-                    number_end_str = &number_end_str[S::Vi8::WIDTH..];
-                    num_length += S::Vi8::WIDTH;
-                }
-            }
-
-            // Parse the number
-            let mut total = 0;
-            let mut num_str = &self.string[..num_length];
-
-            loop {
-                let num_chars = S::Vi8::load_from_slice(num_str);
-                let digits = num_chars - zero_char;
-
-                let length = num_str.len().min(S::Vi8::WIDTH);
-                for digit in digits.iter().take(length) {
-                    total *= 10;
-                    total += digit as u64;
-                }
-
-                if num_str.len() <= S::Vi8::WIDTH {
-                    break;
-                } else {
-                    num_str = &num_str[S::Vi8::WIDTH..];
-                }
-            }
-
-            self.string = number_end_str;
-
-            Some(total)
+            let start = parse::find_digit::<S>(self.string)?;
+            let rest = &self.string[start..];
+            let (num, len) = parse::parse_prefix::<S, u64>(rest).unwrap_or_else(|_| {
+                // Saturate numbers that overflow, skipping over the rest of their digits
+                (
+                    u64::MAX,
+                    rest.iter().take_while(|b| b.is_ascii_digit()).count(),
+                )
+            });
+            self.string = &rest[len..];
+            Some(num)
         })
     }
 }
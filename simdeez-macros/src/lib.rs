@@ -0,0 +1,481 @@
+//! Attribute versions of the `simd_runtime_generate!`, `simd_compiletime_select!` and
+//! `simd_unsafe_generate_all!` macros of simdeez, enabled with its `macros` feature.
+//!
+//! Unlike the `macro_rules!` versions, the attributes work on methods with a `self` receiver,
+//! associated functions, `unsafe fn` and arguments with patterns, and report errors at the
+//! offending tokens. The body is written once with `S` as the engine, exactly like with the
+//! `macro_rules!` versions:
+//!
+//! ```ignore
+//! #[simd_runtime]
+//! pub fn sum(data: &[f32]) -> f32 {
+//!     let mut sum = S::Vf32::zeroes();
+//!     // ...
+//! }
+//! ```
+//!
+//! The attributes can be put on a function, on a method of an inherent impl, or on a whole impl
+//! block to apply to every function in it. Methods of a trait impl need the attribute on the impl
+//! block, as the variants are extra items which go into an inherent impl of the same type.
+//!
+//! When the attribute is on a single function, it is taken as a method if it has a receiver or
+//! mentions `Self` in its signature. Put the attribute on the impl block for associated functions
+//! which do neither.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2, TokenTree};
+use quote::{ToTokens, format_ident, quote};
+use syn::{
+    Attribute, Block, FnArg, GenericParam, Ident, ImplItem, Item, ItemImpl, Pat, PatIdent,
+    ReturnType, Signature, Visibility, parse_quote, spanned::Spanned,
+};
+
+/// Generates a function that picks the engine at runtime, like `simd_runtime_generate!`, along
+/// with the `_resolve` function returning the version of the engine it picks, the `_scalar`
+/// version and the `_generic` version taking the engine as its last generic parameter.
+///
+/// Free functions without generic parameters resolve the engine on the first call and keep it
+/// until `set_max_engine` is called, like `simd_runtime_generate!`. Methods and generic functions
+/// pick the engine on every call, so call them through `_resolve` in hot loops.
+#[proc_macro_attribute]
+pub fn simd_runtime(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand(Kind::Runtime, attr, item)
+}
+
+/// Generates a function that uses the best engine enabled by the compile time target features,
+/// like `simd_compiletime_select!`, along with the `_scalar` and `_generic` versions.
+#[proc_macro_attribute]
+pub fn simd_compiletime(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand(Kind::Compiletime, attr, item)
+}
+
+/// Makes the function generic over the engine, with `S: Simd` as its last generic parameter,
+/// and generates a version for every engine like `simd_unsafe_generate_all!`: the `_scalar`
+/// version and the unsafe `_avx2` version when AVX2 is enabled at compile time.
+#[proc_macro_attribute]
+pub fn simd_generic(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand(Kind::Generic, attr, item)
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Runtime,
+    Compiletime,
+    Generic,
+}
+
+fn expand(kind: Kind, attr: TokenStream, item: TokenStream) -> TokenStream {
+    let result = match TokenStream2::from(attr).into_iter().next() {
+        Some(token) => Err(syn::Error::new(
+            token.span(),
+            "the SIMD dispatch attributes take no arguments",
+        )),
+        None => syn::parse::<Item>(item).and_then(|item| expand_item(kind, item)),
+    };
+    result.unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+fn expand_item(kind: Kind, item: Item) -> syn::Result<TokenStream2> {
+    match item {
+        Item::Fn(item) => {
+            let function = SimdFn::new(kind, item.attrs, item.vis, item.sig, *item.block)?;
+            let in_impl = function.is_method();
+            let (primary, variants) = function.expand(in_impl);
+            Ok(quote! { #primary #variants })
+        }
+        Item::Impl(item) => expand_impl(kind, item),
+        item => Err(syn::Error::new(
+            item.span(),
+            "expected a function or an impl block",
+        )),
+    }
+}
+
+/// Expands every function of an impl block, moving the variants of trait methods into an inherent
+/// impl of the same type.
+fn expand_impl(kind: Kind, mut item: ItemImpl) -> syn::Result<TokenStream2> {
+    if let (Kind::Generic, Some((_, path, _))) = (kind, &item.trait_) {
+        return Err(syn::Error::new(
+            path.span(),
+            "`simd_generic` changes the signature, which a trait impl can't do",
+        ));
+    }
+
+    let mut items = Vec::new();
+    let mut variants = Vec::new();
+    for impl_item in item.items.drain(..) {
+        let ImplItem::Fn(method) = impl_item else {
+            items.push(impl_item.into_token_stream());
+            continue;
+        };
+        let function = SimdFn::new(kind, method.attrs, method.vis, method.sig, method.block)?;
+        let (primary, function_variants) = function.expand(true);
+        items.push(primary);
+        variants.push(function_variants);
+    }
+
+    let ItemImpl {
+        attrs,
+        defaultness,
+        unsafety,
+        impl_token,
+        generics,
+        trait_,
+        self_ty,
+        ..
+    } = &item;
+    let where_clause = &generics.where_clause;
+    Ok(match trait_ {
+        Some((bang, path, for_token)) => quote! {
+            #(#attrs)*
+            #defaultness #unsafety #impl_token #generics #bang #path #for_token #self_ty #where_clause {
+                #(#items)*
+            }
+
+            #impl_token #generics #self_ty #where_clause {
+                #(#variants)*
+            }
+        },
+        None => quote! {
+            #(#attrs)*
+            #defaultness #unsafety #impl_token #generics #self_ty #where_clause {
+                #(#items)*
+                #(#variants)*
+            }
+        },
+    })
+}
+
+fn engine_path(engine: &str) -> TokenStream2 {
+    match engine {
+        "scalar" => quote! { ::simdeez::engines::scalar::Scalar },
+        _ => quote! { ::simdeez::engines::avx2::Avx2 },
+    }
+}
+
+/// Whether the tokens mention the identifier or keyword `name`.
+fn mentions_ident(tokens: TokenStream2, name: &str) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(ident) => ident == name,
+        TokenTree::Group(group) => mentions_ident(group.stream(), name),
+        _ => false,
+    })
+}
+
+/// The names the forwarding functions give the arguments, `self` for the receiver.
+fn forwarded_args(sig: &Signature) -> Vec<Ident> {
+    sig.inputs
+        .iter()
+        .enumerate()
+        .map(|(i, input)| match input {
+            FnArg::Receiver(receiver) => Ident::new("self", receiver.self_token.span),
+            FnArg::Typed(_) => Ident::new(&format!("__simdeez_arg{i}"), Span::mixed_site()),
+        })
+        .collect()
+}
+
+/// The type and const parameters of the function, to pass them on to its generic version.
+fn generic_args(sig: &Signature) -> Vec<&Ident> {
+    sig.generics
+        .params
+        .iter()
+        .filter_map(|param| match param {
+            GenericParam::Type(param) => Some(&param.ident),
+            GenericParam::Const(param) => Some(&param.ident),
+            GenericParam::Lifetime(_) => None,
+        })
+        .collect()
+}
+
+struct SimdFn {
+    kind: Kind,
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    sig: Signature,
+    block: Block,
+}
+
+impl SimdFn {
+    fn new(
+        kind: Kind,
+        attrs: Vec<Attribute>,
+        vis: Visibility,
+        sig: Signature,
+        block: Block,
+    ) -> syn::Result<SimdFn> {
+        if let Some(constness) = &sig.constness {
+            return Err(syn::Error::new(
+                constness.span(),
+                "SIMD dispatch can't be used on a const fn",
+            ));
+        }
+        if let Some(asyncness) = &sig.asyncness {
+            return Err(syn::Error::new(
+                asyncness.span(),
+                "SIMD dispatch can't be used on an async fn",
+            ));
+        }
+        if let Some(param) = sig.generics.type_params().find(|param| param.ident == "S") {
+            return Err(syn::Error::new(
+                param.ident.span(),
+                "`S` is the engine parameter added by the SIMD dispatch attributes",
+            ));
+        }
+        Ok(SimdFn {
+            kind,
+            attrs,
+            vis,
+            sig,
+            block,
+        })
+    }
+
+    fn is_method(&self) -> bool {
+        self.sig.receiver().is_some() || mentions_ident(self.sig.to_token_stream(), "Self")
+    }
+
+    fn variant_name(&self, suffix: &str) -> Ident {
+        format_ident!("{}_{}", self.sig.ident, suffix)
+    }
+
+    /// The name of the version generic over the engine, which holds the body.
+    fn generic_name(&self) -> Ident {
+        match self.kind {
+            Kind::Generic => self.sig.ident.clone(),
+            _ => self.variant_name("generic"),
+        }
+    }
+
+    /// Returns the function keeping the original name, and the other variants.
+    fn expand(&self, in_impl: bool) -> (TokenStream2, TokenStream2) {
+        let generic = self.generic_fn();
+        let scalar =
+            self.forwarding_fn(self.variant_name("scalar"), in_impl, |call| call("scalar"));
+        match self.kind {
+            Kind::Runtime => {
+                let primary = self.forwarding_fn(self.sig.ident.clone(), in_impl, |call| {
+                    if !in_impl && self.sig.generics.params.is_empty() && !self.has_impl_trait() {
+                        return self.cached_call();
+                    }
+                    let (avx2, scalar) = (call("avx2"), call("scalar"));
+                    quote! {
+                        match ::simdeez::max_engine() {
+                            ::simdeez::EngineKind::Avx2 => #avx2,
+                            ::simdeez::EngineKind::Scalar => #scalar,
+                        }
+                    }
+                });
+                let resolve = self.resolve_fn(in_impl);
+                (primary, quote! { #resolve #scalar #generic })
+            }
+            Kind::Compiletime => {
+                let primary = self.forwarding_fn(self.sig.ident.clone(), in_impl, |call| {
+                    let (avx2, scalar) = (call("avx2"), call("scalar"));
+                    quote! {
+                        #[cfg(all(target_arch = "x86_64", target_feature = "avx2", target_feature = "fma"))]
+                        return #avx2;
+                        #[cfg(not(all(target_arch = "x86_64", target_feature = "avx2", target_feature = "fma")))]
+                        return #scalar;
+                    }
+                });
+                (primary, quote! { #scalar #generic })
+            }
+            Kind::Generic => {
+                let avx2 =
+                    self.forwarding_fn_with(self.variant_name("avx2"), in_impl, true, |call| {
+                        call("avx2")
+                    });
+                let variants = quote! {
+                    #scalar
+                    #[cfg(all(target_arch = "x86_64", target_feature = "avx2"))]
+                    #avx2
+                };
+                (generic, variants)
+            }
+        }
+    }
+
+    /// Whether an argument or the return type is an `impl Trait`, which can't be named outside of
+    /// the signature.
+    fn has_impl_trait(&self) -> bool {
+        let arg_types = self.sig.inputs.iter().filter_map(|input| match input {
+            FnArg::Typed(typed) => Some(typed.ty.to_token_stream()),
+            FnArg::Receiver(_) => None,
+        });
+        let return_type = self.sig.output.to_token_stream();
+        arg_types
+            .chain([return_type])
+            .any(|tokens| mentions_ident(tokens, "impl"))
+    }
+
+    /// The types of the arguments, with the receiver as the type it stands for.
+    fn arg_types(&self) -> Vec<TokenStream2> {
+        self.sig
+            .inputs
+            .iter()
+            .map(|input| match input {
+                FnArg::Receiver(receiver) => receiver.ty.to_token_stream(),
+                FnArg::Typed(typed) => typed.ty.to_token_stream(),
+            })
+            .collect()
+    }
+
+    fn return_type(&self) -> TokenStream2 {
+        match &self.sig.output {
+            ReturnType::Default => quote! { () },
+            ReturnType::Type(_, ty) => ty.to_token_stream(),
+        }
+    }
+
+    /// The body of the primary runtime function of a free function without generic parameters,
+    /// which resolves the engine on the first call and keeps its version in a static, like
+    /// `simd_runtime_generate!` does. The runner can't name `Self` or generic parameters of the
+    /// function, so methods and generic functions pick the engine on every call instead.
+    fn cached_call(&self) -> TokenStream2 {
+        let args = forwarded_args(&self.sig);
+        let types = self.arg_types();
+        let return_type = self.return_type();
+        let generic_name = self.generic_name();
+        let runner = Ident::new("__SimdeezRunner", Span::mixed_site());
+        let cache = Ident::new("__SIMDEEZ_CACHE", Span::mixed_site());
+        quote! {
+            struct #runner;
+
+            impl ::simdeez::prelude::__SimdRunner<(#(#types,)*), #return_type> for #runner {
+                #[inline(always)]
+                #[allow(unused_unsafe)]
+                unsafe fn run<S: ::simdeez::Simd>((#(#args,)*): (#(#types,)*)) -> #return_type {
+                    unsafe { #generic_name::<S>(#(#args),*) }
+                }
+            }
+
+            static #cache: ::simdeez::prelude::__SimdRunnerCache =
+                ::simdeez::prelude::__SimdRunnerCache::new();
+            unsafe { #cache.get::<#runner, (#(#types,)*), #return_type>()((#(#args,)*)) }
+        }
+    }
+
+    /// The `_resolve` function of runtime dispatch, returning the version of the engine it picks
+    /// now. It is left out for `impl Trait` arguments, as a function pointer can't take them.
+    fn resolve_fn(&self, in_impl: bool) -> Option<TokenStream2> {
+        if self.has_impl_trait() {
+            return None;
+        }
+        let SimdFn { attrs, vis, .. } = self;
+        let name = self.variant_name("resolve");
+        let generics = &self.sig.generics;
+        let where_clause = &generics.where_clause;
+        let unsafety = &self.sig.unsafety;
+        let types = self.arg_types();
+        let return_type = self.return_type();
+        let generic_args = generic_args(&self.sig);
+        let generic_name = self.generic_name();
+        let prefix = in_impl.then(|| quote! { Self:: });
+        let (avx2, scalar) = (engine_path("avx2"), engine_path("scalar"));
+        Some(quote! {
+            #(#attrs)*
+            #[inline(always)]
+            #vis fn #name #generics() -> #unsafety fn(#(#types),*) -> #return_type #where_clause {
+                match ::simdeez::max_engine() {
+                    ::simdeez::EngineKind::Avx2 => #prefix #generic_name::<#(#generic_args,)* #avx2>,
+                    ::simdeez::EngineKind::Scalar => #prefix #generic_name::<#(#generic_args,)* #scalar>,
+                }
+            }
+        })
+    }
+
+    /// The version generic over the engine, which runs the body inside `S::invoke` so that it is
+    /// compiled with the target features of the engine.
+    fn generic_fn(&self) -> TokenStream2 {
+        let SimdFn {
+            attrs, vis, block, ..
+        } = self;
+        let mut sig = self.sig.clone();
+        sig.ident = self.generic_name();
+        sig.generics
+            .params
+            .push(parse_quote! { S: ::simdeez::Simd });
+        let body = match &sig.unsafety {
+            Some(_) => quote! { unsafe #block },
+            None => quote! { #block },
+        };
+        let allow_unsafe = sig.unsafety.map(|_| quote! { #[allow(unused_unsafe)] });
+        quote! {
+            #(#attrs)*
+            #[inline(always)]
+            #allow_unsafe
+            #vis #sig {
+                <S as ::simdeez::Simd>::invoke(#[inline(always)] || #body)
+            }
+        }
+    }
+
+    fn forwarding_fn(
+        &self,
+        name: Ident,
+        in_impl: bool,
+        body: impl FnOnce(&dyn Fn(&str) -> TokenStream2) -> TokenStream2,
+    ) -> TokenStream2 {
+        self.forwarding_fn_with(name, in_impl, self.sig.unsafety.is_some(), body)
+    }
+
+    /// A function with the same signature that forwards its arguments to the generic version,
+    /// with `body` building the calls for the engines it needs.
+    fn forwarding_fn_with(
+        &self,
+        name: Ident,
+        in_impl: bool,
+        unsafe_fn: bool,
+        body: impl FnOnce(&dyn Fn(&str) -> TokenStream2) -> TokenStream2,
+    ) -> TokenStream2 {
+        let mut sig = self.sig.clone();
+        sig.ident = name;
+        if unsafe_fn {
+            sig.unsafety = Some(Default::default());
+        }
+
+        // The arguments may be patterns, so the forwarding function names them instead.
+        let args = forwarded_args(&sig);
+        for (input, arg) in sig.inputs.iter_mut().zip(&args) {
+            match input {
+                FnArg::Receiver(receiver) => {
+                    if receiver.reference.is_none() {
+                        receiver.mutability = None;
+                    }
+                }
+                FnArg::Typed(typed) => {
+                    *typed.pat = Pat::Ident(PatIdent {
+                        attrs: Vec::new(),
+                        by_ref: None,
+                        mutability: None,
+                        ident: arg.clone(),
+                        subpat: None,
+                    });
+                }
+            }
+        }
+
+        let generic_args = generic_args(&self.sig);
+        let generic_name = self.generic_name();
+        let prefix = in_impl.then(|| quote! { Self:: });
+        let call = |engine: &str| {
+            let engine = engine_path(engine);
+            let call = quote! { #prefix #generic_name::<#(#generic_args,)* #engine>(#(#args),*) };
+            match self.sig.unsafety {
+                Some(_) => quote! { unsafe { #call } },
+                None => call,
+            }
+        };
+        let body = body(&call);
+
+        let attrs = &self.attrs;
+        let vis = &self.vis;
+        quote! {
+            #(#attrs)*
+            #[inline(always)]
+            #vis #sig {
+                #body
+            }
+        }
+    }
+}
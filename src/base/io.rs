@@ -1,5 +1,35 @@
 use crate::SimdConsts;
 
+/// A cache locality hint for `SimdBaseIo::prefetch`. The values correspond to the `_MM_HINT_*` constants.
+pub trait Locality {
+    const HINT: i32;
+}
+
+/// Prefetch into all levels of the cache hierarchy.
+pub struct PrefetchT0;
+/// Prefetch into the second level cache and higher.
+pub struct PrefetchT1;
+/// Prefetch into the third level cache and higher.
+pub struct PrefetchT2;
+/// Prefetch into a non-temporal cache structure, minimizing cache pollution.
+pub struct PrefetchNta;
+
+impl Locality for PrefetchT0 {
+    const HINT: i32 = 3;
+}
+
+impl Locality for PrefetchT1 {
+    const HINT: i32 = 2;
+}
+
+impl Locality for PrefetchT2 {
+    const HINT: i32 = 1;
+}
+
+impl Locality for PrefetchNta {
+    const HINT: i32 = 0;
+}
+
 pub trait SimdBaseIo: SimdConsts {
     /// Transmutes the vector into a array representation defined by `Self::ArrayRepresentation`.
     /// Please don't use this function directly unless necessary.
@@ -26,16 +56,60 @@ pub trait SimdBaseIo: SimdConsts {
         self.transmute_into_array_ref().clone()
     }
 
+    /// Initialize a vector from an array of its lanes.
+    #[inline(always)]
+    fn from_array(array: Self::ArrayRepresentation) -> Self {
+        // The array is less aligned than the vector, which `transmute_copy` allows.
+        unsafe { core::mem::transmute_copy(&array) }
+    }
+
+    /// Copies the lanes of the vector into an array.
+    #[inline(always)]
+    fn to_array(self) -> Self::ArrayRepresentation {
+        unsafe { self.as_array() }
+    }
+
+    /// Initialize a vector by calling `f` with the index of every lane, e.g. a mask of the first
+    /// `n` lanes with `|lane| if lane < n { -1 } else { 0 }`.
+    #[inline(always)]
+    fn from_fn(mut f: impl FnMut(usize) -> Self::Scalar) -> Self {
+        let mut array = Self::zeroes().to_array();
+        for lane in 0..Self::WIDTH {
+            array[lane] = f(lane);
+        }
+        Self::from_array(array)
+    }
+
+    /// Initialize a vector with every lane set to its index, `0, 1, 2, ...`
+    fn lane_indices() -> Self;
+
     /// Load a vector from an unaligned raw pointer.
     unsafe fn load_from_ptr_unaligned(ptr: *const Self::Scalar) -> Self;
     /// Store a vector to an unaligned raw pointer.
     unsafe fn copy_to_ptr_unaligned(self, ptr: *mut Self::Scalar);
 
-    /// Load a vector from a 32 bit aligned raw pointer.
+    /// Load a vector from a raw pointer aligned to the size of the vector, 32 bytes with Avx2.
+    /// A misaligned pointer faults.
     unsafe fn load_from_ptr_aligned(ptr: *const Self::Scalar) -> Self;
-    /// Store a vector to a 32 bit aligned raw pointer.
+    /// Store a vector to a raw pointer aligned to the size of the vector, 32 bytes with Avx2.
+    /// A misaligned pointer faults.
     unsafe fn copy_to_ptr_aligned(self, ptr: *mut Self::Scalar);
 
+    /// Store a vector to a raw pointer aligned to the size of the vector, 32 bytes with Avx2, with
+    /// a non-temporal hint, bypassing the cache. A misaligned pointer faults.
+    /// Use this for data that won't be read again soon, and call `store_fence` once the batch of
+    /// streaming stores is complete and before the data is handed to another thread.
+    unsafe fn copy_to_ptr_streaming(self, ptr: *mut Self::Scalar);
+
+    /// Guarantees that all stores made before the fence, including streaming stores, are globally
+    /// visible before any store made after it. This is a no-op on instruction sets without weakly
+    /// ordered stores.
+    fn store_fence();
+
+    /// Hints the CPU to fetch the cache line containing `ptr` into the cache level described by `L`.
+    /// This never faults, even for invalid pointers, and is a no-op on instruction sets without prefetching.
+    fn prefetch<L: Locality>(ptr: *const Self::Scalar);
+
     unsafe fn underlying_value(self) -> Self::UnderlyingType;
     unsafe fn underlying_value_mut(&mut self) -> &mut Self::UnderlyingType;
     unsafe fn from_underlying_value(value: Self::UnderlyingType) -> Self;
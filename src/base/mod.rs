@@ -12,7 +12,7 @@ pub use transmute::*;
 mod specializations;
 pub use specializations::*;
 
-pub use io::SimdBaseIo;
+pub use io::{Locality, PrefetchNta, PrefetchT0, PrefetchT1, PrefetchT2, SimdBaseIo};
 
 use crate::Simd;
 
@@ -31,6 +31,40 @@ pub trait SimdConsts: 'static + Copy + core::marker::Sync + core::marker::Send +
     type Engine: Simd;
 }
 
+/// The associative operator used by `SimdBaseOps::prefix_scan` and the slice scans in `scan`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScanOp {
+    Add,
+    Max,
+    Min,
+    /// Bitwise xor, which applies to the bit patterns of float types.
+    Xor,
+}
+
+impl ScanOp {
+    /// Applies the operator to every pair of lanes.
+    #[inline(always)]
+    pub fn combine<V: SimdBaseOps>(self, a: V, b: V) -> V {
+        match self {
+            ScanOp::Add => a + b,
+            ScanOp::Max => a.max(b),
+            ScanOp::Min => a.min(b),
+            ScanOp::Xor => a ^ b,
+        }
+    }
+
+    /// A value that leaves the scan unchanged when combined into a vector whose first lane is
+    /// `first`. Max and min have no identity that works for every type, but since they are
+    /// idempotent, combining in the first lane again is harmless.
+    #[inline(always)]
+    pub(crate) fn neutral<V: SimdBaseOps>(self, first: V::Scalar) -> V::Scalar {
+        match self {
+            ScanOp::Add | ScanOp::Xor => unsafe { V::zeroes().get_unchecked(0) },
+            ScanOp::Max | ScanOp::Min => first,
+        }
+    }
+}
+
 /// Operations shared by all SIMD types
 pub trait SimdBaseOps:
     SimdConsts
@@ -133,6 +167,102 @@ pub trait SimdBaseOps:
 
     /// Add every number in the vector together
     fn horizontal_add(self) -> Self::HorizontalAddScalar;
+
+    /// Gets the value of the lane at the constant index `I`, which must be smaller than `Self::WIDTH`.
+    /// Unlike the index operator, this doesn't go through memory on most instruction sets.
+    /// A larger `I` fails to compile on the SIMD engines, and panics on the scalar engine.
+    fn extract<const I: i32>(self) -> Self::Scalar;
+
+    /// Replaces the value of the lane at the constant index `I`, which must be smaller than `Self::WIDTH`.
+    /// Unlike the index operator, this doesn't go through memory on most instruction sets.
+    /// A larger `I` fails to compile on the SIMD engines, and panics on the scalar engine.
+    fn insert<const I: i32>(self, value: Self::Scalar) -> Self;
+
+    /// Moves every lane `K` places towards the higher indices, filling the lowest `K` lanes with `fill`.
+    /// `K` must be between 0 and `Self::WIDTH` inclusive.
+    fn shift_lanes_up<const K: i32>(self, fill: Self::Scalar) -> Self;
+
+    /// Moves every lane `K` places towards the lower indices, filling the highest `K` lanes with `fill`.
+    /// `K` must be between 0 and `Self::WIDTH` inclusive.
+    fn shift_lanes_down<const K: i32>(self, fill: Self::Scalar) -> Self;
+
+    /// Concatenates `self` as the upper half and `lower` as the lower half, shifts the combined vector
+    /// down by `K` lanes, then returns the lower half. This is `palignr` applied across the whole vector.
+    /// `K` must be between 0 and `Self::WIDTH` inclusive.
+    fn align_right<const K: i32>(self, lower: Self) -> Self;
+
+    /// Computes the inclusive prefix sum of the vector: every lane becomes the sum of itself and
+    /// all the lanes below it.
+    #[inline(always)]
+    fn prefix_sum(self) -> Self {
+        self.prefix_scan(ScanOp::Add)
+    }
+
+    /// Computes the inclusive prefix scan of the vector with `op`: every lane becomes `op` applied
+    /// to itself and all the lanes below it.
+    ///
+    /// This takes log2(`Self::WIDTH`) steps, each shifting the partial results up by twice as many
+    /// lanes as the last and combining them in. Since the lanes are combined in a different order
+    /// than a sequential loop, float sums may round differently, and whether a NaN propagates
+    /// through a max or min is unspecified.
+    #[inline(always)]
+    fn prefix_scan(self, op: ScanOp) -> Self {
+        let fill = op.neutral::<Self>(unsafe { self.get_unchecked(0) });
+        let mut scan = self;
+        if Self::WIDTH > 1 {
+            scan = op.combine(scan, scan.shift_lanes_up::<1>(fill));
+        }
+        if Self::WIDTH > 2 {
+            scan = op.combine(scan, scan.shift_lanes_up::<2>(fill));
+        }
+        if Self::WIDTH > 4 {
+            scan = op.combine(scan, scan.shift_lanes_up::<4>(fill));
+        }
+        if Self::WIDTH > 8 {
+            scan = op.combine(scan, scan.shift_lanes_up::<8>(fill));
+        }
+        if Self::WIDTH > 16 {
+            scan = op.combine(scan, scan.shift_lanes_up::<16>(fill));
+        }
+        debug_assert!(Self::WIDTH <= 32);
+        scan
+    }
+
+    /// Moves the lanes selected by `mask` to the front of the vector, keeping their order, and
+    /// zeroes the remaining lanes. Returns the packed vector and the number of selected lanes.
+    /// A lane is selected when its mask value is all 1's, as produced by comparisons.
+    fn compress(self, mask: Self) -> (Self, usize);
+
+    /// The inverse of `compress`: moves the front lanes of the vector, in order, into the lanes
+    /// selected by `mask` and zeroes the others.
+    fn expand(self, mask: Self) -> Self;
+
+    /// Sorts the lanes in ascending order with a bitonic sorting network. For floats, the result
+    /// is unspecified if any lane is NaN.
+    fn sort_lanes(self) -> Self;
+
+    /// Merges two vectors that are each sorted in ascending order, returning the lowest
+    /// `Self::WIDTH` lanes of both and the highest `Self::WIDTH` lanes, each sorted. This is one
+    /// step of a vectorized merge sort.
+    fn merge_sorted(self, other: Self) -> (Self, Self);
+
+    /// Compresses the vector by `mask` and writes the selected lanes to the start of the slice,
+    /// returning how many were written. This is the building block of a vectorized filter.
+    ///
+    /// When the slice holds at least `Self::WIDTH` elements the whole packed vector is stored, so the
+    /// elements after the returned count are overwritten with zeroes.
+    /// Panics if the slice is shorter than the number of selected lanes.
+    fn compress_store(self, slice: &mut [Self::Scalar], mask: Self) -> usize {
+        let (packed, count) = self.compress(mask);
+        if slice.len() >= Self::WIDTH {
+            unsafe { packed.copy_to_ptr_unaligned(slice.as_mut_ptr()) };
+        } else {
+            for (i, s) in slice[..count].iter_mut().enumerate() {
+                *s = unsafe { packed.get_unchecked(i) };
+            }
+        }
+        count
+    }
 }
 
 pub trait SimdBase: SimdBaseOps + SimdBaseIo + SimdIter {}
@@ -70,6 +70,16 @@ pub trait SimdInt8: SimdInt<Scalar = i8, HorizontalAddScalar = i64> + SimdTransm
         a + b
     }
 
+    /// Rearranges the lanes of the vector: every lane of the result is the lane of `self` at the
+    /// matching lane of `indices`. Indices must be smaller than `Self::WIDTH`, and negative indices
+    /// produce zero.
+    fn permute(self, indices: Self) -> Self;
+
+    /// Looks up every lane in a 16 entry table: lanes between 0 and 15 are replaced by that entry of
+    /// `table`, and negative lanes become zero. Other values only use their lowest 4 bits.
+    /// This is `pshufb` with the table repeated in every 128 bit lane.
+    fn lookup_16(self, table: &[i8; 16]) -> Self;
+
     /// Gets the "mask" of a vector, where each bit in the u32 represents whether the value at that location
     /// is truthy. A value is truthy either if the highest bit is one, or if any bit is one, depending
     /// on the instruction set being used. Please always make sure at least the highest bit is set to 1.
@@ -216,6 +226,11 @@ pub trait SimdInt64: SimdInt<Scalar = i64, HorizontalAddScalar = i64> + SimdTran
     /// Element-wise cast to f64
     fn cast_f64(self) -> <Self::Engine as Simd>::Vf64;
 
+    /// The inverse of `extend_to_i64`: truncates the lanes of both vectors to i32 and joins them,
+    /// with the lanes of `self` first. Engines where i32 vectors are no wider than i64 vectors only
+    /// use `self`.
+    fn truncate_to_i32(self, high: Self) -> <Self::Engine as Simd>::Vi32;
+
     fn partial_horizontal_add(self) -> i64;
 }
 
@@ -278,6 +293,21 @@ pub trait SimdFloat:
     /// Element-wise approximate inverse square root
     fn rsqrt(self) -> Self;
 
+    /// Interleaves the lanes of two vectors, returning `a0, b0, a1, b1, ...` for the lower
+    /// halves of both, then the same for the upper halves.
+    fn zip(self, other: Self) -> (Self, Self);
+
+    /// The inverse of `zip`, returning the even lanes of both vectors, then the odd lanes.
+    fn unzip(self, other: Self) -> (Self, Self);
+
+    /// Interleaves the lanes of three vectors, returning `a0, b0, c0, a1, b1, c1, ...` split over
+    /// three vectors.
+    fn zip3(self, b: Self, c: Self) -> (Self, Self, Self);
+
+    /// The inverse of `zip3`, splitting three vectors of interleaved triples into one vector per
+    /// element of the triples.
+    fn unzip3(self, b: Self, c: Self) -> (Self, Self, Self);
+
     fn from_f64(value: f64) -> Self;
 }
 
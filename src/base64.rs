@@ -0,0 +1,348 @@
+//! Base64 encoding and decoding of byte slices, with the standard and URL-safe alphabets.
+//!
+//! Engines whose `Vi8` width is a multiple of 4 process a vector of output characters (or input
+//! characters when decoding) at a time: the bytes of each 3 byte group are spread over 4 lanes
+//! with `permute`, split into 6 bit values with shifts, and mapped to characters with comparisons.
+//! Plain scalar code is the reference implementation, which `Scalar` uses for everything and
+//! other engines use for the bytes left over at the end. The engine is a generic parameter, see
+//! [slice algorithms](crate#slice-algorithms).
+
+use core::fmt;
+
+use crate::lane_tables::{lane_table, load_lane_table};
+use crate::{Simd, SimdBaseIo, SimdBaseOps, SimdConsts, SimdInt, SimdInt8};
+
+/// The characters used for the values 62 and 63.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Alphabet {
+    /// `+` and `/`, from RFC 4648 section 4.
+    Standard,
+    /// `-` and `_`, from RFC 4648 section 5.
+    UrlSafe,
+}
+
+impl Alphabet {
+    fn last_two(self) -> (u8, u8) {
+        match self {
+            Alphabet::Standard => (b'+', b'/'),
+            Alphabet::UrlSafe => (b'-', b'_'),
+        }
+    }
+}
+
+/// How to encode and decode base64.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Base64Config {
+    pub alphabet: Alphabet,
+    /// Whether the output is padded with `=` to a multiple of 4 characters. When decoding, the
+    /// padding is then required, otherwise it is rejected.
+    pub padding: bool,
+}
+
+impl Base64Config {
+    pub const STANDARD: Self = Self {
+        alphabet: Alphabet::Standard,
+        padding: true,
+    };
+    pub const STANDARD_NO_PAD: Self = Self {
+        alphabet: Alphabet::Standard,
+        padding: false,
+    };
+    pub const URL_SAFE: Self = Self {
+        alphabet: Alphabet::UrlSafe,
+        padding: true,
+    };
+    pub const URL_SAFE_NO_PAD: Self = Self {
+        alphabet: Alphabet::UrlSafe,
+        padding: false,
+    };
+}
+
+/// The reason base64 input couldn't be decoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Base64Error {
+    /// The byte at this index isn't part of the alphabet, or is a final character with bits set
+    /// that don't fit in the decoded output.
+    InvalidByte(usize),
+    /// The input length can't be produced by the encoder, including missing padding.
+    InvalidLength,
+}
+
+impl fmt::Display for Base64Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Base64Error::InvalidByte(index) => write!(f, "invalid base64 byte at index {index}"),
+            Base64Error::InvalidLength => write!(f, "invalid base64 input length"),
+        }
+    }
+}
+
+/// The number of characters `encode` writes for `len` input bytes.
+pub fn encoded_len(len: usize, config: Base64Config) -> usize {
+    if config.padding {
+        len.div_ceil(3) * 4
+    } else {
+        len / 3 * 4 + [0, 2, 3][len % 3]
+    }
+}
+
+/// The largest number of bytes `decode` can write for `len` input characters.
+pub fn decoded_len_estimate(len: usize) -> usize {
+    len.div_ceil(4) * 3
+}
+
+fn encode_symbol(value: u8, alphabet: Alphabet) -> u8 {
+    let (char_62, char_63) = alphabet.last_two();
+    match value {
+        0..=25 => b'A' + value,
+        26..=51 => b'a' + value - 26,
+        52..=61 => b'0' + value - 52,
+        62 => char_62,
+        _ => char_63,
+    }
+}
+
+fn decode_symbol(symbol: u8, alphabet: Alphabet) -> Option<u8> {
+    let (char_62, char_63) = alphabet.last_two();
+    match symbol {
+        b'A'..=b'Z' => Some(symbol - b'A'),
+        b'a'..=b'z' => Some(symbol - b'a' + 26),
+        b'0'..=b'9' => Some(symbol - b'0' + 52),
+        _ if symbol == char_62 => Some(62),
+        _ if symbol == char_63 => Some(63),
+        _ => None,
+    }
+}
+
+/// Scalar reference for `encode`.
+fn encode_reference(input: &[u8], output: &mut [u8], config: Base64Config) -> usize {
+    let mut written = 0;
+    for group in input.chunks(3) {
+        let bytes = [
+            group[0],
+            *group.get(1).unwrap_or(&0),
+            *group.get(2).unwrap_or(&0),
+        ];
+        let values = [
+            bytes[0] >> 2,
+            (bytes[0] & 0x03) << 4 | bytes[1] >> 4,
+            (bytes[1] & 0x0F) << 2 | bytes[2] >> 6,
+            bytes[2] & 0x3F,
+        ];
+
+        let symbols = group.len() + 1;
+        for (i, &value) in values.iter().enumerate().take(symbols) {
+            output[written + i] = encode_symbol(value, config.alphabet);
+        }
+        written += symbols;
+        if config.padding {
+            for _ in symbols..4 {
+                output[written] = b'=';
+                written += 1;
+            }
+        }
+    }
+    written
+}
+
+/// Scalar reference for `decode`, after the padding has been removed. `position` is the index of
+/// `input` within the original input, for error reporting.
+fn decode_reference(
+    input: &[u8],
+    output: &mut [u8],
+    alphabet: Alphabet,
+    position: usize,
+) -> Result<usize, Base64Error> {
+    if input.len() % 4 == 1 {
+        return Err(Base64Error::InvalidLength);
+    }
+
+    let mut written = 0;
+    for (i, group) in input.chunks(4).enumerate() {
+        let mut values = [0u8; 4];
+        for (j, &symbol) in group.iter().enumerate() {
+            let index = position + i * 4 + j;
+            values[j] = decode_symbol(symbol, alphabet).ok_or(Base64Error::InvalidByte(index))?;
+        }
+
+        let bytes = [
+            values[0] << 2 | values[1] >> 4,
+            values[1] << 4 | values[2] >> 2,
+            values[2] << 6 | values[3],
+        ];
+        let len = group.len() - 1;
+        output[written..written + len].copy_from_slice(&bytes[..len]);
+        written += len;
+
+        // The bits of a final partial group that don't make up a whole byte must be zero.
+        let leftover = match group.len() {
+            2 => values[1] & 0x0F,
+            3 => values[2] & 0x03,
+            _ => 0,
+        };
+        if leftover != 0 {
+            return Err(Base64Error::InvalidByte(position + i * 4 + group.len() - 1));
+        }
+    }
+    Ok(written)
+}
+
+/// Removes the padding from `input`, checking that it is present exactly when it should be.
+fn strip_padding(input: &[u8], config: Base64Config) -> Result<&[u8], Base64Error> {
+    if !config.padding {
+        return Ok(input);
+    }
+    if !input.len().is_multiple_of(4) {
+        return Err(Base64Error::InvalidLength);
+    }
+    let padding = input
+        .iter()
+        .rev()
+        .take(2)
+        .take_while(|&&b| b == b'=')
+        .count();
+    Ok(&input[..input.len() - padding])
+}
+
+// Permutations that spread each 3 byte group over 4 lanes when encoding. `ENCODE_HIGH` gives the
+// byte holding the high bits of each 6 bit value, `ENCODE_LOW` the one holding its low bits.
+const ENCODE_HIGH: [i8; 32] = lane_table!(|i| (i / 4 * 3 + [0, 0, 1, 2][i % 4]) as i8);
+const ENCODE_LOW: [i8; 32] =
+    lane_table!(|i| [-1, (i / 4 * 3 + 1) as i8, (i / 4 * 3 + 2) as i8, -1][i % 4]);
+const LANE_MOD_4: [i8; 32] = lane_table!(|i| (i % 4) as i8);
+
+// Permutations that gather the two 6 bit values making up each decoded byte. Lanes past the
+// decoded bytes are unused.
+const DECODE_HIGH: [i8; 32] = lane_table!(|i| if i < 24 {
+    (i / 3 * 4 + i % 3) as i8
+} else {
+    -1
+});
+const DECODE_LOW: [i8; 32] = lane_table!(|i| if i < 24 {
+    (i / 3 * 4 + i % 3 + 1) as i8
+} else {
+    -1
+});
+const LANE_MOD_3: [i8; 32] = lane_table!(|i| (i % 3) as i8);
+
+/// Maps 6 bit values to their characters.
+#[inline(always)]
+fn encode_symbols<S: Simd>(values: S::Vi8, alphabet: Alphabet) -> S::Vi8 {
+    let (char_62, char_63) = alphabet.last_two();
+    let set1 = |value: i32| S::Vi8::set1(value as i8);
+
+    // Start from the offset of the uppercase letters, and adjust it for each later range.
+    let mut offset = set1(b'A' as i32);
+    offset += values.cmp_gt(set1(25)) & (b'a' as i32 - 26 - b'A' as i32) as i8;
+    offset += values.cmp_gt(set1(51)) & (b'0' as i32 - 52 - (b'a' as i32 - 26)) as i8;
+    offset += values.cmp_eq(set1(62)) & (char_62 as i32 - 62 - (b'0' as i32 - 52)) as i8;
+    offset += values.cmp_eq(set1(63)) & (char_63 as i32 - 63 - (b'0' as i32 - 52)) as i8;
+    values + offset
+}
+
+/// Maps characters to their 6 bit values, also returning a mask of the characters that aren't
+/// in the alphabet.
+#[inline(always)]
+fn decode_symbols<S: Simd>(symbols: S::Vi8, alphabet: Alphabet) -> (S::Vi8, S::Vi8) {
+    let (char_62, char_63) = alphabet.last_two();
+    let set1 = |value: u8| S::Vi8::set1(value as i8);
+    let in_range = |low: u8, high: u8| symbols.cmp_gte(set1(low)) & symbols.cmp_lte(set1(high));
+
+    let upper = in_range(b'A', b'Z');
+    let lower = in_range(b'a', b'z');
+    let digit = in_range(b'0', b'9');
+    let is_62 = symbols.cmp_eq(set1(char_62));
+    let is_63 = symbols.cmp_eq(set1(char_63));
+
+    let offset = (upper & (-(b'A' as i32)) as i8)
+        | (lower & (26 - b'a' as i32) as i8)
+        | (digit & (52 - b'0' as i32) as i8)
+        | (is_62 & (62 - char_62 as i32) as i8)
+        | (is_63 & (63 - char_63 as i32) as i8);
+    let valid = upper | lower | digit | is_62 | is_63;
+    (symbols + offset, valid.cmp_eq(S::Vi8::zeroes()))
+}
+
+/// Encodes `input` into `output`, returning the number of characters written.
+/// Panics if `output` is shorter than `encoded_len(input.len(), config)`.
+#[inline(always)]
+pub fn encode<S: Simd>(input: &[u8], output: &mut [u8], config: Base64Config) -> usize {
+    assert!(output.len() >= encoded_len(input.len(), config));
+    let width = S::Vi8::WIDTH;
+
+    let mut read = 0;
+    let mut written = 0;
+    if width.is_multiple_of(4) {
+        let high = load_lane_table::<S>(&ENCODE_HIGH);
+        let low = load_lane_table::<S>(&ENCODE_LOW);
+        let lane = load_lane_table::<S>(&LANE_MOD_4);
+
+        // Each step reads 3/4 of a vector, but loads a whole one.
+        while read + width <= input.len() {
+            let bytes =
+                unsafe { S::Vi8::load_from_ptr_unaligned(input.as_ptr().add(read) as *const i8) };
+            let high = bytes.permute(high);
+            let low = bytes.permute(low);
+
+            let mut values = high.shr_const::<2>();
+            let second = (high.shl_const::<4>() | low.shr_const::<4>()) & 0x3F;
+            values = lane.cmp_eq(S::Vi8::set1(1)).blendv(values, second);
+            let third = (high.shl_const::<2>() | low.shr_const::<6>()) & 0x3F;
+            values = lane.cmp_eq(S::Vi8::set1(2)).blendv(values, third);
+            values = lane.cmp_eq(S::Vi8::set1(3)).blendv(values, high & 0x3F);
+
+            let symbols = encode_symbols::<S>(values, config.alphabet);
+            unsafe { symbols.copy_to_ptr_unaligned(output.as_mut_ptr().add(written) as *mut i8) };
+            read += width / 4 * 3;
+            written += width;
+        }
+    }
+
+    written + encode_reference(&input[read..], &mut output[written..], config)
+}
+
+/// Decodes `input` into `output`, returning the number of bytes written.
+/// Panics if `output` is shorter than `decoded_len_estimate(input.len())`.
+#[inline(always)]
+pub fn decode<S: Simd>(
+    input: &[u8],
+    output: &mut [u8],
+    config: Base64Config,
+) -> Result<usize, Base64Error> {
+    assert!(output.len() >= decoded_len_estimate(input.len()));
+    let data = strip_padding(input, config)?;
+    let width = S::Vi8::WIDTH;
+
+    let mut read = 0;
+    let mut written = 0;
+    if width.is_multiple_of(4) {
+        let high = load_lane_table::<S>(&DECODE_HIGH);
+        let low = load_lane_table::<S>(&DECODE_LOW);
+        let lane = load_lane_table::<S>(&LANE_MOD_3);
+
+        // Each step writes 3/4 of a vector, but stores a whole one.
+        while read + width <= data.len() && written + width <= output.len() {
+            let symbols =
+                unsafe { S::Vi8::load_from_ptr_unaligned(data.as_ptr().add(read) as *const i8) };
+            let (values, invalid) = decode_symbols::<S>(symbols, config.alphabet);
+            if let Some(index) = invalid.index_of_first_truthy() {
+                return Err(Base64Error::InvalidByte(read + index));
+            }
+
+            let high = values.permute(high);
+            let low = values.permute(low);
+            let mut bytes = high.shl_const::<2>() | low.shr_const::<4>();
+            let second = high.shl_const::<4>() | low.shr_const::<2>();
+            bytes = lane.cmp_eq(S::Vi8::set1(1)).blendv(bytes, second);
+            let third = high.shl_const::<6>() | low;
+            bytes = lane.cmp_eq(S::Vi8::set1(2)).blendv(bytes, third);
+
+            unsafe { bytes.copy_to_ptr_unaligned(output.as_mut_ptr().add(written) as *mut i8) };
+            read += width;
+            written += width / 4 * 3;
+        }
+    }
+
+    let rest = decode_reference(&data[read..], &mut output[written..], config.alphabet, read)?;
+    Ok(written + rest)
+}
@@ -0,0 +1,217 @@
+//! Complex numbers in split form, with the real and imaginary parts of `WIDTH` numbers held in two
+//! separate vectors.
+//!
+//! The `load_interleaved` and `store_interleaved` functions convert from and to the usual layout of
+//! consecutive `[re, im]` pairs, and take the engine as a generic parameter like the other
+//! [slice algorithms](crate#slice-algorithms).
+
+use core::fmt;
+use core::ops::{Add, Mul, Neg, Sub};
+
+use crate::interleave::{load_interleaved, store_interleaved};
+use crate::{Simd, SimdBaseIo, SimdBaseOps, SimdFloat};
+
+/// Returns `atan(x)` for `x` between 0 and 1, reduced to at most `tan(pi/8)` around 0 and
+/// approximated with the single precision polynomial from the Cephes library.
+#[inline(always)]
+fn atan_unit_f32<V: SimdFloat<Scalar = f32>>(x: V) -> V {
+    let one = V::set1(1.0);
+    let reduce = x.cmp_gt(V::set1(0.414_213_57));
+    let x = reduce.blendv(x, (x - one) / (x + one));
+    let offset = reduce.blendv(V::zeroes(), V::set1(core::f32::consts::FRAC_PI_4));
+
+    let z = x * x;
+    let polynomial = V::set1(8.053_744_5e-2)
+        .mul_add(z, V::set1(-1.387_768_6e-1))
+        .mul_add(z, V::set1(1.997_771_1e-1))
+        .mul_add(z, V::set1(-3.333_295e-1));
+    (polynomial * z).mul_add(x, x) + offset
+}
+
+/// Returns `atan(x)` for `x` between 0 and 1, reduced to at most 0.66 around 0 and approximated with
+/// the double precision rational function from the Cephes library.
+#[inline(always)]
+fn atan_unit_f64<V: SimdFloat<Scalar = f64>>(x: V) -> V {
+    const P: [f64; 5] = [
+        -8.750_608_600_031_904e-1,
+        -1.615_753_718_733_365e1,
+        -7.500_855_792_314_705e1,
+        -1.228_866_684_490_136_2e2,
+        -6.485_021_904_942_025e1,
+    ];
+    const Q: [f64; 5] = [
+        2.485_846_490_142_306e1,
+        1.650_270_098_316_988_6e2,
+        4.328_810_604_912_903e2,
+        4.853_903_996_359_137e2,
+        1.945_506_571_482_614e2,
+    ];
+
+    let one = V::set1(1.0);
+    let reduce = x.cmp_gt(V::set1(0.66));
+    let x = reduce.blendv(x, (x - one) / (x + one));
+    let offset = reduce.blendv(V::zeroes(), V::set1(core::f64::consts::FRAC_PI_4));
+
+    let z = x * x;
+    let p = P[1..]
+        .iter()
+        .fold(V::set1(P[0]), |acc, &c| acc.mul_add(z, V::set1(c)));
+    let q = Q.iter().fold(one, |acc, &c| acc.mul_add(z, V::set1(c)));
+    (z * p / q).mul_add(x, x) + offset
+}
+
+macro_rules! impl_complex {
+    ($name:ident, $ty:ident, $vector:ident, $atan_unit:ident) => {
+        #[doc = concat!("A batch of `", stringify!($ty), "` complex numbers, as `S::", stringify!($vector), "::WIDTH` real parts and imaginary parts.")]
+        pub struct $name<S: Simd> {
+            pub re: S::$vector,
+            pub im: S::$vector,
+        }
+
+        impl<S: Simd> Clone for $name<S> {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        impl<S: Simd> Copy for $name<S> {}
+
+        impl<S: Simd> fmt::Debug for $name<S> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_struct(stringify!($name))
+                    .field("re", &self.re)
+                    .field("im", &self.im)
+                    .finish()
+            }
+        }
+
+        impl<S: Simd> $name<S> {
+            #[inline(always)]
+            pub fn new(re: S::$vector, im: S::$vector) -> Self {
+                Self { re, im }
+            }
+
+            /// Sets every lane to the same complex number.
+            #[inline(always)]
+            pub fn set1(re: $ty, im: $ty) -> Self {
+                Self::new(S::$vector::set1(re), S::$vector::set1(im))
+            }
+
+            #[inline(always)]
+            pub fn zeroes() -> Self {
+                Self::new(S::$vector::zeroes(), S::$vector::zeroes())
+            }
+
+            /// Loads `WIDTH` complex numbers stored as `[re, im]` pairs.
+            /// Panics if `data` is shorter than `2 * WIDTH`.
+            #[inline(always)]
+            pub fn load_interleaved(data: &[$ty]) -> Self {
+                let [re, im] = load_interleaved::<S::$vector, 2>(data);
+                Self { re, im }
+            }
+
+            /// Stores the complex numbers as `[re, im]` pairs.
+            /// Panics if `data` is shorter than `2 * WIDTH`.
+            #[inline(always)]
+            pub fn store_interleaved(&self, data: &mut [$ty]) {
+                store_interleaved::<S::$vector, 2>(&[self.re, self.im], data);
+            }
+
+            #[inline(always)]
+            pub fn conj(self) -> Self {
+                Self::new(self.re, -self.im)
+            }
+
+            /// Returns the squared magnitudes, `re * re + im * im`.
+            #[inline(always)]
+            pub fn norm_sqr(self) -> S::$vector {
+                self.re.mul_add(self.re, self.im * self.im)
+            }
+
+            /// Returns the magnitudes. Unlike `hypot`, this overflows for parts above the square
+            /// root of the largest float.
+            #[inline(always)]
+            pub fn abs(self) -> S::$vector {
+                self.norm_sqr().sqrt()
+            }
+
+            /// Returns the arguments, i.e. the angles from the positive real axis, between `-pi`
+            /// and `pi`, like `atan2(im, re)`, including for signed zeros.
+            #[inline(always)]
+            pub fn arg(self) -> S::$vector {
+                let zero = S::$vector::zeroes();
+                let (x, y) = (self.re.abs(), self.im.abs());
+                let (low, high) = (x.min(y), x.max(y));
+
+                // Reduce to an angle between 0 and pi/4, then mirror it into the right octant.
+                let ratio = high.cmp_eq(zero).blendv(low / high, zero);
+                let angle = $atan_unit(ratio);
+                let angle = y
+                    .cmp_gt(x)
+                    .blendv(angle, S::$vector::set1(core::$ty::consts::FRAC_PI_2) - angle);
+                // Going by the sign bits, rather than comparing with zero, sends `-0.0` to the same
+                // side as `atan2` does.
+                let sign = S::$vector::set1(-0.0);
+                let angle = (self.re & sign)
+                    .blendv(angle, S::$vector::set1(core::$ty::consts::PI) - angle);
+                angle ^ (self.im & sign)
+            }
+
+            /// Returns `self * a + b`, with the real products fused into multiply-adds.
+            #[inline(always)]
+            pub fn mul_add(self, a: Self, b: Self) -> Self {
+                Self::new(
+                    self.re.mul_add(a.re, self.im.neg_mul_add(a.im, b.re)),
+                    self.re.mul_add(a.im, self.im.mul_add(a.re, b.im)),
+                )
+            }
+
+            /// Multiplies every number by a real factor.
+            #[inline(always)]
+            pub fn scale(self, factor: S::$vector) -> Self {
+                Self::new(self.re * factor, self.im * factor)
+            }
+        }
+
+        impl<S: Simd> Add for $name<S> {
+            type Output = Self;
+
+            #[inline(always)]
+            fn add(self, rhs: Self) -> Self {
+                Self::new(self.re + rhs.re, self.im + rhs.im)
+            }
+        }
+
+        impl<S: Simd> Sub for $name<S> {
+            type Output = Self;
+
+            #[inline(always)]
+            fn sub(self, rhs: Self) -> Self {
+                Self::new(self.re - rhs.re, self.im - rhs.im)
+            }
+        }
+
+        impl<S: Simd> Mul for $name<S> {
+            type Output = Self;
+
+            #[inline(always)]
+            fn mul(self, rhs: Self) -> Self {
+                Self::new(
+                    self.re.mul_sub(rhs.re, self.im * rhs.im),
+                    self.re.mul_add(rhs.im, self.im * rhs.re),
+                )
+            }
+        }
+
+        impl<S: Simd> Neg for $name<S> {
+            type Output = Self;
+
+            #[inline(always)]
+            fn neg(self) -> Self {
+                Self::new(-self.re, -self.im)
+            }
+        }
+    };
+}
+impl_complex!(ComplexF32, f32, Vf32, atan_unit_f32);
+impl_complex!(ComplexF64, f64, Vf64, atan_unit_f64);
@@ -0,0 +1,182 @@
+//! The engines runtime dispatch can pick from, and a process-wide limit on which ones it may use.
+
+use core::sync::atomic::{AtomicU8, Ordering};
+
+use crate::Simd;
+use crate::engines::{avx2::Avx2, scalar::Scalar};
+
+/// The engines, ordered from the lowest tier to the highest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(u8)]
+pub enum EngineKind {
+    Scalar = 1,
+    Avx2 = 2,
+}
+
+impl EngineKind {
+    /// Every engine, from the lowest tier to the highest.
+    pub const ALL: [EngineKind; 2] = [EngineKind::Scalar, EngineKind::Avx2];
+
+    /// The name of the engine, as accepted by `from_name`.
+    pub const fn name(self) -> &'static str {
+        match self {
+            EngineKind::Scalar => Scalar::NAME,
+            EngineKind::Avx2 => Avx2::NAME,
+        }
+    }
+
+    /// The width of the vector registers of the engine in bits, see `Simd::REGISTER_BITS`.
+    pub const fn register_bits(self) -> usize {
+        match self {
+            EngineKind::Scalar => Scalar::REGISTER_BITS,
+            EngineKind::Avx2 => Avx2::REGISTER_BITS,
+        }
+    }
+
+    /// The target features the engine needs, see `Simd::REQUIRED_FEATURES`.
+    pub const fn required_features(self) -> &'static [&'static str] {
+        match self {
+            EngineKind::Scalar => Scalar::REQUIRED_FEATURES,
+            EngineKind::Avx2 => Avx2::REQUIRED_FEATURES,
+        }
+    }
+
+    /// Runs `runner` with this engine, like `dispatch!`, which doesn't need a type for the body.
+    #[inline(always)]
+    pub fn run<R: EngineRunner>(self, runner: R) -> R::Output {
+        crate::dispatch!(self, |S| runner.run::<S>())
+    }
+
+    /// Whether the CPU supports the engine, whether or not the build enables its target features.
+    pub fn is_available(self) -> bool {
+        match self {
+            EngineKind::Scalar => true,
+            EngineKind::Avx2 => avx2_available(),
+        }
+    }
+
+    /// Parses an engine name, ignoring ASCII case and surrounding whitespace.
+    pub fn from_name(name: &str) -> Option<EngineKind> {
+        let name = name.trim();
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.name().eq_ignore_ascii_case(name))
+    }
+
+    const fn from_u8(value: u8) -> Option<EngineKind> {
+        match value {
+            1 => Some(EngineKind::Scalar),
+            2 => Some(EngineKind::Avx2),
+            _ => None,
+        }
+    }
+}
+
+/// A body generic over the engine, for `EngineKind::run`. The fields hold what it captures.
+pub trait EngineRunner {
+    type Output;
+
+    /// Runs the body with `S`, inside `S::invoke`. Mark it `#[inline(always)]` so that it's compiled
+    /// with the target features of the engine.
+    fn run<S: Simd>(self) -> Self::Output;
+}
+
+#[cfg(all(feature = "std", target_arch = "x86_64"))]
+fn avx2_available() -> bool {
+    std::is_x86_feature_detected!("avx2") && std::is_x86_feature_detected!("fma")
+}
+
+/// Queries the CPU directly, as `is_x86_feature_detected!` needs `std`.
+#[cfg(all(not(feature = "std"), target_arch = "x86_64"))]
+fn avx2_available() -> bool {
+    use core::arch::x86_64::{__cpuid, __cpuid_count, _xgetbv};
+
+    const FMA: u32 = 1 << 12;
+    const OSXSAVE: u32 = 1 << 27;
+    const AVX: u32 = 1 << 28;
+    const AVX2: u32 = 1 << 5;
+    // The bits of XCR0 telling that the OS saves the SSE and AVX registers.
+    const XMM_YMM_STATE: u64 = 0b110;
+
+    if __cpuid(0).eax < 7 {
+        return false;
+    }
+    let ecx = __cpuid(1).ecx;
+    if ecx & (FMA | OSXSAVE | AVX) != FMA | OSXSAVE | AVX {
+        return false;
+    }
+    // `xgetbv` is available once the OS enabled it, which the OSXSAVE bit tells.
+    let xcr0 = unsafe { _xgetbv(0) };
+    xcr0 & XMM_YMM_STATE == XMM_YMM_STATE && __cpuid_count(7, 0).ebx & AVX2 != 0
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+fn avx2_available() -> bool {
+    false
+}
+
+/// The engines the CPU supports, from the lowest tier to the highest.
+pub fn available_engines() -> impl Iterator<Item = EngineKind> {
+    EngineKind::ALL
+        .into_iter()
+        .filter(|kind| kind.is_available())
+}
+
+/// The highest engine the CPU supports, which runtime dispatch uses unless it is limited with
+/// `set_max_engine`.
+pub fn best_engine() -> EngineKind {
+    available_engines().max().unwrap_or(EngineKind::Scalar)
+}
+
+/// The highest engine runtime dispatch may use, or 0 if it hasn't been decided yet.
+static MAX_ENGINE: AtomicU8 = AtomicU8::new(0);
+
+/// The name of the environment variable that sets the initial value of `max_engine`.
+pub const ENGINE_ENV_VAR: &str = "SIMDEEZ_ENGINE";
+
+/// Limits the engine functions generated by `simd_runtime_generate!` dispatch to, for every thread
+/// in the process. Engines above `kind` or the `best_engine` fall back to the best one at or below
+/// both, so `set_max_engine(EngineKind::Scalar)` forces the scalar engine everywhere.
+///
+/// This takes precedence over the `SIMDEEZ_ENGINE` environment variable, and can be called again
+/// at any time, e.g. to compare engines in the same benchmark. Function pointers returned by the
+/// `_resolve` functions keep the engine they were resolved with.
+pub fn set_max_engine(kind: EngineKind) {
+    MAX_ENGINE.store(kind.min(best_engine()) as u8, Ordering::Relaxed);
+    crate::invoking::clear_runner_caches();
+}
+
+/// Returns the highest engine runtime dispatch may use.
+///
+/// Unless `set_max_engine` was called, this is read once from the `SIMDEEZ_ENGINE` environment
+/// variable with the `std` feature, e.g. `SIMDEEZ_ENGINE=scalar`. Unknown names are ignored, and the
+/// default is the `best_engine`.
+#[inline(always)]
+pub fn max_engine() -> EngineKind {
+    match EngineKind::from_u8(MAX_ENGINE.load(Ordering::Relaxed)) {
+        Some(kind) => kind,
+        None => init_max_engine(),
+    }
+}
+
+#[cold]
+fn init_max_engine() -> EngineKind {
+    let best = best_engine();
+    let kind = env_max_engine().map_or(best, |kind| kind.min(best));
+    // Keep the value of a racing `set_max_engine`.
+    match MAX_ENGINE.compare_exchange(0, kind as u8, Ordering::Relaxed, Ordering::Relaxed) {
+        Ok(_) => kind,
+        Err(current) => EngineKind::from_u8(current).unwrap_or(kind),
+    }
+}
+
+#[cfg(feature = "std")]
+fn env_max_engine() -> Option<EngineKind> {
+    let name = std::env::var(ENGINE_ENV_VAR).ok()?;
+    EngineKind::from_name(&name)
+}
+
+#[cfg(not(feature = "std"))]
+fn env_max_engine() -> Option<EngineKind> {
+    None
+}
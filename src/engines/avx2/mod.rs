@@ -23,7 +23,7 @@ impl_i32_simd_type!(Avx2, I32x8, F32x8, I64x4);
 
 define_simd_type!(Avx2, i64, 4, __m256i);
 impl_simd_int_overloads!(I64x4);
-impl_i64_simd_type!(Avx2, I64x4, F64x4);
+impl_i64_simd_type!(Avx2, I64x4, F64x4, I32x8);
 
 define_simd_type!(Avx2, f32, 8, __m256);
 impl_simd_float_overloads!(F32x8);
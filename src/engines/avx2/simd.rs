@@ -12,6 +12,10 @@ impl Simd for Avx2 {
     type Vf64 = F64x4;
     type Vi64 = I64x4;
 
+    const NAME: &'static str = "avx2";
+    const REGISTER_BITS: usize = 256;
+    const REQUIRED_FEATURES: &'static [&'static str] = &["avx2", "fma"];
+
     #[inline]
     fn invoke<R>(f: impl FnOnce() -> R) -> R {
         #[inline]
@@ -20,7 +20,7 @@ impl_i32_simd_type!(Scalar, I32x1, F32x1, I64x1);
 
 define_simd_type!(Scalar, i64, 1, i64);
 impl_simd_int_overloads!(I64x1);
-impl_i64_simd_type!(Scalar, I64x1, F64x1);
+impl_i64_simd_type!(Scalar, I64x1, F64x1, I32x1);
 
 define_simd_type!(Scalar, f32, 1, f32);
 impl_simd_float_overloads!(F32x1);
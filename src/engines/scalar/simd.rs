@@ -10,6 +10,10 @@ impl Simd for Scalar {
     type Vf64 = F64x1;
     type Vi64 = I64x1;
 
+    const NAME: &'static str = "scalar";
+    const REGISTER_BITS: usize = 64;
+    const REQUIRED_FEATURES: &'static [&'static str] = &[];
+
     #[inline]
     fn invoke<R>(f: impl FnOnce() -> R) -> R {
         #[inline]
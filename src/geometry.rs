@@ -0,0 +1,461 @@
+//! 4x4 matrices, quaternions and 3D vectors for game and graphics math, in SoA form.
+//!
+//! Every type holds `S::Vf32::WIDTH` values at once, with one vector per component, so every
+//! operation works on a whole batch of matrices, quaternions or points. The `load_interleaved` and
+//! `store_interleaved` functions convert from and to the usual array-of-structs layout, where every
+//! value is stored as consecutive `f32`s.
+//!
+//! Matrices are row-major and transform column vectors, so `a.mul(&b)` applies `b` first. The
+//! batches are generic over the engine, like the other [slice algorithms](crate#slice-algorithms).
+
+use core::fmt;
+
+use crate::interleave::{load_interleaved, store_interleaved};
+use crate::{Simd, SimdBaseIo, SimdBaseOps, SimdFloat};
+
+/// A batch of 3D points or directions.
+pub struct Vec3<S: Simd> {
+    pub x: S::Vf32,
+    pub y: S::Vf32,
+    pub z: S::Vf32,
+}
+
+impl<S: Simd> Clone for Vec3<S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S: Simd> Copy for Vec3<S> {}
+
+impl<S: Simd> fmt::Debug for Vec3<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Vec3")
+            .field("x", &self.x)
+            .field("y", &self.y)
+            .field("z", &self.z)
+            .finish()
+    }
+}
+
+impl<S: Simd> Vec3<S> {
+    /// Loads `S::Vf32::WIDTH` vectors stored as `x, y, z` triples.
+    #[inline(always)]
+    pub fn load_interleaved(data: &[f32]) -> Self {
+        let [x, y, z] = load_interleaved::<S::Vf32, 3>(data);
+        Self { x, y, z }
+    }
+
+    /// Stores the vectors as `x, y, z` triples.
+    #[inline(always)]
+    pub fn store_interleaved(&self, data: &mut [f32]) {
+        store_interleaved::<S::Vf32, 3>(&[self.x, self.y, self.z], data);
+    }
+
+    #[inline(always)]
+    pub fn dot(&self, other: &Self) -> S::Vf32 {
+        self.x
+            .mul_add(other.x, self.y.mul_add(other.y, self.z * other.z))
+    }
+
+    #[inline(always)]
+    pub fn cross(&self, other: &Self) -> Self {
+        Self {
+            x: self.y.mul_sub(other.z, self.z * other.y),
+            y: self.z.mul_sub(other.x, self.x * other.z),
+            z: self.x.mul_sub(other.y, self.y * other.x),
+        }
+    }
+}
+
+/// A batch of 4x4 matrices. `rows[r][c]` holds the element in row `r` and column `c` of every
+/// matrix.
+pub struct Mat4<S: Simd> {
+    pub rows: [[S::Vf32; 4]; 4],
+}
+
+impl<S: Simd> Clone for Mat4<S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S: Simd> Copy for Mat4<S> {}
+
+impl<S: Simd> fmt::Debug for Mat4<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Mat4").field("rows", &self.rows).finish()
+    }
+}
+
+impl<S: Simd> Mat4<S> {
+    #[inline(always)]
+    pub fn identity() -> Self {
+        Self {
+            rows: core::array::from_fn(|r| {
+                core::array::from_fn(|c| S::Vf32::set1(if r == c { 1.0 } else { 0.0 }))
+            }),
+        }
+    }
+
+    /// Loads `S::Vf32::WIDTH` matrices, each stored as 16 floats in row-major order.
+    #[inline(always)]
+    pub fn load_interleaved(data: &[f32]) -> Self {
+        let m = load_interleaved::<S::Vf32, 16>(data);
+        Self {
+            rows: core::array::from_fn(|r| core::array::from_fn(|c| m[r * 4 + c])),
+        }
+    }
+
+    /// Stores the matrices as 16 floats each, in row-major order.
+    #[inline(always)]
+    pub fn store_interleaved(&self, data: &mut [f32]) {
+        let m = core::array::from_fn(|i| self.rows[i / 4][i % 4]);
+        store_interleaved::<S::Vf32, 16>(&m, data);
+    }
+
+    /// Returns the matrix product `self * rhs`.
+    #[inline(always)]
+    pub fn mul(&self, rhs: &Self) -> Self {
+        let (a, b) = (&self.rows, &rhs.rows);
+        Self {
+            rows: core::array::from_fn(|r| {
+                core::array::from_fn(|c| {
+                    let sum = a[r][2].mul_add(b[2][c], a[r][3] * b[3][c]);
+                    a[r][0].mul_add(b[0][c], a[r][1].mul_add(b[1][c], sum))
+                })
+            }),
+        }
+    }
+
+    #[inline(always)]
+    pub fn transpose(&self) -> Self {
+        Self {
+            rows: core::array::from_fn(|r| core::array::from_fn(|c| self.rows[c][r])),
+        }
+    }
+
+    /// Returns the 2x2 determinants of the top two rows and bottom two rows that both the
+    /// determinant and the inverse are built from, indexed by pairs of columns.
+    #[inline(always)]
+    fn minors(&self) -> ([S::Vf32; 6], [S::Vf32; 6]) {
+        let m = &self.rows;
+        let det2 = |r0: usize, r1: usize, c0: usize, c1: usize| {
+            m[r0][c0].mul_sub(m[r1][c1], m[r1][c0] * m[r0][c1])
+        };
+        let pairs = [(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)];
+        (
+            pairs.map(|(c0, c1)| det2(0, 1, c0, c1)),
+            pairs.map(|(c0, c1)| det2(2, 3, c0, c1)),
+        )
+    }
+
+    #[inline(always)]
+    fn determinant_from_minors(s: &[S::Vf32; 6], c: &[S::Vf32; 6]) -> S::Vf32 {
+        s[0] * c[5] - s[1] * c[4] + s[2] * c[3] + s[3] * c[2] - s[4] * c[1] + s[5] * c[0]
+    }
+
+    #[inline(always)]
+    pub fn determinant(&self) -> S::Vf32 {
+        let (s, c) = self.minors();
+        Self::determinant_from_minors(&s, &c)
+    }
+
+    /// Returns the inverse of every matrix. The lanes of singular matrices hold infinities or NaNs,
+    /// which can be detected with `determinant`.
+    #[inline(always)]
+    pub fn inverse(&self) -> Self {
+        let m = &self.rows;
+        let (s, c) = self.minors();
+        let inv_det = S::Vf32::set1(1.0) / Self::determinant_from_minors(&s, &c);
+
+        // Every element is a cofactor, expanded along the row or column it doesn't share with
+        // the minors above.
+        let cofactors = [
+            [
+                m[1][1] * c[5] - m[1][2] * c[4] + m[1][3] * c[3],
+                -m[0][1] * c[5] + m[0][2] * c[4] - m[0][3] * c[3],
+                m[3][1] * s[5] - m[3][2] * s[4] + m[3][3] * s[3],
+                -m[2][1] * s[5] + m[2][2] * s[4] - m[2][3] * s[3],
+            ],
+            [
+                -m[1][0] * c[5] + m[1][2] * c[2] - m[1][3] * c[1],
+                m[0][0] * c[5] - m[0][2] * c[2] + m[0][3] * c[1],
+                -m[3][0] * s[5] + m[3][2] * s[2] - m[3][3] * s[1],
+                m[2][0] * s[5] - m[2][2] * s[2] + m[2][3] * s[1],
+            ],
+            [
+                m[1][0] * c[4] - m[1][1] * c[2] + m[1][3] * c[0],
+                -m[0][0] * c[4] + m[0][1] * c[2] - m[0][3] * c[0],
+                m[3][0] * s[4] - m[3][1] * s[2] + m[3][3] * s[0],
+                -m[2][0] * s[4] + m[2][1] * s[2] - m[2][3] * s[0],
+            ],
+            [
+                -m[1][0] * c[3] + m[1][1] * c[1] - m[1][2] * c[0],
+                m[0][0] * c[3] - m[0][1] * c[1] + m[0][2] * c[0],
+                -m[3][0] * s[3] + m[3][1] * s[1] - m[3][2] * s[0],
+                m[2][0] * s[3] - m[2][1] * s[1] + m[2][2] * s[0],
+            ],
+        ];
+        Self {
+            rows: cofactors.map(|row| row.map(|cofactor| cofactor * inv_det)),
+        }
+    }
+
+    /// Transforms points, treating them as having a `w` of 1. The resulting `w` is dropped
+    /// without a perspective divide, which is exact for affine transforms.
+    #[inline(always)]
+    pub fn transform_points(&self, points: &Vec3<S>) -> Vec3<S> {
+        let row = |r: [S::Vf32; 4]| {
+            r[0].mul_add(
+                points.x,
+                r[1].mul_add(points.y, r[2].mul_add(points.z, r[3])),
+            )
+        };
+        Vec3 {
+            x: row(self.rows[0]),
+            y: row(self.rows[1]),
+            z: row(self.rows[2]),
+        }
+    }
+
+    /// Transforms directions, treating them as having a `w` of 0, so translations don't apply.
+    #[inline(always)]
+    pub fn transform_vectors(&self, vectors: &Vec3<S>) -> Vec3<S> {
+        let row =
+            |r: [S::Vf32; 4]| r[0].mul_add(vectors.x, r[1].mul_add(vectors.y, r[2] * vectors.z));
+        Vec3 {
+            x: row(self.rows[0]),
+            y: row(self.rows[1]),
+            z: row(self.rows[2]),
+        }
+    }
+}
+
+/// A batch of quaternions, with `w` as the real part. Rotations are unit quaternions.
+pub struct Quat<S: Simd> {
+    pub x: S::Vf32,
+    pub y: S::Vf32,
+    pub z: S::Vf32,
+    pub w: S::Vf32,
+}
+
+impl<S: Simd> Clone for Quat<S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S: Simd> Copy for Quat<S> {}
+
+impl<S: Simd> fmt::Debug for Quat<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Quat")
+            .field("x", &self.x)
+            .field("y", &self.y)
+            .field("z", &self.z)
+            .field("w", &self.w)
+            .finish()
+    }
+}
+
+/// Approximates `acos(x)` for `x` between 0 and 1, with an error below 1e-7 (Abramowitz and
+/// Stegun 4.4.46).
+#[inline(always)]
+fn acos_unit<V: SimdFloat<Scalar = f32>>(x: V) -> V {
+    const COEFFICIENTS: [f32; 8] = [
+        -0.001_262_491_1,
+        0.006_670_09,
+        -0.017_088_126,
+        0.030_891_88,
+        -0.050_174_303,
+        0.088_978_99,
+        -0.214_598_8,
+        core::f32::consts::FRAC_PI_2,
+    ];
+    let polynomial = COEFFICIENTS[1..]
+        .iter()
+        .fold(V::set1(COEFFICIENTS[0]), |acc, &c| {
+            acc.mul_add(x, V::set1(c))
+        });
+    (V::set1(1.0) - x).sqrt() * polynomial
+}
+
+/// Approximates `sin(x)` for `x` between 0 and pi/2 with its Taylor series, whose error there is
+/// below 1e-7.
+#[inline(always)]
+fn sin_quarter<V: SimdFloat<Scalar = f32>>(x: V) -> V {
+    const COEFFICIENTS: [f32; 6] = [
+        -1.0 / 39_916_800.0,
+        1.0 / 362_880.0,
+        -1.0 / 5040.0,
+        1.0 / 120.0,
+        -1.0 / 6.0,
+        1.0,
+    ];
+    let x2 = x * x;
+    let polynomial = COEFFICIENTS[1..]
+        .iter()
+        .fold(V::set1(COEFFICIENTS[0]), |acc, &c| {
+            acc.mul_add(x2, V::set1(c))
+        });
+    x * polynomial
+}
+
+impl<S: Simd> Quat<S> {
+    #[inline(always)]
+    pub fn identity() -> Self {
+        let zero = S::Vf32::zeroes();
+        Self {
+            x: zero,
+            y: zero,
+            z: zero,
+            w: S::Vf32::set1(1.0),
+        }
+    }
+
+    /// Loads `S::Vf32::WIDTH` quaternions stored as `x, y, z, w`.
+    #[inline(always)]
+    pub fn load_interleaved(data: &[f32]) -> Self {
+        let [x, y, z, w] = load_interleaved::<S::Vf32, 4>(data);
+        Self { x, y, z, w }
+    }
+
+    /// Stores the quaternions as `x, y, z, w`.
+    #[inline(always)]
+    pub fn store_interleaved(&self, data: &mut [f32]) {
+        store_interleaved::<S::Vf32, 4>(&[self.x, self.y, self.z, self.w], data);
+    }
+
+    /// Returns the rotations of `angle` radians around `axis`, which must be normalized.
+    /// `sin_half` and `cos_half` are the sine and cosine of half the angles.
+    #[inline(always)]
+    pub fn from_axis_angle(axis: &Vec3<S>, sin_half: S::Vf32, cos_half: S::Vf32) -> Self {
+        Self {
+            x: axis.x * sin_half,
+            y: axis.y * sin_half,
+            z: axis.z * sin_half,
+            w: cos_half,
+        }
+    }
+
+    #[inline(always)]
+    pub fn dot(&self, other: &Self) -> S::Vf32 {
+        let xy = self.x.mul_add(other.x, self.y * other.y);
+        self.z.mul_add(other.z, self.w.mul_add(other.w, xy))
+    }
+
+    /// Returns the Hamilton product `self * rhs`, which as a rotation applies `rhs` first.
+    #[inline(always)]
+    pub fn mul(&self, rhs: &Self) -> Self {
+        let (a, b) = (self, rhs);
+        Self {
+            x: a.w
+                .mul_add(b.x, a.x.mul_add(b.w, a.y.mul_sub(b.z, a.z * b.y))),
+            y: a.w
+                .mul_add(b.y, a.y.mul_add(b.w, a.z.mul_sub(b.x, a.x * b.z))),
+            z: a.w
+                .mul_add(b.z, a.z.mul_add(b.w, a.x.mul_sub(b.y, a.y * b.x))),
+            w: a.w
+                .mul_sub(b.w, a.x.mul_add(b.x, a.y.mul_add(b.y, a.z * b.z))),
+        }
+    }
+
+    /// Returns the conjugate, which is the inverse rotation for unit quaternions.
+    #[inline(always)]
+    pub fn conjugate(&self) -> Self {
+        Self {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+            w: self.w,
+        }
+    }
+
+    #[inline(always)]
+    pub fn normalize(&self) -> Self {
+        let scale = S::Vf32::set1(1.0) / self.dot(self).sqrt();
+        Self {
+            x: self.x * scale,
+            y: self.y * scale,
+            z: self.z * scale,
+            w: self.w * scale,
+        }
+    }
+
+    /// Rotates `vectors` by the unit quaternions.
+    #[inline(always)]
+    pub fn rotate(&self, vectors: &Vec3<S>) -> Vec3<S> {
+        // v + w * t + u x t, where u is the vector part and t = 2 * (u x v).
+        let u = Vec3::<S> {
+            x: self.x,
+            y: self.y,
+            z: self.z,
+        };
+        let t = u.cross(vectors);
+        let two = S::Vf32::set1(2.0);
+        let t = Vec3::<S> {
+            x: t.x * two,
+            y: t.y * two,
+            z: t.z * two,
+        };
+        let ut = u.cross(&t);
+        Vec3 {
+            x: self.w.mul_add(t.x, vectors.x + ut.x),
+            y: self.w.mul_add(t.y, vectors.y + ut.y),
+            z: self.w.mul_add(t.z, vectors.z + ut.z),
+        }
+    }
+
+    /// Returns the rotation matrices of the unit quaternions.
+    #[inline(always)]
+    pub fn to_mat4(&self) -> Mat4<S> {
+        let two = S::Vf32::set1(2.0);
+        let (x2, y2, z2) = (self.x * two, self.y * two, self.z * two);
+        let (xx, yy, zz) = (self.x * x2, self.y * y2, self.z * z2);
+        let (xy, xz, yz) = (self.x * y2, self.x * z2, self.y * z2);
+        let (wx, wy, wz) = (self.w * x2, self.w * y2, self.w * z2);
+        let (zero, one) = (S::Vf32::zeroes(), S::Vf32::set1(1.0));
+        Mat4 {
+            rows: [
+                [one - (yy + zz), xy - wz, xz + wy, zero],
+                [xy + wz, one - (xx + zz), yz - wx, zero],
+                [xz - wy, yz + wx, one - (xx + yy), zero],
+                [zero, zero, zero, one],
+            ],
+        }
+    }
+
+    /// Spherically interpolates between the unit quaternions `self` and `other`, along the shorter
+    /// path, where `t` runs from 0 to 1. Nearly equal quaternions are interpolated linearly.
+    /// The result is normalized.
+    #[inline(always)]
+    pub fn slerp(&self, other: &Self, t: S::Vf32) -> Self {
+        let zero = S::Vf32::zeroes();
+        let one = S::Vf32::set1(1.0);
+
+        // q and -q are the same rotation, so flip `other` where that takes the shorter path.
+        let dot = self.dot(other);
+        let flip = dot.cmp_lt(zero);
+        let sign = flip.blendv(one, -one);
+        let cos_theta = (dot * sign).min(one);
+
+        let theta = acos_unit(cos_theta);
+        let inv_sin_theta = one / sin_quarter(theta);
+        let slerp_a = sin_quarter((one - t) * theta) * inv_sin_theta;
+        let slerp_b = sin_quarter(t * theta) * inv_sin_theta;
+
+        // Close to 1, sin(theta) loses too much precision to divide by.
+        let linear = cos_theta.cmp_gt(S::Vf32::set1(0.9995));
+        let weight_a = linear.blendv(slerp_a, one - t);
+        let weight_b = linear.blendv(slerp_b, t) * sign;
+
+        Self {
+            x: self.x.mul_add(weight_a, other.x * weight_b),
+            y: self.y.mul_add(weight_a, other.y * weight_b),
+            z: self.z.mul_add(weight_a, other.z * weight_b),
+            w: self.w.mul_add(weight_a, other.w * weight_b),
+        }
+        .normalize()
+    }
+}
@@ -0,0 +1,137 @@
+//! Hex encoding and decoding of byte slices.
+//!
+//! Encoding splits every byte into its two nibbles and maps them to digits with a 16 entry
+//! `lookup_16`. Decoding classifies every character by range, reporting the first one that isn't a
+//! hex digit through `index_of_first_truthy`, then packs pairs of nibbles back into bytes.
+//! Engines with an odd `Vi8` width, such as `Scalar`, use the scalar reference code throughout,
+//! and the engine is picked as for the other [slice algorithms](crate#slice-algorithms).
+
+use core::fmt;
+
+use crate::lane_tables::{lane_table, load_lane_table};
+use crate::{Simd, SimdBaseIo, SimdBaseOps, SimdConsts, SimdInt, SimdInt8};
+
+/// Which letters `hex_encode` uses for the digits 10 to 15.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HexCase {
+    Lower,
+    Upper,
+}
+
+impl HexCase {
+    fn digits(self) -> &'static [u8; 16] {
+        match self {
+            HexCase::Lower => b"0123456789abcdef",
+            HexCase::Upper => b"0123456789ABCDEF",
+        }
+    }
+}
+
+/// The index of the first byte of the input that isn't a hex digit. For an input with an odd
+/// length, this is the index just past the end, where the missing digit would be.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidAt(pub usize);
+
+impl fmt::Display for InvalidAt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid hex digit at index {}", self.0)
+    }
+}
+
+fn decode_digit(digit: u8) -> Option<u8> {
+    match digit {
+        b'0'..=b'9' => Some(digit - b'0'),
+        b'a'..=b'f' => Some(digit - b'a' + 10),
+        b'A'..=b'F' => Some(digit - b'A' + 10),
+        _ => None,
+    }
+}
+
+// Every byte of the input fills two lanes: the even one with its high nibble, the odd one with
+// its low nibble.
+const DUPLICATE: [i8; 32] = lane_table!(|i| (i / 2) as i8);
+const IS_ODD_LANE: [i8; 32] = lane_table!(|i| -((i % 2) as i8));
+
+// Gathers the high and low nibble of each decoded byte. Lanes past the decoded bytes are unused.
+const EVEN_LANES: [i8; 32] = lane_table!(|i| if i < 16 { (i * 2) as i8 } else { -1 });
+const ODD_LANES: [i8; 32] = lane_table!(|i| if i < 16 { (i * 2 + 1) as i8 } else { -1 });
+
+/// Writes the two hex digits of every byte of `input` to `output`, most significant first.
+/// Panics if `output` is shorter than twice the length of `input`.
+#[inline(always)]
+pub fn hex_encode<S: Simd>(input: &[u8], output: &mut [u8], case: HexCase) {
+    assert!(output.len() >= input.len() * 2);
+    let digits = case.digits();
+    let width = S::Vi8::WIDTH;
+
+    let mut read = 0;
+    if width.is_multiple_of(2) {
+        let table: &[i8; 16] = unsafe { &*(digits as *const [u8; 16] as *const [i8; 16]) };
+        let duplicate = load_lane_table::<S>(&DUPLICATE);
+        let is_odd = load_lane_table::<S>(&IS_ODD_LANE);
+
+        // Each step reads half a vector, but loads a whole one.
+        while read + width <= input.len() {
+            let bytes =
+                unsafe { S::Vi8::load_from_ptr_unaligned(input.as_ptr().add(read) as *const i8) };
+            let bytes = bytes.permute(duplicate);
+            let nibbles = is_odd.blendv(bytes.shr_const::<4>(), bytes & 0x0F);
+            let symbols = nibbles.lookup_16(table);
+            unsafe { symbols.copy_to_ptr_unaligned(output.as_mut_ptr().add(read * 2) as *mut i8) };
+            read += width / 2;
+        }
+    }
+
+    for (i, &byte) in input.iter().enumerate().skip(read) {
+        output[i * 2] = digits[(byte >> 4) as usize];
+        output[i * 2 + 1] = digits[(byte & 0x0F) as usize];
+    }
+}
+
+/// Decodes pairs of hex digits in `input`, of either case, into `output`, returning the number
+/// of bytes written. Panics if `output` is shorter than half the length of `input`.
+#[inline(always)]
+pub fn hex_decode<S: Simd>(input: &[u8], output: &mut [u8]) -> Result<usize, InvalidAt> {
+    assert!(output.len() >= input.len() / 2);
+    let width = S::Vi8::WIDTH;
+
+    let mut read = 0;
+    if width.is_multiple_of(2) {
+        let even = load_lane_table::<S>(&EVEN_LANES);
+        let odd = load_lane_table::<S>(&ODD_LANES);
+        let set1 = |value: u8| S::Vi8::set1(value as i8);
+
+        // Each step writes half a vector, but stores a whole one.
+        while read + width <= input.len() && read / 2 + width <= output.len() {
+            let symbols =
+                unsafe { S::Vi8::load_from_ptr_unaligned(input.as_ptr().add(read) as *const i8) };
+            let in_range =
+                |low: u8, high: u8| symbols.cmp_gte(set1(low)) & symbols.cmp_lte(set1(high));
+
+            let digit = in_range(b'0', b'9');
+            let lower = in_range(b'a', b'f');
+            let upper = in_range(b'A', b'F');
+            let invalid = (digit | lower | upper).cmp_eq(S::Vi8::zeroes());
+            if let Some(index) = invalid.index_of_first_truthy() {
+                return Err(InvalidAt(read + index));
+            }
+
+            let offset = (digit & (-(b'0' as i32)) as i8)
+                | (lower & (10 - b'a' as i32) as i8)
+                | (upper & (10 - b'A' as i32) as i8);
+            let nibbles = symbols + offset;
+            let bytes = nibbles.permute(even).shl_const::<4>() | nibbles.permute(odd);
+            unsafe { bytes.copy_to_ptr_unaligned(output.as_mut_ptr().add(read / 2) as *mut i8) };
+            read += width;
+        }
+    }
+
+    for (i, pair) in input[read..].chunks(2).enumerate() {
+        let index = read + i * 2;
+        let high = decode_digit(pair[0]).ok_or(InvalidAt(index))?;
+        let low = pair.get(1).ok_or(InvalidAt(index + 1))?;
+        let low = decode_digit(*low).ok_or(InvalidAt(index + 1))?;
+        output[index / 2] = high << 4 | low;
+    }
+    Ok(input.len() / 2)
+}
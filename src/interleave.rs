@@ -0,0 +1,56 @@
+//! Conversions between interleaved values, such as `x, y, z` triples, and one vector per component.
+
+use crate::SimdFloat;
+
+/// Loads `V::WIDTH` values of `N` consecutive components each, returning one vector per component.
+/// `N` must be 3 or a power of two. Panics if `data` is shorter than `N * V::WIDTH`.
+#[inline(always)]
+pub(crate) fn load_interleaved<V: SimdFloat, const N: usize>(data: &[V::Scalar]) -> [V; N] {
+    let width = V::WIDTH;
+    assert!(data.len() >= N * width);
+
+    let mut vectors: [V; N] =
+        core::array::from_fn(|i| unsafe { V::load_from_ptr_unaligned(data[i * width..].as_ptr()) });
+    if N == 3 {
+        let (x, y, z) = vectors[0].unzip3(vectors[1], vectors[2]);
+        vectors[..3].copy_from_slice(&[x, y, z]);
+    } else {
+        assert!(N.is_power_of_two());
+        // Each pass moves the even values to the first half of the vectors and the odd values to
+        // the second half, so after `log2(N)` passes every vector holds a single component.
+        for _ in 0..N.trailing_zeros() {
+            let previous = vectors;
+            for i in 0..N / 2 {
+                (vectors[i], vectors[N / 2 + i]) = previous[2 * i].unzip(previous[2 * i + 1]);
+            }
+        }
+    }
+    vectors
+}
+
+/// The inverse of `load_interleaved`. Panics if `data` is shorter than `N * V::WIDTH`.
+#[inline(always)]
+pub(crate) fn store_interleaved<V: SimdFloat, const N: usize>(
+    components: &[V; N],
+    data: &mut [V::Scalar],
+) {
+    let width = V::WIDTH;
+    assert!(data.len() >= N * width);
+
+    let mut vectors = *components;
+    if N == 3 {
+        let (a, b, c) = vectors[0].zip3(vectors[1], vectors[2]);
+        vectors[..3].copy_from_slice(&[a, b, c]);
+    } else {
+        assert!(N.is_power_of_two());
+        for _ in 0..N.trailing_zeros() {
+            let previous = vectors;
+            for i in 0..N / 2 {
+                (vectors[2 * i], vectors[2 * i + 1]) = previous[i].zip(previous[N / 2 + i]);
+            }
+        }
+    }
+    for (i, vector) in vectors.iter().enumerate() {
+        unsafe { vector.copy_to_ptr_unaligned(data[i * width..].as_mut_ptr()) };
+    }
+}
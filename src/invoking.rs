@@ -1,5 +1,12 @@
-use crate::{engines, Simd};
+use core::mem;
+use core::ptr;
+use core::sync::atomic::{self, AtomicBool, AtomicPtr, Ordering};
 
+use crate::{engines, max_engine, EngineKind, Simd};
+
+/// Turns the argument types of a function into the type of its argument tuple. A single type needs
+/// a trailing comma to be a tuple, and several types must not get a second pair of parentheses,
+/// which warns with `unused_parens` in every crate calling the dispatch macros.
 #[macro_export]
 macro_rules! fix_tuple_type {
     (()) => {
@@ -9,122 +16,271 @@ macro_rules! fix_tuple_type {
         ($typ,)
     };
     (($($typ:ty),*)) => {
-        (($($typ),*))
+        ($($typ),*)
+    };
+}
+
+/// Parses the generics, arguments, return type, where clause and engine overrides of a function
+/// given to the generating macros, then calls `$cb!(@generate ...)` with them split into the lists
+/// each generated item needs:
+///
+/// * the parameters with their bounds, for the functions and the `__SimdRunner` impl
+/// * the parameters without bounds or lifetimes, for the dispatch struct
+/// * the names of the type and const parameters, for turbofishes
+/// * the names of the type parameters, for the `PhantomData` of the dispatch struct
+///
+/// Every list ends with a comma when it isn't empty, so the `S` engine parameter can follow it.
+#[macro_export]
+macro_rules! __simd_parse_fn {
+    // Split the generic parameters on the commas outside of nested `<>`, counting the depth with
+    // one `<` per level.
+    (@generics $cb:ident $pre:tt [$($params:tt)*] [$($cur:tt)*] [] > $($rest:tt)*) => {
+        __simd_parse_fn!(@classify $cb $pre [] [] [] [] [$($params)* {$($cur)*}] $($rest)*);
+    };
+    (@generics $cb:ident $pre:tt [$($params:tt)*] [$($cur:tt)*] [<] >> $($rest:tt)*) => {
+        __simd_parse_fn!(@classify $cb $pre [] [] [] [] [$($params)* {$($cur)* >}] $($rest)*);
+    };
+    (@generics $cb:ident $pre:tt [$($params:tt)*] [$($cur:tt)*] [] , $($rest:tt)*) => {
+        __simd_parse_fn!(@generics $cb $pre [$($params)* {$($cur)*}] [] [] $($rest)*);
+    };
+    (@generics $cb:ident $pre:tt $params:tt [$($cur:tt)*] [$($depth:tt)*] < $($rest:tt)*) => {
+        __simd_parse_fn!(@generics $cb $pre $params [$($cur)* <] [$($depth)* <] $($rest)*);
+    };
+    (@generics $cb:ident $pre:tt $params:tt [$($cur:tt)*] [< $($depth:tt)*] > $($rest:tt)*) => {
+        __simd_parse_fn!(@generics $cb $pre $params [$($cur)* >] [$($depth)*] $($rest)*);
+    };
+    (@generics $cb:ident $pre:tt $params:tt [$($cur:tt)*] [< < $($depth:tt)*] >> $($rest:tt)*) => {
+        __simd_parse_fn!(@generics $cb $pre $params [$($cur)* >>] [$($depth)*] $($rest)*);
+    };
+    (@generics $cb:ident $pre:tt $params:tt [$($cur:tt)*] $depth:tt $next:tt $($rest:tt)*) => {
+        __simd_parse_fn!(@generics $cb $pre $params [$($cur)* $next] $depth $($rest)*);
+    };
+
+    // Sort every parameter into the lists, skipping the empty one after a trailing comma.
+    (@classify $cb:ident $pre:tt $full:tt $decl:tt $args:tt $types:tt [{} $($params:tt)*] $($rest:tt)*) => {
+        __simd_parse_fn!(@classify $cb $pre $full $decl $args $types [$($params)*] $($rest)*);
+    };
+    (@classify $cb:ident $pre:tt [$($full:tt)*] $decl:tt $args:tt $types:tt [{$lt:lifetime $($bounds:tt)*} $($params:tt)*] $($rest:tt)*) => {
+        __simd_parse_fn!(@classify $cb $pre [$($full)* $lt $($bounds)*,] $decl $args $types [$($params)*] $($rest)*);
+    };
+    (@classify $cb:ident $pre:tt [$($full:tt)*] [$($decl:tt)*] [$($args:tt)*] $types:tt [{const $name:ident : $ty:ty} $($params:tt)*] $($rest:tt)*) => {
+        __simd_parse_fn!(@classify $cb $pre [$($full)* const $name: $ty,] [$($decl)* const $name: $ty,] [$($args)* $name,] $types [$($params)*] $($rest)*);
+    };
+    (@classify $cb:ident $pre:tt [$($full:tt)*] [$($decl:tt)*] [$($args:tt)*] [$($types:tt)*] [{$name:ident $($bounds:tt)*} $($params:tt)*] $($rest:tt)*) => {
+        __simd_parse_fn!(@classify $cb $pre [$($full)* $name $($bounds)*,] [$($decl)* $name,] [$($args)* $name,] [$($types)* $name,] [$($params)*] $($rest)*);
+    };
+    (@classify $cb:ident $pre:tt $full:tt $decl:tt $args:tt $types:tt [] $($rest:tt)*) => {
+        __simd_parse_fn!(@signature $cb $pre [$full $decl $args $types] $($rest)*);
+    };
+
+    // The arguments and the return type, which defaults to `()`.
+    (@signature $cb:ident $pre:tt $generics:tt ($($arg:ident : $typ:ty),* $(,)?) -> $rt:ty { $($body:tt)* } $($overrides:tt)*) => {
+        $cb! { @generate $pre $generics [[$($arg: $typ),*] [$rt]] [] { $($body)* } [$($overrides)*] }
+    };
+    (@signature $cb:ident $pre:tt $generics:tt ($($arg:ident : $typ:ty),* $(,)?) -> $rt:ty where $($rest:tt)*) => {
+        __simd_parse_fn!(@where $cb $pre $generics [[$($arg: $typ),*] [$rt]] [] $($rest)*);
+    };
+    (@signature $cb:ident $pre:tt $generics:tt ($($arg:ident : $typ:ty),* $(,)?) { $($body:tt)* } $($overrides:tt)*) => {
+        $cb! { @generate $pre $generics [[$($arg: $typ),*] [()]] [] { $($body)* } [$($overrides)*] }
+    };
+    (@signature $cb:ident $pre:tt $generics:tt ($($arg:ident : $typ:ty),* $(,)?) where $($rest:tt)*) => {
+        __simd_parse_fn!(@where $cb $pre $generics [[$($arg: $typ),*] [()]] [] $($rest)*);
+    };
+
+    // The where clause runs until the body, the first group in braces.
+    (@where $cb:ident $pre:tt $generics:tt $sig:tt [$($where:tt)*] { $($body:tt)* } $($overrides:tt)*) => {
+        $cb! { @generate $pre $generics $sig [$($where)*] { $($body)* } [$($overrides)*] }
+    };
+    (@where $cb:ident $pre:tt $generics:tt $sig:tt [$($where:tt)*] $next:tt $($rest:tt)*) => {
+        __simd_parse_fn!(@where $cb $pre $generics $sig [$($where)* $next] $($rest)*);
+    };
+
+    ($cb:ident $pre:tt < $($rest:tt)*) => {
+        __simd_parse_fn!(@generics $cb $pre [] [] [] $($rest)*);
+    };
+    ($cb:ident $pre:tt $($rest:tt)*) => {
+        __simd_parse_fn!(@signature $cb $pre [[] [] [] []] $($rest)*);
     };
 }
 
 #[macro_export]
 macro_rules! __simd_generate_base {
-    ($(#[$meta:meta])* $vis:vis fn $fn_name:ident $(<$($lt:lifetime),+>)? ($($arg:ident:$typ:ty),* ) -> $rt:ty $body:block  ) => {
+    (@generate [[$(#[$meta:meta])*] [$vis:vis] [$fn_name:ident]] [[$($full:tt)*] [$($decl:tt)*] [$($args:tt)*] [$($types:tt)*]] [[$($arg:ident : $typ:ty),*] [$rt:ty]] [$($where:tt)*] { $($body:tt)* } [$($engine:ident { $($engine_body:tt)* })*]) => {
         simdeez_paste_item! {
             // In order to pass arguments via generics like this, we need to convert the arguments
             // into tuples. This is part of the reason for the mess below.
 
             #[inline(always)]
-            $vis unsafe fn [<__ $fn_name _generic>]<$($($lt,)+)? S: 'static + Simd>(args_tuple: ($($typ,)*)) -> $rt {
+            $vis unsafe fn [<__ $fn_name _generic>]<$($full)* S: 'static + Simd>(args_tuple: ($($typ,)*)) -> $rt where $($where)* {
+                __simd_generate_base!(@override_checks $fn_name [$($args)*] args_tuple $($engine)*);
                 let ($($arg,)*) = args_tuple;
-                S::invoke(#[inline(always)] || $body)
+                S::invoke(#[inline(always)] || { $($body)* })
             }
 
+            __simd_generate_base!(@overrides [[$fn_name] [$($full)*] [$($arg: $typ),*] [$rt] [$($where)*]] $($engine { $($engine_body)* })*);
+
             $(#[$meta])*
             #[inline(always)]
-            $vis fn [<$fn_name _generic>] <$($($lt),+,)? S: Simd>($($arg:$typ,)*) -> $rt {
+            $vis fn [<$fn_name _generic>]<$($full)* S: Simd>($($arg:$typ,)*) -> $rt where $($where)* {
                 let args_tuple = ($($arg,)*);
-                __run_simd_generic::<S, [<__ $fn_name _dispatch_struct>], fix_tuple_type!(($($typ),*)), $rt>(args_tuple)
+                __run_simd_generic::<S, [<__ $fn_name _dispatch_struct>]<$($args)*>, fix_tuple_type!(($($typ),*)), $rt>(args_tuple)
             }
 
+            // The type parameters don't have to appear in the arguments, so the struct holds them.
             #[allow(non_camel_case_types)]
-            struct [<__ $fn_name _dispatch_struct>];
+            struct [<__ $fn_name _dispatch_struct>]<$($decl)*>(::core::marker::PhantomData<fn() -> ($($types)*)>);
 
-            impl$(<$($lt),+>)? __SimdRunner<fix_tuple_type!(($($typ),*)), $rt> for [<__ $fn_name _dispatch_struct>] {
+            impl<$($full)*> __SimdRunner<fix_tuple_type!(($($typ),*)), $rt> for [<__ $fn_name _dispatch_struct>]<$($args)*> where $($where)* {
                 unsafe fn run<S: Simd>(args_tuple: fix_tuple_type!(($($typ),*))) -> $rt {
-                    [<__ $fn_name _generic>]::<S>(args_tuple)
+                    [<__ $fn_name _generic>]::<$($args)* S>(args_tuple)
                 }
             }
         }
     };
+    // Every override is its own function, whose `S` has the vector types of its engine.
+    (@overrides $sig:tt) => {};
+    (@overrides [[$fn_name:ident] [$($full:tt)*] [$($arg:ident : $typ:ty),*] [$rt:ty] [$($where:tt)*]] $engine:ident { $($engine_body:tt)* } $($rest:tt)*) => {
+        simdeez_paste_item! {
+            #[inline(always)]
+            unsafe fn [<__ $fn_name _ $engine:lower _override>]<
+                $($full)*
+                S: $crate::Simd<
+                    Vi8 = <$crate::engines::[<$engine:lower>]::$engine as $crate::Simd>::Vi8,
+                    Vi16 = <$crate::engines::[<$engine:lower>]::$engine as $crate::Simd>::Vi16,
+                    Vi32 = <$crate::engines::[<$engine:lower>]::$engine as $crate::Simd>::Vi32,
+                    Vi64 = <$crate::engines::[<$engine:lower>]::$engine as $crate::Simd>::Vi64,
+                    Vf32 = <$crate::engines::[<$engine:lower>]::$engine as $crate::Simd>::Vf32,
+                    Vf64 = <$crate::engines::[<$engine:lower>]::$engine as $crate::Simd>::Vf64,
+                >,
+            >(args_tuple: ($($typ,)*)) -> $rt where $($where)* {
+                let ($($arg,)*) = args_tuple;
+                S::invoke(#[inline(always)] || { $($engine_body)* })
+            }
+        }
+
+        __simd_generate_base!(@overrides [[$fn_name] [$($full)*] [$($arg: $typ),*] [$rt] [$($where)*]] $($rest)*);
+    };
+
+    // Runs the override of the engine `S`, if there is one.
+    (@override_checks $fn_name:ident $args:tt $args_tuple:ident) => {};
+    (@override_checks $fn_name:ident [$($args:tt)*] $args_tuple:ident $engine:ident $($rest:ident)*) => {
+        simdeez_paste_item! {
+            if ::core::any::TypeId::of::<S>() == ::core::any::TypeId::of::<$crate::engines::[<$engine:lower>]::$engine>() {
+                return [<__ $fn_name _ $engine:lower _override>]::<$($args)* $crate::engines::[<$engine:lower>]::$engine>($args_tuple);
+            }
+        }
+        __simd_generate_base!(@override_checks $fn_name [$($args)*] $args_tuple $($rest)*);
+    };
+
+    ($(#[$meta:meta])* $vis:vis fn $fn_name:ident $($rest:tt)*) => {
+        __simd_parse_fn!(__simd_generate_base [[$(#[$meta])*] [$vis] [$fn_name]] $($rest)*);
+    };
 }
 
 #[macro_export]
 macro_rules! simd_runtime_generate {
-    ($(#[$meta:meta])* $vis:vis fn $fn_name:ident $(<$($lt:lifetime),+>)? ($($arg:ident:$typ:ty),* $(,)? ) -> $rt:ty $body:block  ) => {
+    (@generate [[$(#[$meta:meta])*] [$vis:vis] [$fn_name:ident]] [[$($full:tt)*] $decl:tt [$($args:tt)*] $types:tt] [[$($arg:ident : $typ:ty),*] [$rt:ty]] [$($where:tt)*] $body:tt $overrides:tt) => {
         simdeez_paste_item! {
             // In order to pass arguments via generics like this, we need to convert the arguments
             // into tuples. This is part of the reason for the mess below.
 
             $(#[$meta])*
             #[inline(always)]
-            $vis fn $fn_name $(<$($lt),+>)?($($arg:$typ,)*) -> $rt {
+            $vis fn $fn_name<$($full)*>($($arg:$typ,)*) -> $rt where $($where)* {
                 let args_tuple = ($($arg,)*);
-                __run_simd_runtime_decide::<[<__ $fn_name _dispatch_struct>], fix_tuple_type!(($($typ),*)), $rt>(args_tuple)
+                __simd_runtime_call!([$($args)*] [<__ $fn_name _dispatch_struct>]<$($args)*>, fix_tuple_type!(($($typ),*)), $rt, args_tuple)
+            }
+
+            $(#[$meta])*
+            #[inline(always)]
+            $vis fn [<$fn_name _resolve>]<$($full)*>() -> fn($($typ),*) -> $rt where $($where)* {
+                match $crate::max_engine() {
+                    $crate::EngineKind::Avx2 => [<$fn_name _generic>]::<$($args)* $crate::engines::avx2::Avx2>,
+                    $crate::EngineKind::Scalar => [<$fn_name _generic>]::<$($args)* $crate::engines::scalar::Scalar>,
+                }
             }
 
             $(#[$meta])*
             #[inline(always)]
-            $vis fn [<$fn_name _scalar>] $(<$($lt),+>)?($($arg:$typ,)*) -> $rt {
+            $vis fn [<$fn_name _scalar>]<$($full)*>($($arg:$typ,)*) -> $rt where $($where)* {
                 let args_tuple = ($($arg,)*);
-                __run_simd_invoke_scalar::<[<__ $fn_name _dispatch_struct>], fix_tuple_type!(($($typ),*)), $rt>(args_tuple)
+                __run_simd_invoke_scalar::<[<__ $fn_name _dispatch_struct>]<$($args)*>, fix_tuple_type!(($($typ),*)), $rt>(args_tuple)
             }
 
-            __simd_generate_base!($(#[$meta])* $vis fn $fn_name $(<$($lt),+>)? ($($arg:$typ),* ) -> $rt $body);
+            __simd_generate_base!(@generate [[$(#[$meta])*] [$vis] [$fn_name]] [[$($full)*] $decl [$($args)*] $types] [[$($arg: $typ),*] [$rt]] [$($where)*] $body $overrides);
         }
     };
-    ($(#[$meta:meta])* $vis:vis fn $fn_name:ident ($($arg:ident:$typ:ty),* $(,)? ) $body:block  ) => {
-        simd_runtime_generate!($(#[$meta])* $vis fn $fn_name ($($arg:$typ),*) -> () $body);
+    ($(#[$meta:meta])* $vis:vis fn $fn_name:ident $($rest:tt)*) => {
+        __simd_parse_fn!(simd_runtime_generate [[$(#[$meta])*] [$vis] [$fn_name]] $($rest)*);
+    };
+}
+
+/// Calls the runner runtime dispatch picks. Without type or const parameters, the runner is
+/// resolved on the first call and kept in a static of the function, so that later calls are a
+/// single indirect call. Statics can't depend on generic parameters, so generic functions pick
+/// the engine on every call.
+#[macro_export]
+macro_rules! __simd_runtime_call {
+    ([] $runner:ty, $args_ty:ty, $rt:ty, $args:expr) => {{
+        static CACHE: __SimdRunnerCache = __SimdRunnerCache::new();
+        unsafe { CACHE.get::<$runner, $args_ty, $rt>()($args) }
+    }};
+    ([$($params:tt)+] $runner:ty, $args_ty:ty, $rt:ty, $args:expr) => {
+        __run_simd_runtime_decide::<$runner, $args_ty, $rt>($args)
     };
 }
 
 #[macro_export]
 macro_rules! simd_compiletime_select {
-    ($(#[$meta:meta])* $vis:vis fn $fn_name:ident $(<$($lt:lifetime),+>)? ($($arg:ident:$typ:ty),* $(,)? ) -> $rt:ty $body:block  ) => {
+    (@generate [[$(#[$meta:meta])*] [$vis:vis] [$fn_name:ident]] [[$($full:tt)*] $decl:tt [$($args:tt)*] $types:tt] [[$($arg:ident : $typ:ty),*] [$rt:ty]] [$($where:tt)*] $body:tt $overrides:tt) => {
         simdeez_paste_item! {
             $(#[$meta])*
             #[inline(always)]
-            $vis fn $fn_name $(<$($lt),+>)?($($arg:$typ,)*) -> $rt {
+            $vis fn $fn_name<$($full)*>($($arg:$typ,)*) -> $rt where $($where)* {
                 let args_tuple = ($($arg,)*);
-                __run_simd_compiletime_select::<[<__ $fn_name _dispatch_struct>], fix_tuple_type!(($($typ),*)), $rt>(args_tuple)
+                __run_simd_compiletime_select::<[<__ $fn_name _dispatch_struct>]<$($args)*>, fix_tuple_type!(($($typ),*)), $rt>(args_tuple)
             }
 
             $(#[$meta])*
             #[inline(always)]
-            $vis fn [<$fn_name _scalar>] $(<$($lt),+>)?($($arg:$typ,)*) -> $rt {
+            $vis fn [<$fn_name _scalar>]<$($full)*>($($arg:$typ,)*) -> $rt where $($where)* {
                 let args_tuple = ($($arg,)*);
-                __run_simd_invoke_scalar::<[<__ $fn_name _dispatch_struct>], fix_tuple_type!(($($typ),*)), $rt>(args_tuple)
+                __run_simd_invoke_scalar::<[<__ $fn_name _dispatch_struct>]<$($args)*>, fix_tuple_type!(($($typ),*)), $rt>(args_tuple)
             }
 
-            __simd_generate_base!($(#[$meta])* $vis fn $fn_name $(<$($lt),+>)? ($($arg:$typ),* ) -> $rt $body);
+            __simd_generate_base!(@generate [[$(#[$meta])*] [$vis] [$fn_name]] [[$($full)*] $decl [$($args)*] $types] [[$($arg: $typ),*] [$rt]] [$($where)*] $body $overrides);
         }
     };
-    ($(#[$meta:meta])* $vis:vis fn $fn_name:ident ($($arg:ident:$typ:ty),* $(,)? ) $body:block  ) => {
-        simd_runtime_generate!($(#[$meta])* $vis fn $fn_name ($($arg:$typ),*) -> () $body);
+    ($(#[$meta:meta])* $vis:vis fn $fn_name:ident $($rest:tt)*) => {
+        __simd_parse_fn!(simd_compiletime_select [[$(#[$meta])*] [$vis] [$fn_name]] $($rest)*);
     };
 }
 
 #[macro_export]
 macro_rules! simd_unsafe_generate_all {
-    ($(#[$meta:meta])* $vis:vis fn $fn_name:ident $(<$($lt:lifetime),+>)? ($($arg:ident:$typ:ty),* $(,)? ) -> $rt:ty $body:block  ) => {
+    (@generate [[$(#[$meta:meta])*] [$vis:vis] [$fn_name:ident]] [[$($full:tt)*] $decl:tt [$($args:tt)*] $types:tt] [[$($arg:ident : $typ:ty),*] [$rt:ty]] [$($where:tt)*] $body:tt $overrides:tt) => {
         simdeez_paste_item! {
             $(#[$meta])*
             #[inline(always)]
             #[cfg(target_arch = "x86_64")]
-            $vis fn [<$fn_name _scalar>] $(<$($lt),+>)?($($arg:$typ,)*) -> $rt {
+            $vis fn [<$fn_name _scalar>]<$($full)*>($($arg:$typ,)*) -> $rt where $($where)* {
                 let args_tuple = ($($arg,)*);
-                __run_simd_invoke_scalar::<[<__ $fn_name _dispatch_struct>], fix_tuple_type!(($($typ),*)), $rt>(args_tuple)
+                __run_simd_invoke_scalar::<[<__ $fn_name _dispatch_struct>]<$($args)*>, fix_tuple_type!(($($typ),*)), $rt>(args_tuple)
             }
 
             $(#[$meta])*
             #[inline(always)]
             #[cfg(all(target_arch = "x86_64", target_feature = "avx2"))]
-            $vis unsafe fn [<$fn_name _avx2>] $(<$($lt),+>)?($($arg:$typ,)*) -> $rt {
+            $vis unsafe fn [<$fn_name _avx2>]<$($full)*>($($arg:$typ,)*) -> $rt where $($where)* {
                 let args_tuple = ($($arg,)*);
-                __run_simd_invoke_avx2::<[<__ $fn_name _dispatch_struct>], fix_tuple_type!(($($typ),*)), $rt>(args_tuple)
+                __run_simd_invoke_avx2::<[<__ $fn_name _dispatch_struct>]<$($args)*>, fix_tuple_type!(($($typ),*)), $rt>(args_tuple)
             }
 
-            __simd_generate_base!($(#[$meta])* $vis fn $fn_name $(<$($lt),+>)? ($($arg:$typ),* ) -> $rt $body);
+            __simd_generate_base!(@generate [[$(#[$meta])*] [$vis] [$fn_name]] [[$($full)*] $decl [$($args)*] $types] [[$($arg: $typ),*] [$rt]] [$($where)*] $body $overrides);
         }
     };
-    ($(#[$meta:meta])* $vis:vis fn $fn_name:ident ($($arg:ident:$typ:ty),* $(,)? ) $body:block  ) => {
-        simd_runtime_generate!($(#[$meta])* $vis fn $fn_name ($($arg:$typ),*) -> () $body);
+    ($(#[$meta:meta])* $vis:vis fn $fn_name:ident $($rest:tt)*) => {
+        __simd_parse_fn!(simd_unsafe_generate_all [[$(#[$meta])*] [$vis] [$fn_name]] $($rest)*);
     };
 }
 
@@ -136,12 +292,102 @@ pub trait Avx2Runner<A, R> {
     unsafe fn run(args: A) -> R;
 }
 
+/// Returns the runner of an engine.
 #[inline(always)]
-pub fn __run_simd_runtime_decide<S: __SimdRunner<A, R>, A, R>(args: A) -> R {
-    #![allow(unreachable_code)]
+fn runner_for<S: __SimdRunner<A, R>, A, R>(kind: EngineKind) -> unsafe fn(A) -> R {
+    match kind {
+        EngineKind::Avx2 => S::run::<engines::avx2::Avx2>,
+        EngineKind::Scalar => S::run::<engines::scalar::Scalar>,
+    }
+}
 
-    return unsafe { S::run::<engines::avx2::Avx2>(args) };
-    unsafe { S::run::<engines::scalar::Scalar>(args) }
+/// The head of the list of every `__SimdRunnerCache` that has resolved a runner, so that
+/// `set_max_engine` can clear them.
+static RUNNER_CACHES: AtomicPtr<__SimdRunnerCache> = AtomicPtr::new(ptr::null_mut());
+
+/// The runner a function generated by `simd_runtime_generate!` resolved on its first call, type
+/// erased so that it can be kept in a static.
+pub struct __SimdRunnerCache {
+    runner: AtomicPtr<()>,
+    next: AtomicPtr<__SimdRunnerCache>,
+    registered: AtomicBool,
+}
+
+impl __SimdRunnerCache {
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        Self {
+            runner: AtomicPtr::new(ptr::null_mut()),
+            next: AtomicPtr::new(ptr::null_mut()),
+            registered: AtomicBool::new(false),
+        }
+    }
+
+    /// Returns the runner of the engine runtime dispatch picks, resolving it if needed.
+    ///
+    /// The cache must only ever be used with the same `S`, `A` and `R`.
+    #[inline(always)]
+    pub fn get<S: __SimdRunner<A, R>, A, R>(&'static self) -> unsafe fn(A) -> R {
+        let runner = self.runner.load(Ordering::Relaxed);
+        if runner.is_null() {
+            return self.resolve::<S, A, R>();
+        }
+        // Safe because the pointer was stored from the same function pointer type below.
+        unsafe { mem::transmute::<*mut (), unsafe fn(A) -> R>(runner) }
+    }
+
+    #[cold]
+    fn resolve<S: __SimdRunner<A, R>, A, R>(&'static self) -> unsafe fn(A) -> R {
+        if !self.registered.swap(true, Ordering::AcqRel) {
+            let mut head = RUNNER_CACHES.load(Ordering::Acquire);
+            loop {
+                self.next.store(head, Ordering::Relaxed);
+                let this = self as *const Self as *mut Self;
+                match RUNNER_CACHES.compare_exchange_weak(
+                    head,
+                    this,
+                    Ordering::AcqRel,
+                    Ordering::Acquire,
+                ) {
+                    Ok(_) => break,
+                    Err(current) => head = current,
+                }
+            }
+        }
+
+        // A `set_max_engine` racing with this may clear the caches before the runner is stored,
+        // so check that the engine is still the same afterwards.
+        loop {
+            let kind = max_engine();
+            let runner = runner_for::<S, A, R>(kind);
+            self.runner.store(runner as *mut (), Ordering::Relaxed);
+            atomic::fence(Ordering::SeqCst);
+            if max_engine() == kind {
+                return runner;
+            }
+        }
+    }
+}
+
+/// Clears every resolved runner, so that the next calls pick the engine again.
+pub(crate) fn clear_runner_caches() {
+    // Pairs with the fence in `resolve`, so that either this sees the cache and clears its runner
+    // or `resolve` sees the new engine.
+    atomic::fence(Ordering::SeqCst);
+    let mut cache = RUNNER_CACHES.load(Ordering::Acquire);
+    // Safe because only statics are added to the list.
+    while let Some(current) = unsafe { cache.as_ref() } {
+        current.runner.store(ptr::null_mut(), Ordering::Relaxed);
+        cache = current.next.load(Ordering::Acquire);
+    }
+}
+
+#[inline(always)]
+pub fn __run_simd_runtime_decide<S: __SimdRunner<A, R>, A, R>(args: A) -> R {
+    match max_engine() {
+        EngineKind::Avx2 => unsafe { S::run::<engines::avx2::Avx2>(args) },
+        EngineKind::Scalar => unsafe { S::run::<engines::scalar::Scalar>(args) },
+    }
 }
 
 #[inline(always)]
@@ -151,11 +397,20 @@ pub fn __run_simd_generic<E: Simd, S: __SimdRunner<A, R>, A, R>(args: A) -> R {
 
 #[inline(always)]
 pub fn __run_simd_compiletime_select<S: __SimdRunner<A, R>, A, R>(args: A) -> R {
-    #![allow(unreachable_code)]
     #![allow(clippy::needless_return)]
 
+    #[cfg(all(
+        target_arch = "x86_64",
+        target_feature = "avx2",
+        target_feature = "fma"
+    ))]
     return unsafe { S::run::<engines::avx2::Avx2>(args) };
 
+    #[cfg(not(all(
+        target_arch = "x86_64",
+        target_feature = "avx2",
+        target_feature = "fma"
+    )))]
     return unsafe { S::run::<engines::scalar::Scalar>(args) };
 }
 
@@ -183,3 +438,30 @@ macro_rules! simd_invoke {
         )
     }
 }
+
+/// Evaluates `$body` with the engine of the `EngineKind` `$kind` inside its `Simd::invoke`, with
+/// `$engine` naming the engine type, e.g. `dispatch!(kind, |S| sum_generic::<S>(&data))`. An engine
+/// the CPU doesn't support falls back to the `best_engine`.
+///
+/// `$engine` is a concrete type in the body, so its vector types are written `<S as Simd>::Vf32`.
+#[macro_export]
+macro_rules! dispatch {
+    ($kind:expr, |$engine:ident| $body:expr $(,)?) => {
+        match ::core::cmp::Ord::min($kind, $crate::best_engine()) {
+            $crate::EngineKind::Avx2 => {
+                type $engine = $crate::engines::avx2::Avx2;
+                <$engine as $crate::Simd>::invoke(
+                    #[inline(always)]
+                    || $body,
+                )
+            }
+            $crate::EngineKind::Scalar => {
+                type $engine = $crate::engines::scalar::Scalar;
+                <$engine as $crate::Simd>::invoke(
+                    #[inline(always)]
+                    || $body,
+                )
+            }
+        }
+    };
+}
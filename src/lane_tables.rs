@@ -0,0 +1,77 @@
+//! Per-lane constant vectors, such as `permute` indices, used by the slice algorithms.
+
+use crate::{Simd, SimdBaseIo};
+
+/// Builds a table with one entry per lane of the widest engine, from an expression of the lane index.
+macro_rules! lane_table {
+    (|$lane:ident| $value:expr) => {{
+        let mut table = [0i8; 32];
+        let mut $lane = 0;
+        while $lane < 32 {
+            table[$lane] = $value;
+            $lane += 1;
+        }
+        table
+    }};
+}
+pub(crate) use lane_table;
+
+/// Loads the first `S::Vi8::WIDTH` entries of a table built with `lane_table!`.
+#[inline(always)]
+pub(crate) fn load_lane_table<S: Simd>(table: &[i8; 32]) -> S::Vi8 {
+    unsafe { S::Vi8::load_from_ptr_unaligned(table.as_ptr()) }
+}
+
+/// The size in bytes of the widest register of any engine.
+const MAX_REGISTER_BYTES: usize = 32;
+
+/// The number of lanes of `T` in the widest register of any engine.
+#[doc(hidden)]
+pub const fn __simd_const_lanes<T>() -> usize {
+    MAX_REGISTER_BYTES / core::mem::size_of::<T>()
+}
+
+/// The lanes of a vector constant for the widest engine, built with `simd_const!`. Narrower
+/// engines use the first `WIDTH` lanes.
+#[derive(Clone, Copy, Debug)]
+#[repr(C, align(32))]
+pub struct SimdConst<T, const N: usize>(pub [T; N]);
+
+impl<T: Copy, const N: usize> SimdConst<T, N> {
+    /// Loads the first `V::WIDTH` lanes.
+    #[inline(always)]
+    pub fn load<V: SimdBaseIo<Scalar = T>>(&self) -> V {
+        assert!(V::WIDTH <= N);
+        unsafe { V::load_from_ptr_aligned(self.0.as_ptr()) }
+    }
+}
+
+/// Defines a `static` or `const` vector from an expression of the lane index, like `from_fn`, e.g.
+/// `simd_const!(static RAMP: f32 = |lane| lane as f32 * 0.5);`. The lanes are computed at compile
+/// time for the widest engine, and `RAMP.load::<S::Vf32>()` loads them for any engine `S`.
+#[macro_export]
+macro_rules! simd_const {
+    (@build $ty:ty, |$lane:ident| $value:expr) => {{
+        // The lanes start as copies of lane 0, as a zeroed value isn't valid for every type.
+        let mut lanes: [$ty; $crate::__simd_const_lanes::<$ty>()] = {
+            let $lane: usize = 0;
+            [$value; $crate::__simd_const_lanes::<$ty>()]
+        };
+        let mut $lane = 1;
+        while $lane < lanes.len() {
+            lanes[$lane] = $value;
+            $lane += 1;
+        }
+        $crate::SimdConst(lanes)
+    }};
+    ($(#[$meta:meta])* $vis:vis static $name:ident: $ty:ty = |$lane:ident| $value:expr $(;)?) => {
+        $(#[$meta])*
+        $vis static $name: $crate::SimdConst<$ty, { $crate::__simd_const_lanes::<$ty>() }> =
+            $crate::simd_const!(@build $ty, |$lane| $value);
+    };
+    ($(#[$meta:meta])* $vis:vis const $name:ident: $ty:ty = |$lane:ident| $value:expr $(;)?) => {
+        $(#[$meta])*
+        $vis const $name: $crate::SimdConst<$ty, { $crate::__simd_const_lanes::<$ty>() }> =
+            $crate::simd_const!(@build $ty, |$lane| $value);
+    };
+}
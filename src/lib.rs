@@ -21,6 +21,20 @@
 //! * Can be used by `#[no_std]` projects
 //! * Operator overloading: `let sum = va + vb` or `s *= s`
 //! * Extract or set a single lane with the index operator: `let v1 = v[1];`
+//! * Runtime dispatch can be limited to a lower engine with `set_max_engine`, or with the
+//!   `SIMDEEZ_ENGINE` environment variable when the `std` feature is enabled
+//! * A `macros` feature with the `#[simd_runtime]`, `#[simd_compiletime]` and `#[simd_generic]`
+//!   attributes, which also work on methods and trait impls
+//! * A `testing` feature with the `simd_test!` macro, which checks your functions on every engine
+//!   against the scalar one
+//!
+//! # Slice algorithms
+//!
+//! The `search`, `utf8`, `parse`, `base64`, `hex`, `scan`, `sort`, `linalg`, `geometry`, `complex`,
+//! `rng` and `noise` modules work on whole slices or batches. Their functions are generic over the
+//! engine and inlined, so call them from inside a function generated by `simd_runtime_generate!`
+//! (or another `S::invoke` context) to get the engine's instructions. Called from plain code they
+//! are compiled without its target features.
 //!
 //! # Compared to Faster
 //!
@@ -36,17 +50,27 @@
 //! of arcane subtleties with inlining and target_features that must be managed. See how the macros
 //! expand for more detail.
 #![allow(clippy::missing_safety_doc, unsafe_op_in_unsafe_fn)] // TODO: Work on the safety of functions
-#![cfg_attr(not(test), no_std)]
+#![cfg_attr(not(any(test, feature = "std")), no_std)]
 
 #[macro_use]
-#[cfg(test)]
+#[cfg(any(test, feature = "std"))]
 extern crate std;
 pub extern crate paste;
 
+// Lets the code generated by the attribute macros refer to `::simdeez` in this crate's tests.
+#[cfg(test)]
+extern crate self as simdeez;
+
+#[cfg(feature = "macros")]
+pub use simdeez_macros::{simd_compiletime, simd_generic, simd_runtime};
+
 
 #[cfg(test)]
 mod tests;
 
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+
 mod ops;
 
 pub mod prelude;
@@ -55,6 +79,9 @@ use core::ops::*;
 
 mod invoking;
 
+mod engine_kind;
+pub use engine_kind::*;
+
 #[macro_use]
 mod overloads;
 
@@ -63,8 +90,37 @@ pub use base::*;
 
 mod libm_ext;
 
+mod lane_tables;
+pub use lane_tables::{__simd_const_lanes, SimdConst};
+
+mod interleave;
+
 pub mod engines;
 
+pub mod base64;
+
+pub mod complex;
+
+pub mod geometry;
+
+pub mod hex;
+
+pub mod linalg;
+
+pub mod noise;
+
+pub mod parse;
+
+pub mod rng;
+
+pub mod scan;
+
+pub mod search;
+
+pub mod sort;
+
+pub mod utf8;
+
 pub use engines::scalar;
 
 /// The abstract SIMD trait which is implemented by Avx2, Sse41, etc
@@ -99,5 +155,15 @@ pub trait Simd: 'static + Sync + Send {
     /// when used with Scalar.
     type Vf64: SimdFloat64<Engine = Self, Scalar = f64> + SimdBaseIo;
 
+    /// The name of the engine, e.g. "avx2", as used by `EngineKind::name`.
+    const NAME: &'static str;
+
+    /// The width of the vector registers in bits, e.g. 256 with Avx2. Scalar holds a single lane
+    /// of any type, so this is the widest lane, 64.
+    const REGISTER_BITS: usize;
+
+    /// The target features the engine needs, e.g. `["avx2", "fma"]` with Avx2.
+    const REQUIRED_FEATURES: &'static [&'static str];
+
     fn invoke<R>(f: impl FnOnce() -> R) -> R;
 }
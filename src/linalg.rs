@@ -0,0 +1,260 @@
+//! Dense linear algebra kernels for `f32` and `f64` slices.
+//!
+//! Vectors are plain slices, and matrices are row-major slices without padding between rows. The
+//! kernels follow the BLAS conventions for their arguments, so `gemm` computes
+//! `C = alpha * A * B + beta * C`, and `C` isn't read when `beta` is zero.
+//!
+//! Every multiply-add goes through `mul_add`, which is a fused multiply-add on `Avx2`. Results may
+//! differ from a sequential loop in the last bits, since the sums are split over several
+//! accumulators. See [slice algorithms](crate#slice-algorithms) for where to call them from.
+
+use core::fmt::Debug;
+use core::ops::{Add, Mul};
+
+use crate::{Simd, SimdBaseIo, SimdBaseOps, SimdConsts, SimdFloat};
+
+/// Float types supported by this module, with the vector type holding them in every engine.
+pub trait LinalgFloat:
+    Copy + Debug + PartialEq + Add<Output = Self> + Mul<Output = Self> + 'static
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    type Vector<S: Simd>: SimdFloat<Scalar = Self, HorizontalAddScalar = Self>;
+}
+
+impl LinalgFloat for f32 {
+    const ZERO: Self = 0.0;
+    const ONE: Self = 1.0;
+
+    type Vector<S: Simd> = S::Vf32;
+}
+
+impl LinalgFloat for f64 {
+    const ZERO: Self = 0.0;
+    const ONE: Self = 1.0;
+
+    type Vector<S: Simd> = S::Vf64;
+}
+
+/// The number of rows of `C` updated at once by the `gemm` microkernel. Its columns are two vectors
+/// wide, so the accumulators take 8 registers.
+const MICRO_ROWS: usize = 4;
+
+/// The number of rows of `A` and `C` in a `gemm` block.
+const BLOCK_ROWS: usize = 64;
+
+/// The depth of a `gemm` block, i.e. the number of columns of `A` and rows of `B` it covers.
+const BLOCK_DEPTH: usize = 256;
+
+/// The number of columns of `B` and `C` in a `gemm` block. A block of `B` takes 256 KiB of `f32`.
+const BLOCK_COLS: usize = 256;
+
+/// Returns the sum of the products of the matching elements of `a` and `b`.
+/// Panics if they have different lengths.
+#[inline(always)]
+pub fn dot<S: Simd, T: LinalgFloat>(a: &[T], b: &[T]) -> T {
+    assert_eq!(a.len(), b.len());
+    let width = T::Vector::<S>::WIDTH;
+    let load = |slice: &[T], i: usize| unsafe {
+        T::Vector::<S>::load_from_ptr_unaligned(slice.as_ptr().add(i))
+    };
+
+    // Four independent accumulators hide the latency of the multiply-adds.
+    let mut acc = [T::Vector::<S>::zeroes(); 4];
+    let mut i = 0;
+    while i + width * 4 <= a.len() {
+        for (j, acc) in acc.iter_mut().enumerate() {
+            let offset = i + j * width;
+            *acc = load(a, offset).mul_add(load(b, offset), *acc);
+        }
+        i += width * 4;
+    }
+    while i + width <= a.len() {
+        acc[0] = load(a, i).mul_add(load(b, i), acc[0]);
+        i += width;
+    }
+
+    // The padding lanes are zero in both, so they don't add anything.
+    let a_rest = T::Vector::<S>::load_from_slice(&a[i..]);
+    let b_rest = T::Vector::<S>::load_from_slice(&b[i..]);
+    acc[1] = a_rest.mul_add(b_rest, acc[1]);
+
+    ((acc[0] + acc[1]) + (acc[2] + acc[3])).horizontal_add()
+}
+
+/// Computes `y = alpha * x + y`. Panics if `x` and `y` have different lengths.
+#[inline(always)]
+pub fn axpy<S: Simd, T: LinalgFloat>(alpha: T, x: &[T], y: &mut [T]) {
+    assert_eq!(x.len(), y.len());
+    let alpha = T::Vector::<S>::set1(alpha);
+    for (x, y) in x
+        .chunks(T::Vector::<S>::WIDTH)
+        .zip(y.chunks_mut(T::Vector::<S>::WIDTH))
+    {
+        let x = T::Vector::<S>::load_from_slice(x);
+        alpha
+            .mul_add(x, T::Vector::<S>::load_from_slice(y))
+            .copy_to_slice(y);
+    }
+}
+
+/// Computes `x = alpha * x`.
+#[inline(always)]
+pub fn scale<S: Simd, T: LinalgFloat>(alpha: T, x: &mut [T]) {
+    let alpha = T::Vector::<S>::set1(alpha);
+    for x in x.chunks_mut(T::Vector::<S>::WIDTH) {
+        (T::Vector::<S>::load_from_slice(x) * alpha).copy_to_slice(x);
+    }
+}
+
+/// Computes `y = alpha * A * x + beta * y`, where `A` is a row-major matrix with `y.len()` rows and
+/// `x.len()` columns. Panics if `a` doesn't have that many elements.
+#[inline(always)]
+pub fn gemv<S: Simd, T: LinalgFloat>(alpha: T, a: &[T], x: &[T], beta: T, y: &mut [T]) {
+    let cols = x.len();
+    assert_eq!(a.len(), y.len() * cols);
+    let width = T::Vector::<S>::WIDTH;
+
+    let update = |y: &mut T, dot: T| {
+        *y = if beta == T::ZERO {
+            alpha * dot
+        } else {
+            alpha * dot + beta * *y
+        };
+    };
+
+    // Take four rows at a time, so every load of `x` is shared between them.
+    let mut row = 0;
+    while row + 4 <= y.len() {
+        let rows: [&[T]; 4] = core::array::from_fn(|r| &a[(row + r) * cols..(row + r + 1) * cols]);
+        let mut acc = [T::Vector::<S>::zeroes(); 4];
+        for i in (0..cols).step_by(width) {
+            let end = (i + width).min(cols);
+            let x = T::Vector::<S>::load_from_slice(&x[i..end]);
+            for (acc, row) in acc.iter_mut().zip(rows) {
+                *acc = T::Vector::<S>::load_from_slice(&row[i..end]).mul_add(x, *acc);
+            }
+        }
+        for (r, acc) in acc.iter().enumerate() {
+            update(&mut y[row + r], acc.horizontal_add());
+        }
+        row += 4;
+    }
+    while row < y.len() {
+        update(
+            &mut y[row],
+            dot::<S, T>(&a[row * cols..(row + 1) * cols], x),
+        );
+        row += 1;
+    }
+}
+
+/// Computes `C = alpha * A * B + beta * C` for row-major matrices, where `A` has `m` rows and `k`
+/// columns, `B` has `k` rows and `n` columns, and `C` has `m` rows and `n` columns.
+/// Panics if any of the slices has the wrong number of elements.
+///
+/// The product is computed in blocks that keep a part of `B` in the cache while every row of `A`
+/// streams past it, and every block is computed by a microkernel that keeps a tile of
+/// `MICRO_ROWS` rows by two vectors of `C` in registers.
+#[allow(clippy::too_many_arguments)]
+#[inline(always)]
+pub fn gemm<S: Simd, T: LinalgFloat>(
+    m: usize,
+    n: usize,
+    k: usize,
+    alpha: T,
+    a: &[T],
+    b: &[T],
+    beta: T,
+    c: &mut [T],
+) {
+    assert_eq!(a.len(), m * k);
+    assert_eq!(b.len(), k * n);
+    assert_eq!(c.len(), m * n);
+
+    if beta == T::ZERO {
+        c.fill(T::ZERO);
+    } else if beta != T::ONE {
+        scale::<S, T>(beta, c);
+    }
+    if k == 0 || alpha == T::ZERO {
+        return;
+    }
+
+    let tile_cols = T::Vector::<S>::WIDTH * 2;
+    for col_block in (0..n).step_by(BLOCK_COLS) {
+        let col_end = (col_block + BLOCK_COLS).min(n);
+        for depth_block in (0..k).step_by(BLOCK_DEPTH) {
+            let depth = depth_block..(depth_block + BLOCK_DEPTH).min(k);
+            for row_block in (0..m).step_by(BLOCK_ROWS) {
+                let row_end = (row_block + BLOCK_ROWS).min(m);
+                for row in (row_block..row_end).step_by(MICRO_ROWS) {
+                    for col in (col_block..col_end).step_by(tile_cols) {
+                        let tile = Tile {
+                            row,
+                            col,
+                            cols: (col_end - col).min(tile_cols),
+                            depth: depth.clone(),
+                        };
+                        match row_end - row {
+                            1 => gemm_kernel::<S, T, 1>(&tile, n, k, alpha, a, b, c),
+                            2 => gemm_kernel::<S, T, 2>(&tile, n, k, alpha, a, b, c),
+                            3 => gemm_kernel::<S, T, 3>(&tile, n, k, alpha, a, b, c),
+                            _ => gemm_kernel::<S, T, MICRO_ROWS>(&tile, n, k, alpha, a, b, c),
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The part of `C` updated by one call of the `gemm` microkernel.
+struct Tile {
+    row: usize,
+    col: usize,
+    /// At most two vectors wide.
+    cols: usize,
+    depth: core::ops::Range<usize>,
+}
+
+/// Adds `alpha` times the product of `ROWS` rows of `A` and two vectors of columns of `B`, over the
+/// tile's range of depths, to `C`.
+#[inline(always)]
+fn gemm_kernel<S: Simd, T: LinalgFloat, const ROWS: usize>(
+    tile: &Tile,
+    n: usize,
+    k: usize,
+    alpha: T,
+    a: &[T],
+    b: &[T],
+    c: &mut [T],
+) {
+    let width = T::Vector::<S>::WIDTH;
+    let low_cols = tile.cols.min(width);
+
+    let mut acc = [[T::Vector::<S>::zeroes(); 2]; ROWS];
+    for p in tile.depth.clone() {
+        let b_row = &b[p * n + tile.col..];
+        let b_low = T::Vector::<S>::load_from_slice(&b_row[..low_cols]);
+        let b_high = T::Vector::<S>::load_from_slice(&b_row[low_cols..tile.cols]);
+        for (r, acc) in acc.iter_mut().enumerate() {
+            let a = T::Vector::<S>::set1(a[(tile.row + r) * k + p]);
+            acc[0] = a.mul_add(b_low, acc[0]);
+            acc[1] = a.mul_add(b_high, acc[1]);
+        }
+    }
+
+    let alpha = T::Vector::<S>::set1(alpha);
+    for (r, acc) in acc.iter().enumerate() {
+        let c_row = &mut c[(tile.row + r) * n + tile.col..][..tile.cols];
+        let (c_low, c_high) = c_row.split_at_mut(low_cols);
+        acc[0]
+            .mul_add(alpha, T::Vector::<S>::load_from_slice(c_low))
+            .copy_to_slice(c_low);
+        acc[1]
+            .mul_add(alpha, T::Vector::<S>::load_from_slice(c_high))
+            .copy_to_slice(c_high);
+    }
+}
@@ -0,0 +1,402 @@
+//! Gradient and cellular noise for procedural generation, in 2, 3 and 4 dimensions.
+//!
+//! Every lane of a `Vf32` is one sample point. The integer lattice cells around it are hashed with
+//! the seed to pick a gradient or a feature point, so the same seed gives the same noise on every
+//! engine, up to rounding differences from fused multiply-adds.
+//!
+//! - Simplex noise sums the contributions of the corners of the simplex containing the point.
+//! - Perlin noise interpolates the gradients of the corners of the cube containing the point.
+//! - Cellular noise is the distance to the nearest of one random feature point per cell.
+//!
+//! Simplex and Perlin noise are scaled to lie within `[-1, 1]`, and cellular noise is rarely above
+//! one. Fractal Brownian motion sums octaves of any of them with increasing frequencies and
+//! decreasing amplitudes, normalized by the sum of the amplitudes.
+//!
+//! `noise`, `fill_grid` and `fill_points` are generic over the engine (see
+//! [slice algorithms](crate#slice-algorithms)), while the `fill_grid_2d` family dispatches at
+//! runtime.
+
+use crate::prelude::*;
+
+/// The kind of noise to generate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NoiseType {
+    Simplex,
+    Perlin,
+    Cellular,
+}
+
+/// How to generate noise.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NoiseSettings {
+    pub noise: NoiseType,
+    pub seed: i32,
+    /// The scale applied to coordinates before sampling, so higher frequencies give smaller
+    /// features.
+    pub frequency: f32,
+    /// The number of fractal Brownian motion octaves. One octave is plain noise.
+    pub octaves: u32,
+    /// The factor the frequency is multiplied by from one octave to the next.
+    pub lacunarity: f32,
+    /// The factor the amplitude is multiplied by from one octave to the next.
+    pub gain: f32,
+}
+
+impl NoiseSettings {
+    /// Plain noise of the given kind, with a seed of zero and a frequency of one. The fractal
+    /// settings are the usual doubling of the frequency and halving of the amplitude, but only
+    /// apply once `octaves` is raised.
+    pub const fn new(noise: NoiseType) -> Self {
+        Self {
+            noise,
+            seed: 0,
+            frequency: 1.0,
+            octaves: 1,
+            lacunarity: 2.0,
+            gain: 0.5,
+        }
+    }
+}
+
+/// Large primes that spread the lattice coordinates of every axis over the hash.
+const PRIMES: [i32; 4] = [501_125_321, 1_136_930_381, 1_720_413_743, 1_066_037_191];
+
+/// Odd multipliers that give each axis of a cellular feature point its own bits of the hash.
+const JITTER_MULTIPLIERS: [i32; 4] = [0x2c1b_3c6d, 0x297a_2d39, 0x1b56_c4e9, 0x3d4d_51cb];
+
+/// The factors that skew space into simplices and back, indexed by dimension.
+const SKEW: [f32; 5] = [0.0, 0.0, 0.366_025_42, 1.0 / 3.0, 0.309_017];
+const UNSKEW: [f32; 5] = [0.0, 0.0, 0.211_324_87, 1.0 / 6.0, 0.138_196_6];
+
+/// The squared radius of the contribution of each simplex corner, indexed by dimension.
+const SIMPLEX_RADIUS: [f32; 5] = [0.0, 0.0, 0.5, 0.6, 0.6];
+
+/// The factors that scale the noise to `[-1, 1]`, indexed by dimension. They were found by
+/// sampling millions of points, with a few percent of margin, and the result is clamped in case
+/// some rarer point goes beyond.
+const SIMPLEX_SCALE: [f32; 5] = [0.0, 0.0, 69.0, 22.0, 26.0];
+const PERLIN_SCALE: [f32; 5] = [0.0, 0.0, 1.0, 0.94, 0.93];
+
+/// Clamps scaled noise to `[-1, 1]`.
+#[inline(always)]
+fn clamp_unit<V: SimdFloat>(x: V) -> V {
+    x.max(V::from_f64(-1.0)).min(V::from_f64(1.0))
+}
+
+/// Hashes the lattice cells of every lane with the seed.
+#[inline(always)]
+fn hash<S: Simd, const D: usize>(seed: S::Vi32, cell: &[S::Vi32; D]) -> S::Vi32 {
+    let mut hash = seed;
+    for (&coordinate, prime) in cell.iter().zip(PRIMES) {
+        hash ^= coordinate * S::Vi32::set1(prime);
+    }
+    hash *= S::Vi32::set1(0x27d4_eb2d);
+    hash ^ hash.shr_const::<15>()
+}
+
+/// Dots `offset` with a gradient picked by `hash`. Every component of the gradient is 1 or -1,
+/// picked by the low bits. From 3 dimensions on, the next two bits zero one component (or none in
+/// 3 dimensions), like the edge gradients of improved Perlin noise, which reduces axis artifacts.
+#[inline(always)]
+fn gradient_dot<S: Simd, const D: usize>(hash: S::Vi32, offset: &[S::Vf32; D]) -> S::Vf32 {
+    let zeroed = hash.shr(D as i32) & 3;
+    let mut sum = S::Vf32::zeroes();
+    for (i, &offset) in offset.iter().enumerate() {
+        let sign = hash.shl(31 - i as i32) & i32::MIN;
+        let mut term = (offset.bitcast_i32() ^ sign).bitcast_f32();
+        if D >= 3 {
+            let zero = zeroed.cmp_eq(S::Vi32::set1(i as i32)).bitcast_f32();
+            term = zero.blendv(term, S::Vf32::zeroes());
+        }
+        sum += term;
+    }
+    sum
+}
+
+/// Splits points into their lattice cells and their offsets within them.
+#[inline(always)]
+fn split_cells<S: Simd, const D: usize>(point: &[S::Vf32; D]) -> ([S::Vi32; D], [S::Vf32; D]) {
+    let floor = point.map(|x| x.floor());
+    (
+        floor.map(|x| x.cast_i32()),
+        core::array::from_fn(|i| point[i] - floor[i]),
+    )
+}
+
+/// Simplex noise of `D` dimensions, within `[-1, 1]`.
+#[inline(always)]
+pub fn simplex<S: Simd, const D: usize>(point: [S::Vf32; D], seed: i32) -> S::Vf32 {
+    assert!((2..=4).contains(&D));
+    let (zero, one) = (S::Vf32::zeroes(), S::Vf32::set1(1.0));
+
+    // Skew the point to find the hypercube cell of its simplex, then unskew the cell's origin to
+    // get the offset from the first corner.
+    let skew = point.iter().fold(zero, |sum, &x| sum + x) * S::Vf32::set1(SKEW[D]);
+    let cell = point.map(|x| (x + skew).floor());
+    let unskew = cell.iter().fold(zero, |sum, &x| sum + x) * S::Vf32::set1(UNSKEW[D]);
+    let offset: [S::Vf32; D] = core::array::from_fn(|i| point[i] - (cell[i] - unskew));
+    let cell = cell.map(|x| x.cast_i32());
+
+    // The simplex is picked by the order of the offsets: the corners step one axis at a time, from
+    // the axis with the largest offset to the one with the smallest.
+    let mut rank = [zero; D];
+    for i in 0..D {
+        for j in i + 1..D {
+            let greater = offset[i].cmp_gt(offset[j]);
+            rank[i] += greater.blendv(zero, one);
+            rank[j] += greater.blendv(one, zero);
+        }
+    }
+
+    let seed = S::Vi32::set1(seed);
+    let radius = S::Vf32::set1(SIMPLEX_RADIUS[D]);
+    let mut sum = zero;
+    for corner in 0..=D {
+        let step: [S::Vf32; D] = core::array::from_fn(|i| {
+            rank[i]
+                .cmp_gte(S::Vf32::set1((D - corner) as f32))
+                .blendv(zero, one)
+        });
+        let corner_unskew = S::Vf32::set1(corner as f32 * UNSKEW[D]);
+        let distance: [S::Vf32; D] = core::array::from_fn(|i| offset[i] - step[i] + corner_unskew);
+        let corner_cell: [S::Vi32; D] = core::array::from_fn(|i| cell[i] + step[i].cast_i32());
+
+        let falloff = distance
+            .iter()
+            .fold(radius, |t, &d| d.neg_mul_add(d, t))
+            .max(zero);
+        let falloff = falloff * falloff;
+        sum = (falloff * falloff).mul_add(
+            gradient_dot::<S, D>(hash::<S, D>(seed, &corner_cell), &distance),
+            sum,
+        );
+    }
+    clamp_unit(sum * S::Vf32::set1(SIMPLEX_SCALE[D]))
+}
+
+/// Perlin noise of `D` dimensions, within `[-1, 1]`. It is zero at every lattice point.
+#[inline(always)]
+pub fn perlin<S: Simd, const D: usize>(point: [S::Vf32; D], seed: i32) -> S::Vf32 {
+    assert!((2..=4).contains(&D));
+    let (cell, offset) = split_cells::<S, D>(&point);
+    let seed = S::Vi32::set1(seed);
+    let one = S::Vf32::set1(1.0);
+
+    // Bit `i` of a corner's index says whether it is on the far side of axis `i`.
+    let mut corners = [S::Vf32::zeroes(); 16];
+    for (index, value) in corners[..1 << D].iter_mut().enumerate() {
+        let far = |i: usize| index >> i & 1 == 1;
+        let corner_cell: [S::Vi32; D] = core::array::from_fn(|i| {
+            if far(i) {
+                cell[i] + S::Vi32::set1(1)
+            } else {
+                cell[i]
+            }
+        });
+        let distance: [S::Vf32; D] =
+            core::array::from_fn(|i| if far(i) { offset[i] - one } else { offset[i] });
+        *value = gradient_dot::<S, D>(hash::<S, D>(seed, &corner_cell), &distance);
+    }
+
+    // Interpolate along one axis at a time, which halves the corners left.
+    let mut count = 1 << D;
+    for &t in &offset {
+        let fade = t
+            * t
+            * t
+            * t.mul_add(
+                t.mul_sub(S::Vf32::set1(6.0), S::Vf32::set1(15.0)),
+                S::Vf32::set1(10.0),
+            );
+        count /= 2;
+        for i in 0..count {
+            let (near, far) = (corners[2 * i], corners[2 * i + 1]);
+            corners[i] = fade.mul_add(far - near, near);
+        }
+    }
+    clamp_unit(corners[0] * S::Vf32::set1(PERLIN_SCALE[D]))
+}
+
+/// Cellular noise of `D` dimensions: the distance to the nearest feature point, where every
+/// lattice cell holds one at a random position.
+#[inline(always)]
+pub fn cellular<S: Simd, const D: usize>(point: [S::Vf32; D], seed: i32) -> S::Vf32 {
+    assert!((2..=4).contains(&D));
+    let (cell, offset) = split_cells::<S, D>(&point);
+    let seed = S::Vi32::set1(seed);
+
+    // The nearest feature point is always within the neighbouring cells.
+    let mut nearest = S::Vf32::set1(f32::MAX);
+    for neighbour in 0..3usize.pow(D as u32) {
+        let step = |i: usize| (neighbour / 3usize.pow(i as u32) % 3) as i32 - 1;
+        let neighbour_cell: [S::Vi32; D] =
+            core::array::from_fn(|i| cell[i] + S::Vi32::set1(step(i)));
+        let hash = hash::<S, D>(seed, &neighbour_cell);
+
+        let mut distance = S::Vf32::zeroes();
+        for (i, &offset) in offset.iter().enumerate() {
+            let jitter = (hash * S::Vi32::set1(JITTER_MULTIPLIERS[i]))
+                .shr_const::<8>()
+                .cast_f32()
+                * S::Vf32::set1(1.0 / (1 << 24) as f32);
+            let d = jitter + S::Vf32::set1(step(i) as f32) - offset;
+            distance = d.mul_add(d, distance);
+        }
+        nearest = nearest.min(distance);
+    }
+    nearest.sqrt()
+}
+
+/// Samples noise as described by `settings`, including the frequency and the fractal octaves.
+#[inline(always)]
+pub fn noise<S: Simd, const D: usize>(settings: &NoiseSettings, point: [S::Vf32; D]) -> S::Vf32 {
+    let lacunarity = S::Vf32::set1(settings.lacunarity);
+    let mut point = point.map(|x| x * S::Vf32::set1(settings.frequency));
+    let mut amplitude = 1.0;
+    let mut total_amplitude = 0.0;
+    let mut sum = S::Vf32::zeroes();
+
+    for octave in 0..settings.octaves.max(1) {
+        let seed = settings.seed.wrapping_add(octave as i32);
+        let value = match settings.noise {
+            NoiseType::Simplex => simplex::<S, D>(point, seed),
+            NoiseType::Perlin => perlin::<S, D>(point, seed),
+            NoiseType::Cellular => cellular::<S, D>(point, seed),
+        };
+        sum = value.mul_add(S::Vf32::set1(amplitude), sum);
+        total_amplitude += amplitude;
+        amplitude *= settings.gain;
+        point = point.map(|x| x * lacunarity);
+    }
+    sum * S::Vf32::set1(1.0 / total_amplitude)
+}
+
+/// Fills `output` with noise sampled on a grid of `size` points along each axis, one unit apart,
+/// starting at `origin`. The first axis varies fastest, so a 2D grid is stored row by row.
+/// Panics if `output` doesn't have exactly as many elements as the grid.
+#[inline(always)]
+pub fn fill_grid<S: Simd, const D: usize>(
+    settings: &NoiseSettings,
+    origin: [f32; D],
+    size: [usize; D],
+    output: &mut [f32],
+) {
+    assert_eq!(output.len(), size.iter().product::<usize>());
+    if output.is_empty() {
+        return;
+    }
+
+    let width = S::Vf32::WIDTH;
+    let lanes = S::Vf32::lane_indices();
+
+    for (row_index, row) in output.chunks_mut(size[0]).enumerate() {
+        // The coordinates along every axis but the first are the same for the whole row.
+        let mut point = [S::Vf32::zeroes(); D];
+        let mut rest = row_index;
+        for axis in 1..D {
+            point[axis] = S::Vf32::set1(origin[axis] + (rest % size[axis]) as f32);
+            rest /= size[axis];
+        }
+
+        for (chunk_index, chunk) in row.chunks_mut(width).enumerate() {
+            point[0] = S::Vf32::set1(origin[0] + (chunk_index * width) as f32) + lanes;
+            noise::<S, D>(settings, point).copy_to_slice(chunk);
+        }
+    }
+}
+
+/// Fills `output` with noise sampled at the points whose coordinates along each axis are given by
+/// the matching elements of `points`. Panics if any of them has a different length from `output`.
+#[inline(always)]
+pub fn fill_points<S: Simd, const D: usize>(
+    settings: &NoiseSettings,
+    points: [&[f32]; D],
+    output: &mut [f32],
+) {
+    for axis in points {
+        assert_eq!(axis.len(), output.len());
+    }
+
+    let width = S::Vf32::WIDTH;
+    for (chunk_index, chunk) in output.chunks_mut(width).enumerate() {
+        let start = chunk_index * width;
+        let point = points.map(|axis| S::Vf32::load_from_slice(&axis[start..start + chunk.len()]));
+        noise::<S, D>(settings, point).copy_to_slice(chunk);
+    }
+}
+
+simd_runtime_generate!(
+    /// Fills `output` with 2D noise on a grid, using the best engine available at runtime.
+    /// See `fill_grid`.
+    pub fn fill_grid_2d(
+        settings: NoiseSettings,
+        origin: [f32; 2],
+        size: [usize; 2],
+        output: &mut [f32],
+    ) {
+        fill_grid::<S, 2>(&settings, origin, size, output);
+    }
+);
+
+simd_runtime_generate!(
+    /// Fills `output` with 3D noise on a grid, using the best engine available at runtime.
+    /// See `fill_grid`.
+    pub fn fill_grid_3d(
+        settings: NoiseSettings,
+        origin: [f32; 3],
+        size: [usize; 3],
+        output: &mut [f32],
+    ) {
+        fill_grid::<S, 3>(&settings, origin, size, output);
+    }
+);
+
+simd_runtime_generate!(
+    /// Fills `output` with 4D noise on a grid, using the best engine available at runtime.
+    /// See `fill_grid`.
+    pub fn fill_grid_4d(
+        settings: NoiseSettings,
+        origin: [f32; 4],
+        size: [usize; 4],
+        output: &mut [f32],
+    ) {
+        fill_grid::<S, 4>(&settings, origin, size, output);
+    }
+);
+
+simd_runtime_generate!(
+    /// Fills `output` with 2D noise at the given points, using the best engine available at
+    /// runtime. See `fill_points`.
+    pub fn fill_points_2d(settings: NoiseSettings, x: &[f32], y: &[f32], output: &mut [f32]) {
+        fill_points::<S, 2>(&settings, [x, y], output);
+    }
+);
+
+simd_runtime_generate!(
+    /// Fills `output` with 3D noise at the given points, using the best engine available at
+    /// runtime. See `fill_points`.
+    pub fn fill_points_3d(
+        settings: NoiseSettings,
+        x: &[f32],
+        y: &[f32],
+        z: &[f32],
+        output: &mut [f32],
+    ) {
+        fill_points::<S, 3>(&settings, [x, y, z], output);
+    }
+);
+
+simd_runtime_generate!(
+    /// Fills `output` with 4D noise at the given points, using the best engine available at
+    /// runtime. See `fill_points`.
+    pub fn fill_points_4d(
+        settings: NoiseSettings,
+        x: &[f32],
+        y: &[f32],
+        z: &[f32],
+        w: &[f32],
+        output: &mut [f32],
+    ) {
+        fill_points::<S, 4>(&settings, [x, y, z, w], output);
+    }
+);
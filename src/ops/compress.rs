@@ -0,0 +1,319 @@
+use super::*;
+
+// Lookup tables for compressing (left-packing) and expanding lanes by a bit mask. They are all
+// indexed by a movemask-style integer with one bit per lane.
+
+/// Nibble-packed `_mm256_permutevar8x32_epi32` indices that move the selected 32 bit lanes to the front.
+const COMPRESS_DWORDS: [u32; 256] = {
+    let mut table = [0; 256];
+    let mut mask = 0;
+    while mask < 256 {
+        let mut count = 0;
+        let mut lane = 0;
+        while lane < 8 {
+            if mask & (1 << lane) != 0 {
+                table[mask] |= (lane as u32) << (count * 4);
+                count += 1;
+            }
+            lane += 1;
+        }
+        mask += 1;
+    }
+    table
+};
+
+/// Nibble-packed `_mm256_permutevar8x32_epi32` indices that spread the front 32 bit lanes into the selected ones.
+const EXPAND_DWORDS: [u32; 256] = {
+    let mut table = [0; 256];
+    let mut mask = 0;
+    while mask < 256 {
+        let mut count = 0;
+        let mut lane = 0;
+        while lane < 8 {
+            if mask & (1 << lane) != 0 {
+                table[mask] |= (count as u32) << (lane * 4);
+                count += 1;
+            }
+            lane += 1;
+        }
+        mask += 1;
+    }
+    table
+};
+
+/// Same as `COMPRESS_DWORDS`, except each selected 64 bit lane moves as a pair of 32 bit lanes.
+const COMPRESS_QWORDS: [u32; 16] = {
+    let mut table = [0; 16];
+    let mut mask = 0;
+    while mask < 16 {
+        let mut count = 0;
+        let mut lane = 0;
+        while lane < 4 {
+            if mask & (1 << lane) != 0 {
+                table[mask] |= ((lane * 2) as u32) << (count * 8);
+                table[mask] |= ((lane * 2 + 1) as u32) << (count * 8 + 4);
+                count += 1;
+            }
+            lane += 1;
+        }
+        mask += 1;
+    }
+    table
+};
+
+/// Same as `EXPAND_DWORDS`, except each selected 64 bit lane moves as a pair of 32 bit lanes.
+const EXPAND_QWORDS: [u32; 16] = {
+    let mut table = [0; 16];
+    let mut mask = 0;
+    while mask < 16 {
+        let mut count = 0;
+        let mut lane = 0;
+        while lane < 4 {
+            if mask & (1 << lane) != 0 {
+                table[mask] |= ((count * 2) as u32) << (lane * 8);
+                table[mask] |= ((count * 2 + 1) as u32) << (lane * 8 + 4);
+                count += 1;
+            }
+            lane += 1;
+        }
+        mask += 1;
+    }
+    table
+};
+
+/// `_mm_shuffle_epi8` indices that move the selected bytes of an 8 byte group to the front.
+/// Unused bytes are 0x80 so they shuffle in zeroes.
+const COMPRESS_BYTES: [u64; 256] = {
+    let mut table = [0; 256];
+    let mut mask = 0;
+    while mask < 256 {
+        let mut bytes = [0x80u8; 8];
+        let mut count = 0;
+        let mut lane = 0;
+        while lane < 8 {
+            if mask & (1 << lane) != 0 {
+                bytes[count] = lane as u8;
+                count += 1;
+            }
+            lane += 1;
+        }
+        table[mask] = u64::from_le_bytes(bytes);
+        mask += 1;
+    }
+    table
+};
+
+/// `_mm_shuffle_epi8` indices that spread the front bytes of an 8 byte group into the selected ones.
+/// Unused bytes are 0x80 so they shuffle in zeroes.
+const EXPAND_BYTES: [u64; 256] = {
+    let mut table = [0; 256];
+    let mut mask = 0;
+    while mask < 256 {
+        let mut bytes = [0x80u8; 8];
+        let mut count = 0;
+        let mut lane = 0;
+        while lane < 8 {
+            if mask & (1 << lane) != 0 {
+                bytes[lane] = count;
+                count += 1;
+            }
+            lane += 1;
+        }
+        table[mask] = u64::from_le_bytes(bytes);
+        mask += 1;
+    }
+    table
+};
+
+/// `_mm_shuffle_epi8` indices that move the selected 16 bit lanes of a 128 bit vector to the front.
+const COMPRESS_WORDS: [[u8; 16]; 256] = {
+    let mut table = [[0x80; 16]; 256];
+    let mut mask = 0;
+    while mask < 256 {
+        let mut count = 0;
+        let mut lane = 0;
+        while lane < 8 {
+            if mask & (1 << lane) != 0 {
+                table[mask][count * 2] = lane as u8 * 2;
+                table[mask][count * 2 + 1] = lane as u8 * 2 + 1;
+                count += 1;
+            }
+            lane += 1;
+        }
+        mask += 1;
+    }
+    table
+};
+
+/// `_mm_shuffle_epi8` indices that spread the front 16 bit lanes of a 128 bit vector into the selected ones.
+const EXPAND_WORDS: [[u8; 16]; 256] = {
+    let mut table = [[0x80; 16]; 256];
+    let mut mask = 0;
+    while mask < 256 {
+        let mut count = 0;
+        let mut lane = 0;
+        while lane < 8 {
+            if mask & (1 << lane) != 0 {
+                table[mask][lane * 2] = count * 2;
+                table[mask][lane * 2 + 1] = count * 2 + 1;
+                count += 1;
+            }
+            lane += 1;
+        }
+        mask += 1;
+    }
+    table
+};
+
+/// Repeated in every byte, used to offset a group of shuffle indices without touching the 0x80 flag.
+const BYTE_ONES: u64 = 0x0101_0101_0101_0101;
+
+impl_op! {
+    fn compress_epi32<binary> {
+        for Avx2(a: __m256i, mask: u32) -> (__m256i, usize) {
+            let shifts = _mm256_setr_epi32(0, 4, 8, 12, 16, 20, 24, 28);
+            let packed_indices = _mm256_set1_epi32(COMPRESS_DWORDS[mask as usize] as i32);
+            let indices = _mm256_srlv_epi32(packed_indices, shifts);
+            let count = mask.count_ones() as usize;
+            let permuted = _mm256_permutevar8x32_epi32(a, indices);
+            let lanes = _mm256_setr_epi32(0, 1, 2, 3, 4, 5, 6, 7);
+            let keep = _mm256_cmpgt_epi32(_mm256_set1_epi32(count as i32), lanes);
+            (_mm256_and_si256(permuted, keep), count)
+        }
+    }
+}
+
+impl_op! {
+    fn expand_epi32<binary> {
+        for Avx2(a: __m256i, mask: u32) -> __m256i {
+            let shifts = _mm256_setr_epi32(0, 4, 8, 12, 16, 20, 24, 28);
+            let packed_indices = _mm256_set1_epi32(EXPAND_DWORDS[mask as usize] as i32);
+            let indices = _mm256_srlv_epi32(packed_indices, shifts);
+            let permuted = _mm256_permutevar8x32_epi32(a, indices);
+            let bits = _mm256_setr_epi32(1, 2, 4, 8, 16, 32, 64, 128);
+            let selected = _mm256_and_si256(_mm256_set1_epi32(mask as i32), bits);
+            let keep = _mm256_cmpeq_epi32(selected, bits);
+            _mm256_and_si256(permuted, keep)
+        }
+    }
+}
+
+impl_op! {
+    fn compress_epi64<binary> {
+        for Avx2(a: __m256i, mask: u32) -> (__m256i, usize) {
+            let shifts = _mm256_setr_epi32(0, 4, 8, 12, 16, 20, 24, 28);
+            let packed_indices = _mm256_set1_epi32(COMPRESS_QWORDS[mask as usize] as i32);
+            let indices = _mm256_srlv_epi32(packed_indices, shifts);
+            let count = mask.count_ones() as usize;
+            let permuted = _mm256_permutevar8x32_epi32(a, indices);
+            let lanes = _mm256_setr_epi32(0, 1, 2, 3, 4, 5, 6, 7);
+            let keep = _mm256_cmpgt_epi32(_mm256_set1_epi32(count as i32 * 2), lanes);
+            (_mm256_and_si256(permuted, keep), count)
+        }
+    }
+}
+
+impl_op! {
+    fn expand_epi64<binary> {
+        for Avx2(a: __m256i, mask: u32) -> __m256i {
+            let shifts = _mm256_setr_epi32(0, 4, 8, 12, 16, 20, 24, 28);
+            let packed_indices = _mm256_set1_epi32(EXPAND_QWORDS[mask as usize] as i32);
+            let indices = _mm256_srlv_epi32(packed_indices, shifts);
+            let permuted = _mm256_permutevar8x32_epi32(a, indices);
+            let bits = _mm256_setr_epi64x(1, 2, 4, 8);
+            let selected = _mm256_and_si256(_mm256_set1_epi64x(mask as i64), bits);
+            let keep = _mm256_cmpeq_epi64(selected, bits);
+            _mm256_and_si256(permuted, keep)
+        }
+    }
+}
+
+impl_op! {
+    fn compress_epi8<binary> {
+        for Avx2(a: __m256i, mask: u32) -> (__m256i, usize) {
+            // Each 128 bit half is packed with a byte shuffle built from two 8 byte table entries,
+            // then the halves are joined by storing them back to back.
+            let mut buffer = [0i8; 48];
+            let halves = [_mm256_castsi256_si128(a), _mm256_extracti128_si256(a, 1)];
+            let mut count = 0;
+            for (i, half) in halves.into_iter().enumerate() {
+                let half_mask = (mask >> (i * 16)) as usize;
+                let (low, high) = (half_mask & 0xFF, (half_mask >> 8) & 0xFF);
+                let low_count = low.count_ones() as usize * 8;
+
+                let low_bytes = COMPRESS_BYTES[low] as u128 & ((1u128 << low_count) - 1);
+                let high_bytes = ((COMPRESS_BYTES[high] + BYTE_ONES * 8) as u128) << low_count;
+                let padding_bits = u128::MAX.checked_shl(low_count as u32 + 64).unwrap_or(0);
+                let padding = u128::from_le_bytes([0x80; 16]) & padding_bits;
+                let control = core::mem::transmute::<u128, __m128i>(low_bytes | high_bytes | padding);
+
+                let packed = _mm_shuffle_epi8(half, control);
+                _mm_storeu_si128(buffer.as_mut_ptr().add(count) as *mut __m128i, packed);
+                count += (half_mask & 0xFFFF).count_ones() as usize;
+            }
+            (_mm256_loadu_si256(buffer.as_ptr() as *const __m256i), count)
+        }
+    }
+}
+
+impl_op! {
+    fn expand_epi8<binary> {
+        for Avx2(a: __m256i, mask: u32) -> __m256i {
+            let mut buffer = [0i8; 48];
+            _mm256_storeu_si256(buffer.as_mut_ptr() as *mut __m256i, a);
+            let mut halves = [_mm_setzero_si128(); 2];
+            let mut count = 0;
+            for (i, half) in halves.iter_mut().enumerate() {
+                let half_mask = (mask >> (i * 16)) as usize;
+                let (low, high) = (half_mask & 0xFF, (half_mask >> 8) & 0xFF);
+                let low_count = low.count_ones() as u64;
+
+                let low_bytes = EXPAND_BYTES[low] as u128;
+                let high_bytes = ((EXPAND_BYTES[high] + BYTE_ONES * low_count) as u128) << 64;
+                let control = core::mem::transmute::<u128, __m128i>(low_bytes | high_bytes);
+
+                let source = _mm_loadu_si128(buffer.as_ptr().add(count) as *const __m128i);
+                *half = _mm_shuffle_epi8(source, control);
+                count += (half_mask & 0xFFFF).count_ones() as usize;
+            }
+            _mm256_set_m128i(halves[1], halves[0])
+        }
+    }
+}
+
+impl_op! {
+    fn compress_epi16<binary> {
+        for Avx2(a: __m256i, mask: u32) -> (__m256i, usize) {
+            let mut buffer = [0i16; 24];
+            let halves = [_mm256_castsi256_si128(a), _mm256_extracti128_si256(a, 1)];
+            let mut count = 0;
+            for (i, half) in halves.into_iter().enumerate() {
+                let half_mask = ((mask >> (i * 8)) & 0xFF) as usize;
+                let control = core::mem::transmute::<[u8; 16], __m128i>(COMPRESS_WORDS[half_mask]);
+                let packed = _mm_shuffle_epi8(half, control);
+                _mm_storeu_si128(buffer.as_mut_ptr().add(count) as *mut __m128i, packed);
+                count += half_mask.count_ones() as usize;
+            }
+            (_mm256_loadu_si256(buffer.as_ptr() as *const __m256i), count)
+        }
+    }
+}
+
+impl_op! {
+    fn expand_epi16<binary> {
+        for Avx2(a: __m256i, mask: u32) -> __m256i {
+            let mut buffer = [0i16; 24];
+            _mm256_storeu_si256(buffer.as_mut_ptr() as *mut __m256i, a);
+            let mut halves = [_mm_setzero_si128(); 2];
+            let mut count = 0;
+            for (i, half) in halves.iter_mut().enumerate() {
+                let half_mask = ((mask >> (i * 8)) & 0xFF) as usize;
+                let control = core::mem::transmute::<[u8; 16], __m128i>(EXPAND_WORDS[half_mask]);
+                let source = _mm_loadu_si128(buffer.as_ptr().add(count) as *const __m128i);
+                *half = _mm_shuffle_epi8(source, control);
+                count += half_mask.count_ones() as usize;
+            }
+            _mm256_set_m128i(halves[1], halves[0])
+        }
+    }
+}
@@ -325,6 +325,225 @@ impl_op! {
     }
 }
 
+impl_imm8_op! {
+    fn extract<f32, const I: i32> {
+        for Avx2(a: __m256) -> f32 {
+            f32::from_bits(_mm256_extract_epi32::<I>(_mm256_castps_si256(a)) as u32)
+        }
+        for Scalar(a: f32) -> f32 {
+            // The only lane is 0, and the other engines reject indices out of range at compile
+            // time.
+            assert!(I == 0);
+            a
+        }
+    }
+}
+
+impl_imm8_op! {
+    fn insert<f32, const I: i32> {
+        for Avx2(a: __m256, value: f32) -> __m256 {
+            let result = _mm256_insert_epi32::<I>(_mm256_castps_si256(a), value.to_bits() as i32);
+            _mm256_castsi256_ps(result)
+        }
+        for Scalar(_a: f32, value: f32) -> f32 {
+            assert!(I == 0);
+            value
+        }
+    }
+}
+
+impl_imm8_op! {
+    fn align_right<f32, const K: i32> {
+        for Avx2(a: __m256, b: __m256) -> __m256 {
+            let (a, b) = (_mm256_castps_si256(a), _mm256_castps_si256(b));
+            let result = Ops::<Avx2, binary>::align_right_bytes(a, b, K * 4);
+            _mm256_castsi256_ps(result)
+        }
+        for Scalar(a: f32, b: f32) -> f32 {
+            if K == 0 {
+                b
+            } else {
+                a
+            }
+        }
+    }
+}
+
+impl_imm8_op! {
+    fn shift_lanes_up<f32, const K: i32> {
+        for Avx2(a: __m256, fill: f32) -> __m256 {
+            let (a, fill) = (_mm256_castps_si256(a), _mm256_castps_si256(_mm256_set1_ps(fill)));
+            let result = Ops::<Avx2, binary>::align_right_bytes(a, fill, 32 - K * 4);
+            _mm256_castsi256_ps(result)
+        }
+        for Scalar(a: f32, fill: f32) -> f32 {
+            if K == 0 {
+                a
+            } else {
+                fill
+            }
+        }
+    }
+}
+
+impl_imm8_op! {
+    fn shift_lanes_down<f32, const K: i32> {
+        for Avx2(a: __m256, fill: f32) -> __m256 {
+            let (a, fill) = (_mm256_castps_si256(a), _mm256_castps_si256(_mm256_set1_ps(fill)));
+            let result = Ops::<Avx2, binary>::align_right_bytes(fill, a, K * 4);
+            _mm256_castsi256_ps(result)
+        }
+        for Scalar(a: f32, fill: f32) -> f32 {
+            if K == 0 {
+                a
+            } else {
+                fill
+            }
+        }
+    }
+}
+
+impl_op! {
+    fn compress<f32> {
+        for Avx2(a: __m256, mask: __m256) -> (__m256, usize) {
+            let mask = _mm256_movemask_ps(mask) as u32;
+            let (result, count) = Ops::<Avx2, binary>::compress_epi32(_mm256_castps_si256(a), mask);
+            (_mm256_castsi256_ps(result), count)
+        }
+        for Scalar(a: f32, mask: f32) -> (f32, usize) {
+            if mask.to_bits() == 0 {
+                (0.0, 0)
+            } else {
+                (a, 1)
+            }
+        }
+    }
+}
+
+impl_op! {
+    fn expand<f32> {
+        for Avx2(a: __m256, mask: __m256) -> __m256 {
+            let mask = _mm256_movemask_ps(mask) as u32;
+            _mm256_castsi256_ps(Ops::<Avx2, binary>::expand_epi32(_mm256_castps_si256(a), mask))
+        }
+        for Scalar(a: f32, mask: f32) -> f32 {
+            if mask.to_bits() == 0 {
+                0.0
+            } else {
+                a
+            }
+        }
+    }
+}
+
+impl_op! {
+    fn sort_lanes<f32> {
+        for Avx2(a: __m256) -> __m256 {
+            let min_max = |a: __m256i, b: __m256i| {
+                let (a, b) = (_mm256_castsi256_ps(a), _mm256_castsi256_ps(b));
+                let (min, max) = (_mm256_min_ps(a, b), _mm256_max_ps(a, b));
+                (_mm256_castps_si256(min), _mm256_castps_si256(max))
+            };
+            let sorted = Ops::<Avx2, binary>::bitonic_sort(_mm256_castps_si256(a), 4, min_max);
+            _mm256_castsi256_ps(sorted)
+        }
+        for Scalar(a: f32) -> f32 {
+            a
+        }
+    }
+}
+
+impl_op! {
+    fn merge_sorted<f32> {
+        for Avx2(a: __m256, b: __m256) -> (__m256, __m256) {
+            let min_max = |a: __m256i, b: __m256i| {
+                let (a, b) = (_mm256_castsi256_ps(a), _mm256_castsi256_ps(b));
+                let (min, max) = (_mm256_min_ps(a, b), _mm256_max_ps(a, b));
+                (_mm256_castps_si256(min), _mm256_castps_si256(max))
+            };
+            let (a, b) = (_mm256_castps_si256(a), _mm256_castps_si256(b));
+            let (low, high) = Ops::<Avx2, binary>::bitonic_merge(a, b, 4, min_max);
+            (_mm256_castsi256_ps(low), _mm256_castsi256_ps(high))
+        }
+        for Scalar(a: f32, b: f32) -> (f32, f32) {
+            (a.min(b), a.max(b))
+        }
+    }
+}
+
+impl_op! {
+    fn zip<f32> {
+        for Avx2(a: __m256, b: __m256) -> (__m256, __m256) {
+            // The unpacks interleave within each 128 bit half, so swap the middle halves after.
+            let low = _mm256_unpacklo_ps(a, b);
+            let high = _mm256_unpackhi_ps(a, b);
+            (
+                _mm256_permute2f128_ps(low, high, 0x20),
+                _mm256_permute2f128_ps(low, high, 0x31),
+            )
+        }
+        for Scalar(a: f32, b: f32) -> (f32, f32) {
+            (a, b)
+        }
+    }
+}
+
+impl_op! {
+    fn unzip<f32> {
+        for Avx2(a: __m256, b: __m256) -> (__m256, __m256) {
+            // Each 128 bit half gets the even (or odd) lanes of both inputs, then moving the
+            // 64 bit pairs puts those of `a` before those of `b`.
+            let even = _mm256_shuffle_ps(a, b, 0b10_00_10_00);
+            let odd = _mm256_shuffle_ps(a, b, 0b11_01_11_01);
+            let order = |v: __m256| {
+                _mm256_castpd_ps(_mm256_permute4x64_pd(_mm256_castps_pd(v), 0b11_01_10_00))
+            };
+            (order(even), order(odd))
+        }
+        for Scalar(a: f32, b: f32) -> (f32, f32) {
+            (a, b)
+        }
+    }
+}
+
+impl_op! {
+    fn zip3<f32> {
+        for Avx2(a: __m256, b: __m256, c: __m256) -> (__m256, __m256, __m256) {
+            // Move every lane to where it goes in its output vector, then the outputs are blends.
+            let a = _mm256_permutevar8x32_ps(a, _mm256_setr_epi32(0, 3, 6, 1, 4, 7, 2, 5));
+            let b = _mm256_permutevar8x32_ps(b, _mm256_setr_epi32(5, 0, 3, 6, 1, 4, 7, 2));
+            let c = _mm256_permutevar8x32_ps(c, _mm256_setr_epi32(2, 5, 0, 3, 6, 1, 4, 7));
+            (
+                _mm256_blend_ps(_mm256_blend_ps(a, b, 0x92), c, 0x24),
+                _mm256_blend_ps(_mm256_blend_ps(a, b, 0x24), c, 0x49),
+                _mm256_blend_ps(_mm256_blend_ps(a, b, 0x49), c, 0x92),
+            )
+        }
+        for Scalar(a: f32, b: f32, c: f32) -> (f32, f32, f32) {
+            (a, b, c)
+        }
+    }
+}
+
+impl_op! {
+    fn unzip3<f32> {
+        for Avx2(a: __m256, b: __m256, c: __m256) -> (__m256, __m256, __m256) {
+            // Blend together the lanes of each component, then put them in order.
+            let x = _mm256_blend_ps(_mm256_blend_ps(a, b, 0x92), c, 0x24);
+            let y = _mm256_blend_ps(_mm256_blend_ps(a, b, 0x24), c, 0x49);
+            let z = _mm256_blend_ps(_mm256_blend_ps(a, b, 0x49), c, 0x92);
+            (
+                _mm256_permutevar8x32_ps(x, _mm256_setr_epi32(0, 3, 6, 1, 4, 7, 2, 5)),
+                _mm256_permutevar8x32_ps(y, _mm256_setr_epi32(1, 4, 7, 2, 5, 0, 3, 6)),
+                _mm256_permutevar8x32_ps(z, _mm256_setr_epi32(2, 5, 0, 3, 6, 1, 4, 7)),
+            )
+        }
+        for Scalar(a: f32, b: f32, c: f32) -> (f32, f32, f32) {
+            (a, b, c)
+        }
+    }
+}
+
 impl_op! {
     fn horizontal_add<f32> {
         for Avx2(a: __m256) -> f32 {
@@ -429,3 +648,14 @@ impl_op! {
         }
     }
 }
+
+impl_op! {
+    fn store_streaming<f32> {
+        for Avx2(ptr: *mut f32, a: __m256) {
+            _mm256_stream_ps(ptr, a)
+        }
+        for Scalar(ptr: *mut f32, a: f32) {
+            unsafe { *ptr = a }
+        }
+    }
+}
@@ -315,14 +315,231 @@ impl_op! {
     }
 }
 
+impl_imm8_op! {
+    fn extract<f64, const I: i32> {
+        for Avx2(a: __m256d) -> f64 {
+            f64::from_bits(_mm256_extract_epi64::<I>(_mm256_castpd_si256(a)) as u64)
+        }
+        for Scalar(a: f64) -> f64 {
+            // The only lane is 0, and the other engines reject indices out of range at compile
+            // time.
+            assert!(I == 0);
+            a
+        }
+    }
+}
+
+impl_imm8_op! {
+    fn insert<f64, const I: i32> {
+        for Avx2(a: __m256d, value: f64) -> __m256d {
+            let result = _mm256_insert_epi64::<I>(_mm256_castpd_si256(a), value.to_bits() as i64);
+            _mm256_castsi256_pd(result)
+        }
+        for Scalar(_a: f64, value: f64) -> f64 {
+            assert!(I == 0);
+            value
+        }
+    }
+}
+
+impl_imm8_op! {
+    fn align_right<f64, const K: i32> {
+        for Avx2(a: __m256d, b: __m256d) -> __m256d {
+            let (a, b) = (_mm256_castpd_si256(a), _mm256_castpd_si256(b));
+            let result = Ops::<Avx2, binary>::align_right_bytes(a, b, K * 8);
+            _mm256_castsi256_pd(result)
+        }
+        for Scalar(a: f64, b: f64) -> f64 {
+            if K == 0 {
+                b
+            } else {
+                a
+            }
+        }
+    }
+}
+
+impl_imm8_op! {
+    fn shift_lanes_up<f64, const K: i32> {
+        for Avx2(a: __m256d, fill: f64) -> __m256d {
+            let (a, fill) = (_mm256_castpd_si256(a), _mm256_castpd_si256(_mm256_set1_pd(fill)));
+            let result = Ops::<Avx2, binary>::align_right_bytes(a, fill, 32 - K * 8);
+            _mm256_castsi256_pd(result)
+        }
+        for Scalar(a: f64, fill: f64) -> f64 {
+            if K == 0 {
+                a
+            } else {
+                fill
+            }
+        }
+    }
+}
+
+impl_imm8_op! {
+    fn shift_lanes_down<f64, const K: i32> {
+        for Avx2(a: __m256d, fill: f64) -> __m256d {
+            let (a, fill) = (_mm256_castpd_si256(a), _mm256_castpd_si256(_mm256_set1_pd(fill)));
+            let result = Ops::<Avx2, binary>::align_right_bytes(fill, a, K * 8);
+            _mm256_castsi256_pd(result)
+        }
+        for Scalar(a: f64, fill: f64) -> f64 {
+            if K == 0 {
+                a
+            } else {
+                fill
+            }
+        }
+    }
+}
+
+impl_op! {
+    fn compress<f64> {
+        for Avx2(a: __m256d, mask: __m256d) -> (__m256d, usize) {
+            let mask = _mm256_movemask_pd(mask) as u32;
+            let (result, count) = Ops::<Avx2, binary>::compress_epi64(_mm256_castpd_si256(a), mask);
+            (_mm256_castsi256_pd(result), count)
+        }
+        for Scalar(a: f64, mask: f64) -> (f64, usize) {
+            if mask.to_bits() == 0 {
+                (0.0, 0)
+            } else {
+                (a, 1)
+            }
+        }
+    }
+}
+
+impl_op! {
+    fn expand<f64> {
+        for Avx2(a: __m256d, mask: __m256d) -> __m256d {
+            let mask = _mm256_movemask_pd(mask) as u32;
+            _mm256_castsi256_pd(Ops::<Avx2, binary>::expand_epi64(_mm256_castpd_si256(a), mask))
+        }
+        for Scalar(a: f64, mask: f64) -> f64 {
+            if mask.to_bits() == 0 {
+                0.0
+            } else {
+                a
+            }
+        }
+    }
+}
+
+impl_op! {
+    fn sort_lanes<f64> {
+        for Avx2(a: __m256d) -> __m256d {
+            let min_max = |a: __m256i, b: __m256i| {
+                let (a, b) = (_mm256_castsi256_pd(a), _mm256_castsi256_pd(b));
+                let (min, max) = (_mm256_min_pd(a, b), _mm256_max_pd(a, b));
+                (_mm256_castpd_si256(min), _mm256_castpd_si256(max))
+            };
+            let sorted = Ops::<Avx2, binary>::bitonic_sort(_mm256_castpd_si256(a), 8, min_max);
+            _mm256_castsi256_pd(sorted)
+        }
+        for Scalar(a: f64) -> f64 {
+            a
+        }
+    }
+}
+
+impl_op! {
+    fn merge_sorted<f64> {
+        for Avx2(a: __m256d, b: __m256d) -> (__m256d, __m256d) {
+            let min_max = |a: __m256i, b: __m256i| {
+                let (a, b) = (_mm256_castsi256_pd(a), _mm256_castsi256_pd(b));
+                let (min, max) = (_mm256_min_pd(a, b), _mm256_max_pd(a, b));
+                (_mm256_castpd_si256(min), _mm256_castpd_si256(max))
+            };
+            let (a, b) = (_mm256_castpd_si256(a), _mm256_castpd_si256(b));
+            let (low, high) = Ops::<Avx2, binary>::bitonic_merge(a, b, 8, min_max);
+            (_mm256_castsi256_pd(low), _mm256_castsi256_pd(high))
+        }
+        for Scalar(a: f64, b: f64) -> (f64, f64) {
+            (a.min(b), a.max(b))
+        }
+    }
+}
+
+impl_op! {
+    fn zip<f64> {
+        for Avx2(a: __m256d, b: __m256d) -> (__m256d, __m256d) {
+            // The unpacks interleave within each 128 bit half, so swap the middle halves after.
+            let low = _mm256_unpacklo_pd(a, b);
+            let high = _mm256_unpackhi_pd(a, b);
+            (
+                _mm256_permute2f128_pd(low, high, 0x20),
+                _mm256_permute2f128_pd(low, high, 0x31),
+            )
+        }
+        for Scalar(a: f64, b: f64) -> (f64, f64) {
+            (a, b)
+        }
+    }
+}
+
+impl_op! {
+    fn unzip<f64> {
+        for Avx2(a: __m256d, b: __m256d) -> (__m256d, __m256d) {
+            let even = _mm256_unpacklo_pd(a, b);
+            let odd = _mm256_unpackhi_pd(a, b);
+            (
+                _mm256_permute4x64_pd(even, 0b11_01_10_00),
+                _mm256_permute4x64_pd(odd, 0b11_01_10_00),
+            )
+        }
+        for Scalar(a: f64, b: f64) -> (f64, f64) {
+            (a, b)
+        }
+    }
+}
+
+impl_op! {
+    fn zip3<f64> {
+        for Avx2(a: __m256d, b: __m256d, c: __m256d) -> (__m256d, __m256d, __m256d) {
+            // Move every lane to where it goes in its output vector, then the outputs are blends.
+            let a = _mm256_permute4x64_pd(a, 0b01_10_11_00);
+            let b = _mm256_permute4x64_pd(b, 0b10_11_00_01);
+            let c = _mm256_permute4x64_pd(c, 0b11_00_01_10);
+            (
+                _mm256_blend_pd(_mm256_blend_pd(a, b, 0b0010), c, 0b0100),
+                _mm256_blend_pd(_mm256_blend_pd(a, b, 0b1001), c, 0b0010),
+                _mm256_blend_pd(_mm256_blend_pd(a, b, 0b0100), c, 0b1001),
+            )
+        }
+        for Scalar(a: f64, b: f64, c: f64) -> (f64, f64, f64) {
+            (a, b, c)
+        }
+    }
+}
+
+impl_op! {
+    fn unzip3<f64> {
+        for Avx2(a: __m256d, b: __m256d, c: __m256d) -> (__m256d, __m256d, __m256d) {
+            // Blend together the lanes of each component, then put them in order.
+            let x = _mm256_blend_pd(_mm256_blend_pd(a, b, 0b0100), c, 0b0010);
+            let y = _mm256_blend_pd(_mm256_blend_pd(a, b, 0b1001), c, 0b0100);
+            let z = _mm256_blend_pd(_mm256_blend_pd(a, b, 0b0010), c, 0b1001);
+            (
+                _mm256_permute4x64_pd(x, 0b01_10_11_00),
+                _mm256_permute4x64_pd(y, 0b10_11_00_01),
+                _mm256_permute4x64_pd(z, 0b11_00_01_10),
+            )
+        }
+        for Scalar(a: f64, b: f64, c: f64) -> (f64, f64, f64) {
+            (a, b, c)
+        }
+    }
+}
+
 impl_op! {
     fn horizontal_add<f64> {
         for Avx2(a: __m256d) -> f64 {
+            // Each 128 bit half holds two lanes, so one horizontal add sums each half.
             let a = _mm256_hadd_pd(a, a);
-            let b = _mm256_hadd_pd(a, a);
 
-            let first = _mm_cvtsd_f64(_mm256_extractf128_pd(b, 0));
-            let second = _mm_cvtsd_f64(_mm256_extractf128_pd(b, 1));
+            let first = _mm_cvtsd_f64(_mm256_extractf128_pd(a, 0));
+            let second = _mm_cvtsd_f64(_mm256_extractf128_pd(a, 1));
 
             first + second
         }
@@ -426,3 +643,14 @@ impl_op! {
         }
     }
 }
+
+impl_op! {
+    fn store_streaming<f64> {
+        for Avx2(ptr: *mut f64, a: __m256d) {
+            _mm256_stream_pd(ptr, a)
+        }
+        for Scalar(ptr: *mut f64, a: f64) {
+            unsafe { *ptr = a }
+        }
+    }
+}
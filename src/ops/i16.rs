@@ -177,6 +177,137 @@ impl_op! {
     }
 }
 
+impl_imm8_op! {
+    fn extract<i16, const I: i32> {
+        for Avx2(a: __m256i) -> i16 {
+            _mm256_extract_epi16::<I>(a) as i16
+        }
+        for Scalar(a: i16) -> i16 {
+            // The only lane is 0, and the other engines reject indices out of range at compile
+            // time.
+            assert!(I == 0);
+            a
+        }
+    }
+}
+
+impl_imm8_op! {
+    fn insert<i16, const I: i32> {
+        for Avx2(a: __m256i, value: i16) -> __m256i {
+            _mm256_insert_epi16::<I>(a, value)
+        }
+        for Scalar(_a: i16, value: i16) -> i16 {
+            assert!(I == 0);
+            value
+        }
+    }
+}
+
+impl_imm8_op! {
+    fn align_right<i16, const K: i32> {
+        for Avx2(a: __m256i, b: __m256i) -> __m256i {
+            Ops::<Avx2, binary>::align_right_bytes(a, b, K * 2)
+        }
+        for Scalar(a: i16, b: i16) -> i16 {
+            if K == 0 {
+                b
+            } else {
+                a
+            }
+        }
+    }
+}
+
+impl_imm8_op! {
+    fn shift_lanes_up<i16, const K: i32> {
+        for Avx2(a: __m256i, fill: i16) -> __m256i {
+            Ops::<Avx2, binary>::align_right_bytes(a, _mm256_set1_epi16(fill), 32 - K * 2)
+        }
+        for Scalar(a: i16, fill: i16) -> i16 {
+            if K == 0 {
+                a
+            } else {
+                fill
+            }
+        }
+    }
+}
+
+impl_imm8_op! {
+    fn shift_lanes_down<i16, const K: i32> {
+        for Avx2(a: __m256i, fill: i16) -> __m256i {
+            Ops::<Avx2, binary>::align_right_bytes(_mm256_set1_epi16(fill), a, K * 2)
+        }
+        for Scalar(a: i16, fill: i16) -> i16 {
+            if K == 0 {
+                a
+            } else {
+                fill
+            }
+        }
+    }
+}
+
+impl_op! {
+    fn compress<i16> {
+        for Avx2(a: __m256i, mask: __m256i) -> (__m256i, usize) {
+            // Saturating to 8 bits keeps the sign, so one movemask bit is left per 16 bit lane.
+            let bits = _mm256_movemask_epi8(_mm256_packs_epi16(mask, mask)) as u32;
+            let mask = (bits & 0xFF) | ((bits >> 8) & 0xFF00);
+            Ops::<Avx2, binary>::compress_epi16(a, mask)
+        }
+        for Scalar(a: i16, mask: i16) -> (i16, usize) {
+            if mask == 0 {
+                (0, 0)
+            } else {
+                (a, 1)
+            }
+        }
+    }
+}
+
+impl_op! {
+    fn expand<i16> {
+        for Avx2(a: __m256i, mask: __m256i) -> __m256i {
+            // Saturating to 8 bits keeps the sign, so one movemask bit is left per 16 bit lane.
+            let bits = _mm256_movemask_epi8(_mm256_packs_epi16(mask, mask)) as u32;
+            let mask = (bits & 0xFF) | ((bits >> 8) & 0xFF00);
+            Ops::<Avx2, binary>::expand_epi16(a, mask)
+        }
+        for Scalar(a: i16, mask: i16) -> i16 {
+            if mask == 0 {
+                0
+            } else {
+                a
+            }
+        }
+    }
+}
+
+impl_op! {
+    fn sort_lanes<i16> {
+        for Avx2(a: __m256i) -> __m256i {
+            let min_max = |a: __m256i, b: __m256i| (_mm256_min_epi16(a, b), _mm256_max_epi16(a, b));
+            Ops::<Avx2, binary>::bitonic_sort(a, 2, min_max)
+        }
+        for Scalar(a: i16) -> i16 {
+            a
+        }
+    }
+}
+
+impl_op! {
+    fn merge_sorted<i16> {
+        for Avx2(a: __m256i, b: __m256i) -> (__m256i, __m256i) {
+            let min_max = |a: __m256i, b: __m256i| (_mm256_min_epi16(a, b), _mm256_max_epi16(a, b));
+            Ops::<Avx2, binary>::bitonic_merge(a, b, 2, min_max)
+        }
+        for Scalar(a: i16, b: i16) -> (i16, i16) {
+            (a.min(b), a.max(b))
+        }
+    }
+}
+
 impl_op! {
     fn shl<i16> {
         for Avx2(a: __m256i, rhs: i32) -> __m256i {
@@ -312,3 +443,14 @@ impl_op! {
         }
     }
 }
+
+impl_op! {
+    fn store_streaming<i16> {
+        for Avx2(ptr: *mut i16, a: __m256i) {
+            _mm256_stream_si256(ptr as *mut __m256i, a)
+        }
+        for Scalar(ptr: *mut i16, a: i16) {
+            unsafe { *ptr = a }
+        }
+    }
+}
@@ -177,6 +177,133 @@ impl_op! {
     }
 }
 
+impl_imm8_op! {
+    fn extract<i32, const I: i32> {
+        for Avx2(a: __m256i) -> i32 {
+            _mm256_extract_epi32::<I>(a)
+        }
+        for Scalar(a: i32) -> i32 {
+            // The only lane is 0, and the other engines reject indices out of range at compile
+            // time.
+            assert!(I == 0);
+            a
+        }
+    }
+}
+
+impl_imm8_op! {
+    fn insert<i32, const I: i32> {
+        for Avx2(a: __m256i, value: i32) -> __m256i {
+            _mm256_insert_epi32::<I>(a, value)
+        }
+        for Scalar(_a: i32, value: i32) -> i32 {
+            assert!(I == 0);
+            value
+        }
+    }
+}
+
+impl_imm8_op! {
+    fn align_right<i32, const K: i32> {
+        for Avx2(a: __m256i, b: __m256i) -> __m256i {
+            Ops::<Avx2, binary>::align_right_bytes(a, b, K * 4)
+        }
+        for Scalar(a: i32, b: i32) -> i32 {
+            if K == 0 {
+                b
+            } else {
+                a
+            }
+        }
+    }
+}
+
+impl_imm8_op! {
+    fn shift_lanes_up<i32, const K: i32> {
+        for Avx2(a: __m256i, fill: i32) -> __m256i {
+            Ops::<Avx2, binary>::align_right_bytes(a, _mm256_set1_epi32(fill), 32 - K * 4)
+        }
+        for Scalar(a: i32, fill: i32) -> i32 {
+            if K == 0 {
+                a
+            } else {
+                fill
+            }
+        }
+    }
+}
+
+impl_imm8_op! {
+    fn shift_lanes_down<i32, const K: i32> {
+        for Avx2(a: __m256i, fill: i32) -> __m256i {
+            Ops::<Avx2, binary>::align_right_bytes(_mm256_set1_epi32(fill), a, K * 4)
+        }
+        for Scalar(a: i32, fill: i32) -> i32 {
+            if K == 0 {
+                a
+            } else {
+                fill
+            }
+        }
+    }
+}
+
+impl_op! {
+    fn compress<i32> {
+        for Avx2(a: __m256i, mask: __m256i) -> (__m256i, usize) {
+            let mask = _mm256_movemask_ps(_mm256_castsi256_ps(mask)) as u32;
+            Ops::<Avx2, binary>::compress_epi32(a, mask)
+        }
+        for Scalar(a: i32, mask: i32) -> (i32, usize) {
+            if mask == 0 {
+                (0, 0)
+            } else {
+                (a, 1)
+            }
+        }
+    }
+}
+
+impl_op! {
+    fn expand<i32> {
+        for Avx2(a: __m256i, mask: __m256i) -> __m256i {
+            let mask = _mm256_movemask_ps(_mm256_castsi256_ps(mask)) as u32;
+            Ops::<Avx2, binary>::expand_epi32(a, mask)
+        }
+        for Scalar(a: i32, mask: i32) -> i32 {
+            if mask == 0 {
+                0
+            } else {
+                a
+            }
+        }
+    }
+}
+
+impl_op! {
+    fn sort_lanes<i32> {
+        for Avx2(a: __m256i) -> __m256i {
+            let min_max = |a: __m256i, b: __m256i| (_mm256_min_epi32(a, b), _mm256_max_epi32(a, b));
+            Ops::<Avx2, binary>::bitonic_sort(a, 4, min_max)
+        }
+        for Scalar(a: i32) -> i32 {
+            a
+        }
+    }
+}
+
+impl_op! {
+    fn merge_sorted<i32> {
+        for Avx2(a: __m256i, b: __m256i) -> (__m256i, __m256i) {
+            let min_max = |a: __m256i, b: __m256i| (_mm256_min_epi32(a, b), _mm256_max_epi32(a, b));
+            Ops::<Avx2, binary>::bitonic_merge(a, b, 4, min_max)
+        }
+        for Scalar(a: i32, b: i32) -> (i32, i32) {
+            (a.min(b), a.max(b))
+        }
+    }
+}
+
 impl_op! {
     fn shl<i32> {
         for Avx2(a: __m256i, rhs: i32) -> __m256i {
@@ -334,3 +461,14 @@ impl_op! {
         }
     }
 }
+
+impl_op! {
+    fn store_streaming<i32> {
+        for Avx2(ptr: *mut i32, a: __m256i) {
+            _mm256_stream_si256(ptr as *mut __m256i, a)
+        }
+        for Scalar(ptr: *mut i32, a: i32) {
+            unsafe { *ptr = a }
+        }
+    }
+}
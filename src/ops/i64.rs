@@ -188,6 +188,139 @@ impl_op! {
     }
 }
 
+impl_imm8_op! {
+    fn extract<i64, const I: i32> {
+        for Avx2(a: __m256i) -> i64 {
+            _mm256_extract_epi64::<I>(a)
+        }
+        for Scalar(a: i64) -> i64 {
+            // The only lane is 0, and the other engines reject indices out of range at compile
+            // time.
+            assert!(I == 0);
+            a
+        }
+    }
+}
+
+impl_imm8_op! {
+    fn insert<i64, const I: i32> {
+        for Avx2(a: __m256i, value: i64) -> __m256i {
+            _mm256_insert_epi64::<I>(a, value)
+        }
+        for Scalar(_a: i64, value: i64) -> i64 {
+            assert!(I == 0);
+            value
+        }
+    }
+}
+
+impl_imm8_op! {
+    fn align_right<i64, const K: i32> {
+        for Avx2(a: __m256i, b: __m256i) -> __m256i {
+            Ops::<Avx2, binary>::align_right_bytes(a, b, K * 8)
+        }
+        for Scalar(a: i64, b: i64) -> i64 {
+            if K == 0 {
+                b
+            } else {
+                a
+            }
+        }
+    }
+}
+
+impl_imm8_op! {
+    fn shift_lanes_up<i64, const K: i32> {
+        for Avx2(a: __m256i, fill: i64) -> __m256i {
+            Ops::<Avx2, binary>::align_right_bytes(a, _mm256_set1_epi64x(fill), 32 - K * 8)
+        }
+        for Scalar(a: i64, fill: i64) -> i64 {
+            if K == 0 {
+                a
+            } else {
+                fill
+            }
+        }
+    }
+}
+
+impl_imm8_op! {
+    fn shift_lanes_down<i64, const K: i32> {
+        for Avx2(a: __m256i, fill: i64) -> __m256i {
+            Ops::<Avx2, binary>::align_right_bytes(_mm256_set1_epi64x(fill), a, K * 8)
+        }
+        for Scalar(a: i64, fill: i64) -> i64 {
+            if K == 0 {
+                a
+            } else {
+                fill
+            }
+        }
+    }
+}
+
+impl_op! {
+    fn compress<i64> {
+        for Avx2(a: __m256i, mask: __m256i) -> (__m256i, usize) {
+            let mask = _mm256_movemask_pd(_mm256_castsi256_pd(mask)) as u32;
+            Ops::<Avx2, binary>::compress_epi64(a, mask)
+        }
+        for Scalar(a: i64, mask: i64) -> (i64, usize) {
+            if mask == 0 {
+                (0, 0)
+            } else {
+                (a, 1)
+            }
+        }
+    }
+}
+
+impl_op! {
+    fn expand<i64> {
+        for Avx2(a: __m256i, mask: __m256i) -> __m256i {
+            let mask = _mm256_movemask_pd(_mm256_castsi256_pd(mask)) as u32;
+            Ops::<Avx2, binary>::expand_epi64(a, mask)
+        }
+        for Scalar(a: i64, mask: i64) -> i64 {
+            if mask == 0 {
+                0
+            } else {
+                a
+            }
+        }
+    }
+}
+
+impl_op! {
+    fn sort_lanes<i64> {
+        for Avx2(a: __m256i) -> __m256i {
+            let min_max = |a: __m256i, b: __m256i| {
+                let greater = _mm256_cmpgt_epi64(a, b);
+                (_mm256_blendv_epi8(a, b, greater), _mm256_blendv_epi8(b, a, greater))
+            };
+            Ops::<Avx2, binary>::bitonic_sort(a, 8, min_max)
+        }
+        for Scalar(a: i64) -> i64 {
+            a
+        }
+    }
+}
+
+impl_op! {
+    fn merge_sorted<i64> {
+        for Avx2(a: __m256i, b: __m256i) -> (__m256i, __m256i) {
+            let min_max = |a: __m256i, b: __m256i| {
+                let greater = _mm256_cmpgt_epi64(a, b);
+                (_mm256_blendv_epi8(a, b, greater), _mm256_blendv_epi8(b, a, greater))
+            };
+            Ops::<Avx2, binary>::bitonic_merge(a, b, 8, min_max)
+        }
+        for Scalar(a: i64, b: i64) -> (i64, i64) {
+            (a.min(b), a.max(b))
+        }
+    }
+}
+
 impl_op! {
     fn shl<i64> {
         for Avx2(a: __m256i, rhs: i32) -> __m256i {
@@ -250,6 +383,21 @@ impl_op! {
     }
 }
 
+impl_op! {
+    fn truncate_i32<i64> {
+        for Avx2(low: __m256i, high: __m256i) -> __m256i {
+            // Gather the low halves of the lanes into the lower 128 bits, then join both vectors.
+            let order = _mm256_setr_epi32(0, 2, 4, 6, 1, 3, 5, 7);
+            let low = _mm256_permutevar8x32_epi32(low, order);
+            let high = _mm256_permutevar8x32_epi32(high, order);
+            _mm256_permute2x128_si256(low, high, 0x20)
+        }
+        for Scalar(low: i64, _high: i64) -> i32 {
+            low as i32
+        }
+    }
+}
+
 impl_op! {
     fn bitcast_f64<i64> {
         for Avx2(a: __m256i) -> __m256d {
@@ -342,3 +490,14 @@ impl_op! {
         }
     }
 }
+
+impl_op! {
+    fn store_streaming<i64> {
+        for Avx2(ptr: *mut i64, a: __m256i) {
+            _mm256_stream_si256(ptr as *mut __m256i, a)
+        }
+        for Scalar(ptr: *mut i64, a: i64) {
+            unsafe { *ptr = a }
+        }
+    }
+}
@@ -182,6 +182,133 @@ impl_op! {
     }
 }
 
+impl_imm8_op! {
+    fn extract<i8, const I: i32> {
+        for Avx2(a: __m256i) -> i8 {
+            _mm256_extract_epi8::<I>(a) as i8
+        }
+        for Scalar(a: i8) -> i8 {
+            // The only lane is 0, and the other engines reject indices out of range at compile
+            // time.
+            assert!(I == 0);
+            a
+        }
+    }
+}
+
+impl_imm8_op! {
+    fn insert<i8, const I: i32> {
+        for Avx2(a: __m256i, value: i8) -> __m256i {
+            _mm256_insert_epi8::<I>(a, value)
+        }
+        for Scalar(_a: i8, value: i8) -> i8 {
+            assert!(I == 0);
+            value
+        }
+    }
+}
+
+impl_imm8_op! {
+    fn align_right<i8, const K: i32> {
+        for Avx2(a: __m256i, b: __m256i) -> __m256i {
+            Ops::<Avx2, binary>::align_right_bytes(a, b, K)
+        }
+        for Scalar(a: i8, b: i8) -> i8 {
+            if K == 0 {
+                b
+            } else {
+                a
+            }
+        }
+    }
+}
+
+impl_imm8_op! {
+    fn shift_lanes_up<i8, const K: i32> {
+        for Avx2(a: __m256i, fill: i8) -> __m256i {
+            Ops::<Avx2, binary>::align_right_bytes(a, _mm256_set1_epi8(fill), 32 - K)
+        }
+        for Scalar(a: i8, fill: i8) -> i8 {
+            if K == 0 {
+                a
+            } else {
+                fill
+            }
+        }
+    }
+}
+
+impl_imm8_op! {
+    fn shift_lanes_down<i8, const K: i32> {
+        for Avx2(a: __m256i, fill: i8) -> __m256i {
+            Ops::<Avx2, binary>::align_right_bytes(_mm256_set1_epi8(fill), a, K)
+        }
+        for Scalar(a: i8, fill: i8) -> i8 {
+            if K == 0 {
+                a
+            } else {
+                fill
+            }
+        }
+    }
+}
+
+impl_op! {
+    fn compress<i8> {
+        for Avx2(a: __m256i, mask: __m256i) -> (__m256i, usize) {
+            let mask = _mm256_movemask_epi8(mask) as u32;
+            Ops::<Avx2, binary>::compress_epi8(a, mask)
+        }
+        for Scalar(a: i8, mask: i8) -> (i8, usize) {
+            if mask == 0 {
+                (0, 0)
+            } else {
+                (a, 1)
+            }
+        }
+    }
+}
+
+impl_op! {
+    fn expand<i8> {
+        for Avx2(a: __m256i, mask: __m256i) -> __m256i {
+            let mask = _mm256_movemask_epi8(mask) as u32;
+            Ops::<Avx2, binary>::expand_epi8(a, mask)
+        }
+        for Scalar(a: i8, mask: i8) -> i8 {
+            if mask == 0 {
+                0
+            } else {
+                a
+            }
+        }
+    }
+}
+
+impl_op! {
+    fn sort_lanes<i8> {
+        for Avx2(a: __m256i) -> __m256i {
+            let min_max = |a: __m256i, b: __m256i| (_mm256_min_epi8(a, b), _mm256_max_epi8(a, b));
+            Ops::<Avx2, binary>::bitonic_sort(a, 1, min_max)
+        }
+        for Scalar(a: i8) -> i8 {
+            a
+        }
+    }
+}
+
+impl_op! {
+    fn merge_sorted<i8> {
+        for Avx2(a: __m256i, b: __m256i) -> (__m256i, __m256i) {
+            let min_max = |a: __m256i, b: __m256i| (_mm256_min_epi8(a, b), _mm256_max_epi8(a, b));
+            Ops::<Avx2, binary>::bitonic_merge(a, b, 1, min_max)
+        }
+        for Scalar(a: i8, b: i8) -> (i8, i8) {
+            (a.min(b), a.max(b))
+        }
+    }
+}
+
 impl_op! {
     fn shl<i8> {
         for Avx2(a: __m256i, rhs: i32) -> __m256i {
@@ -264,6 +391,42 @@ impl_op! {
     }
 }
 
+impl_op! {
+    fn lookup_16<i8> {
+        for Avx2(a: __m256i, table: &[i8; 16]) -> __m256i {
+            let table = _mm256_broadcastsi128_si256(_mm_loadu_si128(table.as_ptr() as *const __m128i));
+            _mm256_shuffle_epi8(table, a)
+        }
+        for Scalar(a: i8, table: &[i8; 16]) -> i8 {
+            if a < 0 {
+                0
+            } else {
+                table[(a & 0x0F) as usize]
+            }
+        }
+    }
+}
+
+impl_op! {
+    fn permute<i8> {
+        for Avx2(a: __m256i, indices: __m256i) -> __m256i {
+            // Shuffle from both halves, then pick the result by bit 4 of each index.
+            let low = _mm256_permute2x128_si256(a, a, 0x00);
+            let high = _mm256_permute2x128_si256(a, a, 0x11);
+            let from_low = _mm256_shuffle_epi8(low, indices);
+            let from_high = _mm256_shuffle_epi8(high, indices);
+            _mm256_blendv_epi8(from_low, from_high, _mm256_slli_epi16(indices, 3))
+        }
+        for Scalar(a: i8, indices: i8) -> i8 {
+            if indices < 0 {
+                0
+            } else {
+                a
+            }
+        }
+    }
+}
+
 impl_op! {
     fn get_mask<i8> {
         for Avx2(val: __m256i) -> u32 {
@@ -341,6 +504,17 @@ impl_op! {
     }
 }
 
+impl_op! {
+    fn store_streaming<i8> {
+        for Avx2(ptr: *mut i8, a: __m256i) {
+            _mm256_stream_si256(ptr as *mut __m256i, a)
+        }
+        for Scalar(ptr: *mut i8, a: i8) {
+            unsafe { *ptr = a }
+        }
+    }
+}
+
 impl_op! {
     fn is_truthy<i8> {
         for Avx2(a: __m256i) -> bool {
@@ -0,0 +1,26 @@
+use super::*;
+
+impl_op! {
+    fn prefetch<binary> {
+        for Avx2(ptr: *const i8, hint: i32) {
+            match hint {
+                _MM_HINT_T0 => _mm_prefetch::<_MM_HINT_T0>(ptr),
+                _MM_HINT_T1 => _mm_prefetch::<_MM_HINT_T1>(ptr),
+                _MM_HINT_T2 => _mm_prefetch::<_MM_HINT_T2>(ptr),
+                _ => _mm_prefetch::<_MM_HINT_NTA>(ptr),
+            }
+        }
+        for Scalar(_ptr: *const i8, _hint: i32) {
+        }
+    }
+}
+
+impl_op! {
+    fn store_fence<binary> {
+        for Avx2() {
+            _mm_sfence()
+        }
+        for Scalar() {
+        }
+    }
+}
@@ -14,6 +14,10 @@ mod f32;
 mod f64;
 mod bit;
 mod casts;
+mod compress;
+mod memory;
+mod shuffle;
+mod sort;
 #[allow(non_camel_case_types)]
 pub struct binary;
 
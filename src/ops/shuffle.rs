@@ -0,0 +1,25 @@
+use super::*;
+
+macro_rules! alignr_epi8_match {
+    ($hi:expr, $lo:expr, $bytes:expr, $($n:literal)*) => {
+        match $bytes {
+            $($n => _mm256_alignr_epi8::<$n>($hi, $lo),)*
+            _ => $hi,
+        }
+    };
+}
+
+impl_op! {
+    fn align_right_bytes<binary> {
+        for Avx2(hi: __m256i, lo: __m256i, bytes: i32) -> __m256i {
+            // `_mm256_alignr_epi8` only shifts within 128 bit lanes, so first build the vector that
+            // straddles both inputs, then align against whichever half the shift lands in.
+            let mid = _mm256_permute2x128_si256(lo, hi, 0x21);
+            if bytes < 16 {
+                alignr_epi8_match!(mid, lo, bytes, 0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15)
+            } else {
+                alignr_epi8_match!(hi, mid, bytes - 16, 0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15)
+            }
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use super::*;
+
+// Bitonic sorting networks over a whole 256 bit register, shared by every lane type. Each
+// compare-exchange step pairs every lane with the lane whose index differs in one bit, takes the
+// minimum and maximum of each pair, and blends them back so the lower lane of an ascending pair
+// gets the minimum.
+
+impl_op! {
+    fn swap_adjacent_groups<binary> {
+        for Avx2(a: __m256i, bytes: usize) -> __m256i {
+            // Swaps every group of `bytes` bytes with its neighbour, so byte `i` moves to
+            // `i ^ bytes`.
+            match bytes {
+                1 => {
+                    let indices = _mm256_setr_epi8(
+                        1, 0, 3, 2, 5, 4, 7, 6, 9, 8, 11, 10, 13, 12, 15, 14,
+                        1, 0, 3, 2, 5, 4, 7, 6, 9, 8, 11, 10, 13, 12, 15, 14,
+                    );
+                    _mm256_shuffle_epi8(a, indices)
+                }
+                2 => _mm256_shufflehi_epi16(_mm256_shufflelo_epi16(a, 0xB1), 0xB1),
+                4 => _mm256_shuffle_epi32(a, 0xB1),
+                8 => _mm256_shuffle_epi32(a, 0x4E),
+                _ => _mm256_permute2x128_si256(a, a, 0x01),
+            }
+        }
+    }
+}
+
+impl_op! {
+    fn bitonic_blend_mask<binary> {
+        for Avx2(lane_bytes: usize, distance: usize, block: usize) -> __m256i {
+            // Lane `i` takes the maximum of its pair when it is the upper lane of an ascending pair
+            // or the lower lane of a descending one. Blocks of `block` lanes alternate between
+            // ascending and descending.
+            let mut mask = [0i8; 32];
+            for (byte, m) in mask.iter_mut().enumerate() {
+                let lane = byte / lane_bytes;
+                if ((lane & distance) != 0) != ((lane & block) != 0) {
+                    *m = -1;
+                }
+            }
+            _mm256_loadu_si256(mask.as_ptr() as *const __m256i)
+        }
+    }
+}
+
+impl Ops<Avx2, binary> {
+    /// Runs the compare-exchange steps from `distance` lanes down to neighbouring lanes, which sort
+    /// bitonic sequences of twice `distance` lanes. Blocks of `block` lanes alternate between
+    /// ascending and descending, unless `block` covers the whole register.
+    #[inline(always)]
+    unsafe fn bitonic_merge_steps(
+        mut a: __m256i,
+        lane_bytes: usize,
+        mut distance: usize,
+        block: usize,
+        min_max: impl Fn(__m256i, __m256i) -> (__m256i, __m256i),
+    ) -> __m256i {
+        while distance >= 1 {
+            let partner = Self::swap_adjacent_groups(a, distance * lane_bytes);
+            let (min, max) = min_max(a, partner);
+            let mask = Self::bitonic_blend_mask(lane_bytes, distance, block);
+            a = _mm256_blendv_epi8(min, max, mask);
+            distance /= 2;
+        }
+        a
+    }
+
+    /// Sorts the lanes of `a` in ascending order, given the lane size and a function returning the
+    /// lane-wise minimum and maximum of two vectors.
+    #[inline(always)]
+    pub unsafe fn bitonic_sort(
+        mut a: __m256i,
+        lane_bytes: usize,
+        min_max: impl Fn(__m256i, __m256i) -> (__m256i, __m256i) + Copy,
+    ) -> __m256i {
+        let lanes = 32 / lane_bytes;
+        let mut block = 2;
+        while block <= lanes {
+            a = Self::bitonic_merge_steps(a, lane_bytes, block / 2, block, min_max);
+            block *= 2;
+        }
+        a
+    }
+
+    /// Merges two sorted vectors, returning the lowest and highest halves of the combined lanes,
+    /// both sorted in ascending order.
+    #[inline(always)]
+    pub unsafe fn bitonic_merge(
+        a: __m256i,
+        b: __m256i,
+        lane_bytes: usize,
+        min_max: impl Fn(__m256i, __m256i) -> (__m256i, __m256i) + Copy,
+    ) -> (__m256i, __m256i) {
+        // Reversing `b` makes the concatenation bitonic. Swapping every group size from whole
+        // lanes up to the 128 bit halves reverses the lanes.
+        let mut reversed = b;
+        let mut bytes = lane_bytes;
+        while bytes < 32 {
+            reversed = Self::swap_adjacent_groups(reversed, bytes);
+            bytes *= 2;
+        }
+
+        let (low, high) = min_max(a, reversed);
+        let lanes = 32 / lane_bytes;
+        (
+            Self::bitonic_merge_steps(low, lane_bytes, lanes / 2, lanes * 2, min_max),
+            Self::bitonic_merge_steps(high, lane_bytes, lanes / 2, lanes * 2, min_max),
+        )
+    }
+}
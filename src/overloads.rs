@@ -464,6 +464,11 @@ macro_rules! impl_simd_base {
                 unsafe { Self(Ops::<$engine, $scalar_ty>::set1(x)) }
             }
 
+            #[inline(always)]
+            fn lane_indices() -> Self {
+                Self::from_fn(|lane| lane as $scalar_ty)
+            }
+
             #[inline(always)]
             unsafe fn load_from_array(array: Self::ArrayRepresentation) -> Self {
                 unsafe { Self(Ops::<$engine, $scalar_ty>::load_aligned(array.as_ptr())) }
@@ -489,6 +494,21 @@ macro_rules! impl_simd_base {
                 unsafe { Ops::<$engine, $scalar_ty>::store_aligned(ptr, self.0) }
             }
 
+            #[inline(always)]
+            unsafe fn copy_to_ptr_streaming(self, ptr: *mut Self::Scalar) {
+                unsafe { Ops::<$engine, $scalar_ty>::store_streaming(ptr, self.0) }
+            }
+
+            #[inline(always)]
+            fn store_fence() {
+                unsafe { Ops::<$engine, binary>::store_fence() }
+            }
+
+            #[inline(always)]
+            fn prefetch<L: Locality>(ptr: *const Self::Scalar) {
+                unsafe { Ops::<$engine, binary>::prefetch(ptr as *const i8, L::HINT) }
+            }
+
             #[inline(always)]
             unsafe fn underlying_value(self) -> Self::UnderlyingType {
                 self.0
@@ -634,6 +654,53 @@ macro_rules! impl_simd_base {
             fn horizontal_add($self) -> Self::HorizontalAddScalar {
                 $($hadd)*
             }
+
+            #[inline(always)]
+            fn extract<const I: i32>(self) -> Self::Scalar {
+                unsafe { Ops::<$engine, $scalar_ty>::extract::<I>(self.0) }
+            }
+
+            #[inline(always)]
+            fn insert<const I: i32>(self, value: Self::Scalar) -> Self {
+                unsafe { Self(Ops::<$engine, $scalar_ty>::insert::<I>(self.0, value)) }
+            }
+
+            #[inline(always)]
+            fn shift_lanes_up<const K: i32>(self, fill: Self::Scalar) -> Self {
+                unsafe { Self(Ops::<$engine, $scalar_ty>::shift_lanes_up::<K>(self.0, fill)) }
+            }
+
+            #[inline(always)]
+            fn shift_lanes_down<const K: i32>(self, fill: Self::Scalar) -> Self {
+                unsafe { Self(Ops::<$engine, $scalar_ty>::shift_lanes_down::<K>(self.0, fill)) }
+            }
+
+            #[inline(always)]
+            fn align_right<const K: i32>(self, lower: Self) -> Self {
+                unsafe { Self(Ops::<$engine, $scalar_ty>::align_right::<K>(self.0, lower.0)) }
+            }
+
+            #[inline(always)]
+            fn compress(self, mask: Self) -> (Self, usize) {
+                let (packed, count) = unsafe { Ops::<$engine, $scalar_ty>::compress(self.0, mask.0) };
+                (Self(packed), count)
+            }
+
+            #[inline(always)]
+            fn expand(self, mask: Self) -> Self {
+                unsafe { Self(Ops::<$engine, $scalar_ty>::expand(self.0, mask.0)) }
+            }
+
+            #[inline(always)]
+            fn sort_lanes(self) -> Self {
+                unsafe { Self(Ops::<$engine, $scalar_ty>::sort_lanes(self.0)) }
+            }
+
+            #[inline(always)]
+            fn merge_sorted(self, other: Self) -> (Self, Self) {
+                let (low, high) = unsafe { Ops::<$engine, $scalar_ty>::merge_sorted(self.0, other.0) };
+                (Self(low), Self(high))
+            }
         }
     };
 }
@@ -744,6 +811,30 @@ macro_rules! impl_simd_float {
                 unsafe { Self(Ops::<$engine, $scalar_ty>::rsqrt(self.0)) }
             }
 
+            #[inline(always)]
+            fn zip(self, other: Self) -> (Self, Self) {
+                let (low, high) = unsafe { Ops::<$engine, $scalar_ty>::zip(self.0, other.0) };
+                (Self(low), Self(high))
+            }
+
+            #[inline(always)]
+            fn unzip(self, other: Self) -> (Self, Self) {
+                let (even, odd) = unsafe { Ops::<$engine, $scalar_ty>::unzip(self.0, other.0) };
+                (Self(even), Self(odd))
+            }
+
+            #[inline(always)]
+            fn zip3(self, b: Self, c: Self) -> (Self, Self, Self) {
+                let (a, b, c) = unsafe { Ops::<$engine, $scalar_ty>::zip3(self.0, b.0, c.0) };
+                (Self(a), Self(b), Self(c))
+            }
+
+            #[inline(always)]
+            fn unzip3(self, b: Self, c: Self) -> (Self, Self, Self) {
+                let (x, y, z) = unsafe { Ops::<$engine, $scalar_ty>::unzip3(self.0, b.0, c.0) };
+                (Self(x), Self(y), Self(z))
+            }
+
             #[inline(always)]
             fn from_f64(value: f64) -> Self {
                 Self::set1(value as $scalar_ty)
@@ -782,6 +873,16 @@ macro_rules! impl_i8_simd_type {
                 ($i16_ty(a), $i16_ty(b))
             }
 
+            #[inline(always)]
+            fn permute(self, indices: Self) -> Self {
+                unsafe { Self(Ops::<$engine, i8>::permute(self.0, indices.0)) }
+            }
+
+            #[inline(always)]
+            fn lookup_16(self, table: &[i8; 16]) -> Self {
+                unsafe { Self(Ops::<$engine, i8>::lookup_16(self.0, table)) }
+            }
+
             #[inline(always)]
             fn get_mask(self) -> u32 {
                 unsafe { Ops::<$engine, i8>::get_mask(self.0) }
@@ -865,7 +966,7 @@ macro_rules! impl_i32_simd_type {
 }
 
 macro_rules! impl_i64_simd_type {
-    ($engine:ident, $i64_ty:ident, $f64_ty:ident) => {
+    ($engine:ident, $i64_ty:ident, $f64_ty:ident, $i32_ty:ident) => {
         impl_simd_base!($engine, $i64_ty, i64, |self| {
             self.partial_horizontal_add()
         });
@@ -884,6 +985,11 @@ macro_rules! impl_i64_simd_type {
                 unsafe { $f64_ty(Ops::<$engine, i64>::cast_f64(self.0)) }
             }
 
+            #[inline(always)]
+            fn truncate_to_i32(self, high: Self) -> <Self::Engine as Simd>::Vi32 {
+                unsafe { $i32_ty(Ops::<$engine, i64>::truncate_i32(self.0, high.0)) }
+            }
+
             #[inline(always)]
             fn partial_horizontal_add(self) -> i64 {
                 unsafe { Ops::<$engine, i64>::horizontal_add(self.0) }
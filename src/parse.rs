@@ -0,0 +1,403 @@
+//! Decimal number parsing from byte slices.
+//!
+//! Digit runs are located a vector at a time, see [slice algorithms](crate#slice-algorithms). The
+//! digits are then combined 8 at a time in a general purpose register, by reading each group of 8
+//! ASCII digits as a `u64` and merging neighbouring digits with three multiply-adds.
+//!
+//! Integers are an optional sign (signed types only) followed by digits. Floats are an optional
+//! sign, digits with an optional `.` and fraction, and an optional exponent such as `e-5`.
+
+use core::{fmt, marker::PhantomData};
+
+use crate::search::{as_i8_slice, find_first_by};
+use crate::{Simd, SimdBaseIo, SimdBaseOps, SimdConsts, SimdInt8};
+
+/// The reason a number couldn't be parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseNumberError {
+    /// The input doesn't start with a number.
+    NoDigits,
+    /// The number doesn't fit in the target type.
+    Overflow,
+    /// The number is followed by a byte that isn't allowed there.
+    UnexpectedByte,
+}
+
+impl fmt::Display for ParseNumberError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseNumberError::NoDigits => write!(f, "no digits found"),
+            ParseNumberError::Overflow => write!(f, "number too large to fit in target type"),
+            ParseNumberError::UnexpectedByte => write!(f, "unexpected byte after number"),
+        }
+    }
+}
+
+/// An error while parsing a delimiter-separated list, with the byte offset of the element that failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ListParseError {
+    pub position: usize,
+    pub error: ParseNumberError,
+}
+
+impl fmt::Display for ListParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} in element at index {}", self.error, self.position)
+    }
+}
+
+/// Number types that can be parsed by this module.
+pub trait ParseNumber: Sized + Copy {
+    /// Parses the number at the start of `input`, returning it with the number of bytes used.
+    fn parse_prefix<S: Simd>(input: &[u8]) -> Result<(Self, usize), ParseNumberError>;
+}
+
+/// Returns the number of ASCII digits at the start of `input`.
+#[inline(always)]
+fn digit_run<S: Simd>(input: &[u8]) -> usize {
+    let bytes = as_i8_slice(input);
+    let zero = S::Vi8::set1(b'0' as i8);
+    let nine = S::Vi8::set1(b'9' as i8);
+
+    let mut len = 0;
+    loop {
+        // A partial load is padded with zeroes, which aren't digits, so the run always ends there.
+        let chars = S::Vi8::load_from_slice(&bytes[len..]);
+        let is_digit = chars.cmp_gte(zero) & chars.cmp_lte(nine);
+        match is_digit.index_of_first_falsy() {
+            Some(index) => return len + index,
+            None => len += S::Vi8::WIDTH,
+        }
+    }
+}
+
+/// Converts 8 ASCII digits, most significant first, to their value.
+#[inline(always)]
+fn parse_eight_digits(digits: [u8; 8]) -> u64 {
+    let mut value = u64::from_le_bytes(digits) - 0x3030_3030_3030_3030;
+    value = (value.wrapping_mul(10) + (value >> 8)) & 0x00FF_00FF_00FF_00FF;
+    value = (value.wrapping_mul(100) + (value >> 16)) & 0x0000_FFFF_0000_FFFF;
+    (value.wrapping_mul(10000) + (value >> 32)) & 0xFFFF_FFFF
+}
+
+/// Appends `digits` to the decimal number `value`, returning `None` on overflow.
+#[inline(always)]
+fn accumulate_digits(mut value: u64, digits: &[u8]) -> Option<u64> {
+    let mut chunks = digits.chunks_exact(8);
+    for chunk in &mut chunks {
+        let chunk = parse_eight_digits(chunk.try_into().unwrap());
+        value = value.checked_mul(100_000_000)?.checked_add(chunk)?;
+    }
+
+    // Left pad the remainder with zeroes so it can go through the same path.
+    let rest = chunks.remainder();
+    if !rest.is_empty() {
+        let mut padded = [b'0'; 8];
+        padded[8 - rest.len()..].copy_from_slice(rest);
+        let chunk = parse_eight_digits(padded);
+        value = value
+            .checked_mul(10u64.pow(rest.len() as u32))?
+            .checked_add(chunk)?;
+    }
+
+    Some(value)
+}
+
+fn strip_leading_zeroes(digits: &[u8]) -> &[u8] {
+    let zeroes = digits.iter().take_while(|&&digit| digit == b'0').count();
+    &digits[zeroes..]
+}
+
+/// Splits off a leading `+` or `-`, returning whether the number is negative and the sign length.
+fn parse_sign(input: &[u8]) -> (bool, usize) {
+    match input.first() {
+        Some(b'-') => (true, 1),
+        Some(b'+') => (false, 1),
+        _ => (false, 0),
+    }
+}
+
+#[inline(always)]
+fn parse_unsigned<S: Simd>(input: &[u8]) -> Result<(u64, usize), ParseNumberError> {
+    let len = digit_run::<S>(input);
+    if len == 0 {
+        return Err(ParseNumberError::NoDigits);
+    }
+
+    // u64::MAX has 20 digits, so longer numbers overflow without needing to be read.
+    let digits = strip_leading_zeroes(&input[..len]);
+    if digits.len() > 20 {
+        return Err(ParseNumberError::Overflow);
+    }
+    let value = accumulate_digits(0, digits).ok_or(ParseNumberError::Overflow)?;
+    Ok((value, len))
+}
+
+#[inline(always)]
+fn parse_signed<S: Simd>(input: &[u8]) -> Result<(bool, u64, usize), ParseNumberError> {
+    let (negative, sign_len) = parse_sign(input);
+    let (magnitude, len) = parse_unsigned::<S>(&input[sign_len..])?;
+    Ok((negative, magnitude, sign_len + len))
+}
+
+macro_rules! impl_parse_unsigned {
+    ($($ty:ident),*) => {
+        $(
+            impl ParseNumber for $ty {
+                #[inline(always)]
+                fn parse_prefix<S: Simd>(input: &[u8]) -> Result<(Self, usize), ParseNumberError> {
+                    let (value, len) = parse_unsigned::<S>(input)?;
+                    let value = $ty::try_from(value).map_err(|_| ParseNumberError::Overflow)?;
+                    Ok((value, len))
+                }
+            }
+        )*
+    };
+}
+impl_parse_unsigned!(u8, u16, u32, u64, usize);
+
+macro_rules! impl_parse_signed {
+    ($($ty:ident),*) => {
+        $(
+            impl ParseNumber for $ty {
+                #[inline(always)]
+                fn parse_prefix<S: Simd>(input: &[u8]) -> Result<(Self, usize), ParseNumberError> {
+                    let (negative, magnitude, len) = parse_signed::<S>(input)?;
+                    let value = if negative {
+                        // The magnitude of MIN is one larger than MAX, so negate in i128.
+                        $ty::try_from(-(magnitude as i128))
+                    } else {
+                        $ty::try_from(magnitude)
+                    };
+                    let value = value.map_err(|_| ParseNumberError::Overflow)?;
+                    Ok((value, len))
+                }
+            }
+        )*
+    };
+}
+impl_parse_signed!(i8, i16, i32, i64, isize);
+
+/// The powers of ten that are exactly representable as an f64.
+const POWERS_OF_TEN: [f64; 23] = [
+    1e0, 1e1, 1e2, 1e3, 1e4, 1e5, 1e6, 1e7, 1e8, 1e9, 1e10, 1e11, 1e12, 1e13, 1e14, 1e15, 1e16,
+    1e17, 1e18, 1e19, 1e20, 1e21, 1e22,
+];
+
+/// The syntax of a float, split into its significant digits and a power of ten.
+struct Decimal {
+    negative: bool,
+    /// The significant digits as an integer, if there are at most 19 of them.
+    mantissa: Option<u64>,
+    exponent: i64,
+    len: usize,
+}
+
+#[inline(always)]
+fn parse_decimal<S: Simd>(input: &[u8]) -> Result<Decimal, ParseNumberError> {
+    let (negative, mut len) = parse_sign(input);
+
+    let integer_len = digit_run::<S>(&input[len..]);
+    let integer = &input[len..len + integer_len];
+    len += integer_len;
+
+    let mut fraction: &[u8] = &[];
+    if input.get(len) == Some(&b'.') {
+        let fraction_len = digit_run::<S>(&input[len + 1..]);
+        fraction = &input[len + 1..len + 1 + fraction_len];
+        len += 1 + fraction_len;
+    }
+
+    if integer.is_empty() && fraction.is_empty() {
+        return Err(ParseNumberError::NoDigits);
+    }
+
+    // The exponent is only part of the number if it has digits, so `1e` parses as `1`.
+    let mut exponent = 0i64;
+    if let Some(b'e' | b'E') = input.get(len) {
+        let (exponent_negative, sign_len) = parse_sign(&input[len + 1..]);
+        let start = len + 1 + sign_len;
+        let exponent_len = digit_run::<S>(&input[start..]);
+        if exponent_len > 0 {
+            // Huge exponents saturate, which still gives the right infinity or zero.
+            let digits = strip_leading_zeroes(&input[start..start + exponent_len]);
+            let value = accumulate_digits(0, digits)
+                .unwrap_or(u64::MAX)
+                .min(i64::MAX as u64);
+            exponent = if exponent_negative {
+                -(value as i64)
+            } else {
+                value as i64
+            };
+            len = start + exponent_len;
+        }
+    }
+
+    // Leading zeroes of the fraction only count if there was no integer part.
+    let integer = strip_leading_zeroes(integer);
+    let fraction_digits = if integer.is_empty() {
+        strip_leading_zeroes(fraction)
+    } else {
+        fraction
+    };
+    let mantissa = if integer.len() + fraction_digits.len() <= 19 {
+        accumulate_digits(0, integer).and_then(|value| accumulate_digits(value, fraction_digits))
+    } else {
+        None
+    };
+
+    Ok(Decimal {
+        negative,
+        mantissa,
+        exponent: exponent.saturating_sub(fraction.len() as i64),
+        len,
+    })
+}
+
+macro_rules! impl_parse_float {
+    ($ty:ident, $mantissa_bits:literal, $max_exact_exponent:literal) => {
+        impl ParseNumber for $ty {
+            #[inline(always)]
+            fn parse_prefix<S: Simd>(input: &[u8]) -> Result<(Self, usize), ParseNumberError> {
+                let decimal = parse_decimal::<S>(input)?;
+
+                // If the mantissa and the power of ten are both exact, a single rounding
+                // multiplication or division gives the correctly rounded result.
+                let exact_mantissa = decimal.mantissa.filter(|&m| m <= 1 << $mantissa_bits);
+                let exact_exponent = decimal.exponent.unsigned_abs() <= $max_exact_exponent;
+                let value = match exact_mantissa {
+                    Some(mantissa) if exact_exponent => {
+                        let power = POWERS_OF_TEN[decimal.exponent.unsigned_abs() as usize] as $ty;
+                        let value = if decimal.exponent < 0 {
+                            mantissa as $ty / power
+                        } else {
+                            mantissa as $ty * power
+                        };
+                        if decimal.negative { -value } else { value }
+                    }
+                    // Everything else goes through the slower, always exact, core parser. The
+                    // bytes were validated above, and its syntax is a superset of ours.
+                    _ => unsafe { core::str::from_utf8_unchecked(&input[..decimal.len]) }
+                        .parse::<$ty>()
+                        .map_err(|_| ParseNumberError::NoDigits)?,
+                };
+                Ok((value, decimal.len))
+            }
+        }
+    };
+}
+impl_parse_float!(f32, 24, 10);
+impl_parse_float!(f64, 53, 22);
+
+/// Parses the number at the start of `input`, returning it with the number of bytes used.
+#[inline(always)]
+pub fn parse_prefix<S: Simd, T: ParseNumber>(input: &[u8]) -> Result<(T, usize), ParseNumberError> {
+    T::parse_prefix::<S>(input)
+}
+
+/// Parses `input` as a single number, failing if anything follows it.
+#[inline(always)]
+pub fn parse<S: Simd, T: ParseNumber>(input: &[u8]) -> Result<T, ParseNumberError> {
+    let (value, len) = T::parse_prefix::<S>(input)?;
+    if len != input.len() {
+        return Err(ParseNumberError::UnexpectedByte);
+    }
+    Ok(value)
+}
+
+/// Returns the index of the first ASCII digit in `input`.
+#[inline(always)]
+pub fn find_digit<S: Simd>(input: &[u8]) -> Option<usize> {
+    let zero = S::Vi8::set1(b'0' as i8);
+    let nine = S::Vi8::set1(b'9' as i8);
+    find_first_by::<S>(input, |chars| chars.cmp_gte(zero) & chars.cmp_lte(nine))
+}
+
+/// An iterator over the numbers in a delimiter-separated list, created by `split_numbers`.
+/// It stops after the first error.
+pub struct DelimitedNumbers<'a, S: Simd, T: ParseNumber> {
+    input: &'a [u8],
+    position: usize,
+    delimiter: u8,
+    done: bool,
+    _marker: PhantomData<(S, T)>,
+}
+
+impl<S: Simd, T: ParseNumber> DelimitedNumbers<'_, S, T> {
+    /// The byte offset of the next element to be parsed.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl<S: Simd, T: ParseNumber> Iterator for DelimitedNumbers<'_, S, T> {
+    type Item = Result<T, ParseNumberError>;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done || self.position >= self.input.len() {
+            return None;
+        }
+
+        let input = self.input;
+        let rest = &input[self.position..];
+        let result = T::parse_prefix::<S>(rest).and_then(|(value, len)| match rest.get(len) {
+            None => {
+                self.done = true;
+                self.position += len;
+                Ok(value)
+            }
+            Some(&byte) if byte == self.delimiter => {
+                self.position += len + 1;
+                Ok(value)
+            }
+            Some(_) => Err(ParseNumberError::UnexpectedByte),
+        });
+
+        if result.is_err() {
+            self.done = true;
+        }
+        Some(result)
+    }
+}
+
+/// Iterates over the numbers in `input`, which are separated by `delimiter`. A trailing
+/// delimiter at the end of the input is allowed.
+#[inline(always)]
+pub fn split_numbers<S: Simd, T: ParseNumber>(
+    input: &[u8],
+    delimiter: u8,
+) -> DelimitedNumbers<'_, S, T> {
+    DelimitedNumbers {
+        input,
+        position: 0,
+        delimiter,
+        done: false,
+        _marker: PhantomData,
+    }
+}
+
+/// Parses numbers separated by `delimiter` into `output`, stopping when either runs out.
+/// Returns the number of values written and the number of bytes of `input` consumed, so a full
+/// `output` can be drained and the rest of the input parsed by calling this again.
+#[inline(always)]
+pub fn parse_list<S: Simd, T: ParseNumber>(
+    input: &[u8],
+    delimiter: u8,
+    output: &mut [T],
+) -> Result<(usize, usize), ListParseError> {
+    let mut numbers = split_numbers::<S, T>(input, delimiter);
+    let mut count = 0;
+    while count < output.len() {
+        let position = numbers.position();
+        match numbers.next() {
+            Some(Ok(value)) => {
+                output[count] = value;
+                count += 1;
+            }
+            Some(Err(error)) => return Err(ListParseError { position, error }),
+            None => break,
+        }
+    }
+    Ok((count, numbers.position()))
+}
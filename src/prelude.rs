@@ -1,14 +1,18 @@
 pub use super::{
-    fix_tuple_type, simd_compiletime_select, simd_invoke, simd_runtime_generate, Simd,
-    __simd_generate_base, simd_unsafe_generate_all,
+    __simd_generate_base, __simd_parse_fn, __simd_runtime_call, dispatch, fix_tuple_type,
+    simd_compiletime_select, simd_const, simd_invoke, simd_runtime_generate,
+    simd_unsafe_generate_all, Simd, SimdConst,
 };
 
 pub use super::invoking::*;
 
+#[cfg(feature = "macros")]
+pub use simdeez_macros::{simd_compiletime, simd_generic, simd_runtime};
+
 pub use crate::base::{
-    SimdArrayIterator, SimdArrayMutIterator, SimdBase, SimdBaseIo, SimdBaseOps, SimdConsts,
-    SimdFloat, SimdFloat32, SimdFloat64, SimdInt, SimdInt16, SimdInt32, SimdInt64, SimdInt8,
-    SimdIter,
+    Locality, PrefetchNta, PrefetchT0, PrefetchT1, PrefetchT2, ScanOp, SimdArrayIterator,
+    SimdArrayMutIterator, SimdBase, SimdBaseIo, SimdBaseOps, SimdConsts, SimdFloat, SimdFloat32,
+    SimdFloat64, SimdInt, SimdInt16, SimdInt32, SimdInt64, SimdInt8, SimdIter,
 };
 
 pub use paste::item as simdeez_paste_item;
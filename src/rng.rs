@@ -0,0 +1,369 @@
+//! Pseudo-random number generators running `S::Vi64::WIDTH` independent streams at once.
+//!
+//! Every lane of the 64 bit state is one stream, and the streams of a generator are numbered
+//! consecutively from the first stream it was created with. A stream only depends on the seed and
+//! its number, so the same seed gives the same numbers on every engine:
+//!
+//! - Lane `k` of a 64 bit output, such as `next_u64` or `next_f64`, is the next value of stream `k`.
+//! - Vectors with 32 bit lanes take `S::Vi32::WIDTH / S::Vi64::WIDTH` values from every stream, so
+//!   lane `j` is a value of stream `j % S::Vi64::WIDTH`, and the lanes of a stream hold consecutive
+//!   values in lane order.
+//! - Every pair of normal vectors is computed lane by lane from two consecutive uniform values of
+//!   the lane's stream.
+//!
+//! The float conversions and the `ln`, `sin` and `cos` approximations used by Box-Muller avoid
+//! multiply-adds, which aren't fused on every engine, so even the normals are identical across
+//! engines.
+
+use crate::{
+    Simd, SimdBaseIo, SimdBaseOps, SimdConsts, SimdFloat, SimdFloat32, SimdFloat64, SimdInt,
+    SimdInt32, SimdInt64,
+};
+
+/// The largest engine width for 64 bit lanes, so stream states can be staged in a fixed size buffer.
+const MAX_STREAMS: usize = 16;
+
+/// A generator of random vectors, built on a generator of 32 and 64 random bits per stream.
+pub trait SimdRng<S: Simd> {
+    /// Advances every stream by one value, returning 32 random bits per stream in the low half of
+    /// each lane. The high halves are zero.
+    fn next_u32_lanes(&mut self) -> S::Vi64;
+
+    /// Returns 64 random bits per stream.
+    fn next_u64(&mut self) -> S::Vi64;
+
+    /// Returns 32 random bits per lane.
+    #[inline(always)]
+    fn next_i32(&mut self) -> S::Vi32 {
+        let low = self.next_u32_lanes();
+        let high = if two_draws_per_i32::<S>() {
+            self.next_u32_lanes()
+        } else {
+            S::Vi64::zeroes()
+        };
+        low.truncate_to_i32(high)
+    }
+
+    /// Returns uniform floats in `[0, 1)`, with 24 random bits each.
+    #[inline(always)]
+    fn next_f32(&mut self) -> S::Vf32 {
+        unit_f32::<S>(self.next_i32())
+    }
+
+    /// Returns uniform floats in `[0, 1)`, with 53 random bits each.
+    #[inline(always)]
+    fn next_f64(&mut self) -> S::Vf64 {
+        unit_f64::<S>(self.next_u64())
+    }
+
+    /// Returns two vectors of independent standard normal floats, using the Box-Muller transform.
+    #[inline(always)]
+    fn next_normal_f32(&mut self) -> (S::Vf32, S::Vf32) {
+        // Both uniforms of a lane come from consecutive values of its stream.
+        let (low1, low2) = (self.next_u32_lanes(), self.next_u32_lanes());
+        let (high1, high2) = if two_draws_per_i32::<S>() {
+            (self.next_u32_lanes(), self.next_u32_lanes())
+        } else {
+            (S::Vi64::zeroes(), S::Vi64::zeroes())
+        };
+        let u1 = unit_f32::<S>(low1.truncate_to_i32(high1));
+        let u2 = unit_f32::<S>(low2.truncate_to_i32(high2));
+
+        let (mantissa, exponent) = split_f32::<S>(S::Vf32::set1(1.0) - u1);
+        let ln = ln_split(mantissa, exponent, 5);
+        box_muller(ln, u2, 6)
+    }
+
+    /// Returns two vectors of independent standard normal floats, using the Box-Muller transform.
+    #[inline(always)]
+    fn next_normal_f64(&mut self) -> (S::Vf64, S::Vf64) {
+        let u1 = self.next_f64();
+        let u2 = self.next_f64();
+
+        let (mantissa, exponent) = split_f64::<S>(S::Vf64::set1(1.0) - u1);
+        let ln = ln_split(mantissa, exponent, 12);
+        box_muller(ln, u2, 11)
+    }
+}
+
+/// Whether a `Vi32` takes two values from every stream rather than one, which is when its lanes
+/// come from two vectors of 64 bit lanes, joined by `truncate_to_i32`.
+#[inline(always)]
+fn two_draws_per_i32<S: Simd>() -> bool {
+    debug_assert!(S::Vi32::WIDTH <= 2 * S::Vi64::WIDTH);
+    S::Vi32::WIDTH > S::Vi64::WIDTH
+}
+
+/// Maps random bits to `[0, 1)` using the top 24 bits.
+#[inline(always)]
+fn unit_f32<S: Simd>(bits: S::Vi32) -> S::Vf32 {
+    bits.shr_const::<8>().cast_f32() * S::Vf32::set1(1.0 / (1u32 << 24) as f32)
+}
+
+/// Maps random bits to `[0, 1)` using the top 53 bits.
+#[inline(always)]
+fn unit_f64<S: Simd>(bits: S::Vi64) -> S::Vf64 {
+    bits.shr_const::<11>().cast_f64() * S::Vf64::set1(1.0 / (1u64 << 53) as f64)
+}
+
+/// Splits positive normal floats into a mantissa between `sqrt(1/2)` and `sqrt(2)` and an exponent.
+#[inline(always)]
+fn split_f32<S: Simd>(x: S::Vf32) -> (S::Vf32, S::Vf32) {
+    let bits = x.bitcast_i32();
+    let exponent = bits.shr_const::<23>().cast_f32() - S::Vf32::set1(127.0);
+    let mantissa = ((bits & 0x007F_FFFF) | 0x3F80_0000).bitcast_f32();
+    let high = mantissa.cmp_gt(S::Vf32::set1(core::f32::consts::SQRT_2));
+    (
+        high.blendv(mantissa, mantissa * S::Vf32::set1(0.5)),
+        high.blendv(exponent, exponent + S::Vf32::set1(1.0)),
+    )
+}
+
+/// Splits positive normal floats into a mantissa between `sqrt(1/2)` and `sqrt(2)` and an exponent.
+#[inline(always)]
+fn split_f64<S: Simd>(x: S::Vf64) -> (S::Vf64, S::Vf64) {
+    let bits = x.bitcast_i64();
+    let exponent = bits.shr_const::<52>().cast_f64() - S::Vf64::set1(1023.0);
+    let mantissa = ((bits & 0x000F_FFFF_FFFF_FFFF) | 0x3FF0_0000_0000_0000).bitcast_f64();
+    let high = mantissa.cmp_gt(S::Vf64::set1(core::f64::consts::SQRT_2));
+    (
+        high.blendv(mantissa, mantissa * S::Vf64::set1(0.5)),
+        high.blendv(exponent, exponent + S::Vf64::set1(1.0)),
+    )
+}
+
+/// Evaluates a polynomial in `x` with the coefficients `coefficient(0)` to `coefficient(terms - 1)`,
+/// without multiply-adds.
+#[inline(always)]
+fn polynomial<V: SimdFloat>(x: V, terms: usize, coefficient: impl Fn(usize) -> f64) -> V {
+    (0..terms - 1)
+        .rev()
+        .fold(V::from_f64(coefficient(terms - 1)), |acc, i| {
+            acc * x + V::from_f64(coefficient(i))
+        })
+}
+
+/// Returns `ln(mantissa * 2^exponent)`, using `terms` terms of the series
+/// `ln(m) = 2 * atanh((m - 1) / (m + 1))`, which converges quickly for mantissas close to 1.
+#[inline(always)]
+fn ln_split<V: SimdFloat>(mantissa: V, exponent: V, terms: usize) -> V {
+    let one = V::from_f64(1.0);
+    let f = (mantissa - one) / (mantissa + one);
+    let series = polynomial(f * f, terms, |k| 2.0 / (2 * k + 1) as f64);
+    f * series + exponent * V::from_f64(core::f64::consts::LN_2)
+}
+
+/// `1 / n!` for the Taylor series of the sine and cosine.
+const INV_FACTORIALS: [f64; 24] = {
+    let mut table = [1.0; 24];
+    let mut factorial = 1.0;
+    let mut n = 1;
+    while n < table.len() {
+        factorial *= n as f64;
+        table[n] = 1.0 / factorial;
+        n += 1;
+    }
+    table
+};
+
+/// Returns `sqrt(-2 * ln) * (cos(angle), sin(angle))` for an angle of `2 * pi * u` (shifted by a
+/// constant, which doesn't matter for a uniform angle). The sine and cosine use `terms` terms of
+/// their Taylor series, after reducing the angle to at most `pi / 4` around a multiple of `pi / 2`.
+#[inline(always)]
+fn box_muller<V: SimdFloat>(ln: V, u: V, terms: usize) -> (V, V) {
+    let radius = (ln * V::from_f64(-2.0)).sqrt();
+
+    let quarters = u * V::from_f64(4.0);
+    let quadrant = quarters.floor();
+    let a = (quarters - quadrant - V::from_f64(0.5)) * V::from_f64(core::f64::consts::FRAC_PI_2);
+    let a2 = a * a;
+    let sign = |k: usize| if k.is_multiple_of(2) { 1.0 } else { -1.0 };
+    let sin = a * polynomial(a2, terms, |k| sign(k) * INV_FACTORIALS[2 * k + 1]);
+    let cos = polynomial(a2, terms, |k| sign(k) * INV_FACTORIALS[2 * k]);
+
+    // Rotate (cos, sin) by the quadrant.
+    let odd = quadrant.cmp_eq(V::from_f64(1.0)) | quadrant.cmp_eq(V::from_f64(3.0));
+    let (cos, sin) = (odd.blendv(cos, -sin), odd.blendv(sin, cos));
+    let flip = quadrant.cmp_gt(V::from_f64(1.5));
+    let (cos, sin) = (flip.blendv(cos, -cos), flip.blendv(sin, -sin));
+
+    (radius * cos, radius * sin)
+}
+
+/// Loads one value per stream, computed from the stream number.
+#[inline(always)]
+fn load_streams<S: Simd>(mut state: impl FnMut(usize) -> u64) -> S::Vi64 {
+    debug_assert!(S::Vi64::WIDTH <= MAX_STREAMS);
+    let mut lanes = [0i64; MAX_STREAMS];
+    for (k, lane) in lanes[..S::Vi64::WIDTH].iter_mut().enumerate() {
+        *lane = state(k) as i64;
+    }
+    S::Vi64::load_from_slice(&lanes[..S::Vi64::WIDTH])
+}
+
+#[inline(always)]
+fn rotate_left<S: Simd>(x: S::Vi64, by: i32) -> S::Vi64 {
+    x.shl(by) | x.shr(64 - by)
+}
+
+/// The xoshiro256++ generator by Blackman and Vigna, with one stream per lane.
+///
+/// The streams start from a state seeded with SplitMix64, and every stream is 2^128 values ahead of
+/// the one before it, so they never overlap. Creating a generator takes time linear in the number
+/// of its first stream.
+pub struct Xoshiro256PlusPlus<S: Simd> {
+    state: [S::Vi64; 4],
+}
+
+/// One step of the scalar generator, which is only used to create the streams.
+fn xoshiro_step(s: &mut [u64; 4]) {
+    let t = s[1] << 17;
+    s[2] ^= s[0];
+    s[3] ^= s[1];
+    s[1] ^= s[2];
+    s[0] ^= s[3];
+    s[2] ^= t;
+    s[3] = s[3].rotate_left(45);
+}
+
+/// Advances the scalar generator by 2^128 values.
+fn xoshiro_jump(s: &mut [u64; 4]) {
+    const JUMP: [u64; 4] = [
+        0x180e_c6d3_3cfd_0aba,
+        0xd5a6_1266_f0c9_392c,
+        0xa958_2618_e03f_c9aa,
+        0x39ab_dc45_29b1_661c,
+    ];
+    let mut jumped = [0; 4];
+    for word in JUMP {
+        for bit in 0..64 {
+            if word & (1 << bit) != 0 {
+                jumped.iter_mut().zip(&*s).for_each(|(j, s)| *j ^= s);
+            }
+            xoshiro_step(s);
+        }
+    }
+    *s = jumped;
+}
+
+fn splitmix64(x: &mut u64) -> u64 {
+    *x = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = *x;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+impl<S: Simd> Xoshiro256PlusPlus<S> {
+    /// Creates a generator with the streams `0` to `S::Vi64::WIDTH - 1` of `seed`.
+    pub fn new(seed: u64) -> Self {
+        Self::from_stream(seed, 0)
+    }
+
+    /// Creates a generator with the streams `first_stream` to `first_stream + S::Vi64::WIDTH - 1`
+    /// of `seed`.
+    pub fn from_stream(seed: u64, first_stream: u64) -> Self {
+        let mut splitmix = seed;
+        let mut s = [0; 4].map(|_: u64| splitmix64(&mut splitmix));
+        for _ in 0..first_stream {
+            xoshiro_jump(&mut s);
+        }
+
+        let mut streams = [[0; 4]; MAX_STREAMS];
+        for stream in &mut streams[..S::Vi64::WIDTH] {
+            *stream = s;
+            xoshiro_jump(&mut s);
+        }
+        Self {
+            state: core::array::from_fn(|i| load_streams::<S>(|k| streams[k][i])),
+        }
+    }
+}
+
+impl<S: Simd> SimdRng<S> for Xoshiro256PlusPlus<S> {
+    /// Returns the high 32 bits of `next_u64`, which are the strongest.
+    #[inline(always)]
+    fn next_u32_lanes(&mut self) -> S::Vi64 {
+        self.next_u64().shr_const::<32>()
+    }
+
+    #[inline(always)]
+    fn next_u64(&mut self) -> S::Vi64 {
+        let [s0, s1, s2, s3] = self.state;
+        let result = rotate_left::<S>(s0 + s3, 23) + s0;
+
+        let t = s1.shl_const::<17>();
+        let s2 = s2 ^ s0;
+        let s3 = s3 ^ s1;
+        let s1 = s1 ^ s2;
+        let s0 = s0 ^ s3;
+        let s2 = s2 ^ t;
+        let s3 = rotate_left::<S>(s3, 45);
+        self.state = [s0, s1, s2, s3];
+
+        result
+    }
+}
+
+/// The PCG32 (XSH RR) generator by O'Neill, with one stream per lane.
+///
+/// Every stream has its own increment, derived from its number like `pcg32_srandom`, so the
+/// streams are distinct sequences rather than offsets into the same one.
+pub struct Pcg32<S: Simd> {
+    state: S::Vi64,
+    increment: S::Vi64,
+}
+
+const PCG_MULTIPLIER: u64 = 6_364_136_223_846_793_005;
+
+impl<S: Simd> Pcg32<S> {
+    /// Creates a generator with the streams `0` to `S::Vi64::WIDTH - 1` of `seed`.
+    pub fn new(seed: u64) -> Self {
+        Self::from_stream(seed, 0)
+    }
+
+    /// Creates a generator with the streams `first_stream` to `first_stream + S::Vi64::WIDTH - 1`
+    /// of `seed`. Stream numbers wrap around at 2^63.
+    pub fn from_stream(seed: u64, first_stream: u64) -> Self {
+        let increment = |k: usize| (first_stream.wrapping_add(k as u64) << 1) | 1;
+        let state = |k: usize| {
+            let step = |state: u64| {
+                state
+                    .wrapping_mul(PCG_MULTIPLIER)
+                    .wrapping_add(increment(k))
+            };
+            step(step(0).wrapping_add(seed))
+        };
+        Self {
+            state: load_streams::<S>(state),
+            increment: load_streams::<S>(increment),
+        }
+    }
+}
+
+impl<S: Simd> SimdRng<S> for Pcg32<S> {
+    #[inline(always)]
+    fn next_u32_lanes(&mut self) -> S::Vi64 {
+        let old = self.state;
+        self.state = old * S::Vi64::set1(PCG_MULTIPLIER as i64) + self.increment;
+
+        let low_bits = S::Vi64::set1(0xFFFF_FFFF);
+        let mut x = ((old.shr_const::<18>() ^ old).shr_const::<27>()) & low_bits;
+        let rotation = old.shr_const::<59>();
+
+        // Rotate every lane right by its own amount, one bit of the amount at a time.
+        for bit in [1, 2, 4, 8, 16] {
+            let rotated = (x.shr(bit) | x.shl(32 - bit)) & low_bits;
+            let mask = (rotation & bit as i64).cmp_eq(S::Vi64::set1(bit as i64));
+            x = mask.blendv(x, rotated);
+        }
+        x
+    }
+
+    /// Joins two 32 bit values, with the first one in the high half.
+    #[inline(always)]
+    fn next_u64(&mut self) -> S::Vi64 {
+        let high = self.next_u32_lanes();
+        high.shl_const::<32>() | self.next_u32_lanes()
+    }
+}
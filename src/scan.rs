@@ -0,0 +1,62 @@
+//! Inclusive and exclusive scans over slices, such as running sums or running maximums.
+//!
+//! Every vector of the input is scanned in register with `SimdBaseOps::prefix_scan`, then
+//! combined with the running total of all the vectors before it, whose last lane becomes the new
+//! running total. The final partial vector is loaded zero padded, which doesn't affect the lanes
+//! before the padding.
+//!
+//! The functions are generic over the vector type rather than the engine, so for example a
+//! running sum of `i32` is `inclusive_scan::<S::Vi32>(..)` from inside a function generated by
+//! `simd_runtime_generate!`.
+
+use crate::{ScanOp, SimdBaseOps};
+
+/// Writes the inclusive scan of `input` with `op` to `output`: every element of the output is `op`
+/// applied to the matching element of the input and all the elements before it.
+/// Panics if `output` is shorter than `input`.
+#[inline(always)]
+pub fn inclusive_scan<V: SimdBaseOps>(input: &[V::Scalar], output: &mut [V::Scalar], op: ScanOp) {
+    assert!(output.len() >= input.len());
+    let Some(&first) = input.first() else {
+        return;
+    };
+    let mut carry = V::set1(op.neutral::<V>(first));
+
+    for (src, dst) in input
+        .chunks(V::WIDTH)
+        .zip(output[..input.len()].chunks_mut(V::WIDTH))
+    {
+        let scan = op.combine(V::load_from_slice(src).prefix_scan(op), carry);
+        scan.copy_to_slice(dst);
+        carry = V::set1(scan[src.len() - 1]);
+    }
+}
+
+/// Writes the exclusive scan of `input` with `op`, starting from `init`, to `output`: the first
+/// element of the output is `init`, and every later one is `op` applied to `init` and all the
+/// elements of the input before it. The last element of the input only contributes to the
+/// returned total, which is `op` applied to `init` and the whole input.
+/// Panics if `output` is shorter than `input`.
+#[inline(always)]
+pub fn exclusive_scan<V: SimdBaseOps>(
+    input: &[V::Scalar],
+    output: &mut [V::Scalar],
+    init: V::Scalar,
+    op: ScanOp,
+) -> V::Scalar {
+    assert!(output.len() >= input.len());
+    let fill = op.neutral::<V>(init);
+    let mut carry = V::set1(init);
+
+    for (src, dst) in input
+        .chunks(V::WIDTH)
+        .zip(output[..input.len()].chunks_mut(V::WIDTH))
+    {
+        let scan = V::load_from_slice(src).prefix_scan(op);
+        op.combine(scan.shift_lanes_up::<1>(fill), carry)
+            .copy_to_slice(dst);
+        carry = V::set1(op.combine(scan, carry)[src.len() - 1]);
+    }
+
+    carry[0]
+}
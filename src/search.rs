@@ -0,0 +1,190 @@
+//! Slice-level byte search, similar to the `memchr` family of functions.
+//!
+//! Every function here is generic over the engine, see [slice algorithms](crate#slice-algorithms).
+//! The haystack is walked one vector at a time with unaligned loads, and the final partial vector
+//! is loaded through a zero padded copy whose extra lanes are masked out.
+
+use crate::{Simd, SimdBaseIo, SimdBaseOps, SimdConsts, SimdInt8};
+
+#[inline(always)]
+pub(crate) fn as_i8_slice(slice: &[u8]) -> &[i8] {
+    // Safe because u8 and i8 have the same size and alignment.
+    unsafe { core::slice::from_raw_parts(slice.as_ptr() as *const i8, slice.len()) }
+}
+
+/// A mask with the lowest `len` bits set, used to discard the padding lanes of a partial load.
+#[inline(always)]
+fn low_bits(len: usize) -> u32 {
+    if len >= 32 { u32::MAX } else { (1 << len) - 1 }
+}
+
+/// Returns the index of the first byte for which `matches` returns a truthy lane.
+#[inline(always)]
+pub(crate) fn find_first_by<S: Simd>(
+    haystack: &[u8],
+    matches: impl Fn(S::Vi8) -> S::Vi8,
+) -> Option<usize> {
+    let bytes = as_i8_slice(haystack);
+    let width = S::Vi8::WIDTH;
+
+    let mut offset = 0;
+    while offset + width <= bytes.len() {
+        let chunk = unsafe { S::Vi8::load_from_ptr_unaligned(bytes.as_ptr().add(offset)) };
+        let mask = matches(chunk).get_mask();
+        if mask != 0 {
+            return Some(offset + mask.trailing_zeros() as usize);
+        }
+        offset += width;
+    }
+
+    let rest = &bytes[offset..];
+    if !rest.is_empty() {
+        let mask = matches(S::Vi8::load_from_slice(rest)).get_mask() & low_bits(rest.len());
+        if mask != 0 {
+            return Some(offset + mask.trailing_zeros() as usize);
+        }
+    }
+
+    None
+}
+
+/// Returns the index of the first occurrence of `needle` in `haystack`.
+#[inline(always)]
+pub fn find_byte<S: Simd>(haystack: &[u8], needle: u8) -> Option<usize> {
+    let needle = S::Vi8::set1(needle as i8);
+    find_first_by::<S>(haystack, |chunk| chunk.cmp_eq(needle))
+}
+
+/// Returns the index of the first byte in `haystack` that is equal to either `a` or `b`.
+#[inline(always)]
+pub fn find_any_of2<S: Simd>(haystack: &[u8], a: u8, b: u8) -> Option<usize> {
+    let a = S::Vi8::set1(a as i8);
+    let b = S::Vi8::set1(b as i8);
+    find_first_by::<S>(haystack, |chunk| chunk.cmp_eq(a) | chunk.cmp_eq(b))
+}
+
+/// Returns the index of the first byte in `haystack` that is equal to `a`, `b` or `c`.
+#[inline(always)]
+pub fn find_any_of3<S: Simd>(haystack: &[u8], a: u8, b: u8, c: u8) -> Option<usize> {
+    let a = S::Vi8::set1(a as i8);
+    let b = S::Vi8::set1(b as i8);
+    let c = S::Vi8::set1(c as i8);
+    find_first_by::<S>(haystack, |chunk| {
+        chunk.cmp_eq(a) | chunk.cmp_eq(b) | chunk.cmp_eq(c)
+    })
+}
+
+/// Returns the index of the last occurrence of `needle` in `haystack`.
+#[inline(always)]
+pub fn rfind_byte<S: Simd>(haystack: &[u8], needle: u8) -> Option<usize> {
+    let bytes = as_i8_slice(haystack);
+    let width = S::Vi8::WIDTH;
+    let needle = S::Vi8::set1(needle as i8);
+
+    let mut end = bytes.len();
+    while end >= width {
+        let start = end - width;
+        let chunk = unsafe { S::Vi8::load_from_ptr_unaligned(bytes.as_ptr().add(start)) };
+        let mask = chunk.cmp_eq(needle).get_mask();
+        if mask != 0 {
+            return Some(start + 31 - mask.leading_zeros() as usize);
+        }
+        end = start;
+    }
+
+    if end > 0 {
+        let chunk = S::Vi8::load_from_slice(&bytes[..end]);
+        let mask = chunk.cmp_eq(needle).get_mask() & low_bits(end);
+        if mask != 0 {
+            return Some(31 - mask.leading_zeros() as usize);
+        }
+    }
+
+    None
+}
+
+/// Counts the occurrences of `needle` in `haystack`.
+#[inline(always)]
+pub fn count_byte<S: Simd>(haystack: &[u8], needle: u8) -> usize {
+    let bytes = as_i8_slice(haystack);
+    let width = S::Vi8::WIDTH;
+    let needle = S::Vi8::set1(needle as i8);
+
+    let mut count = 0;
+    let mut offset = 0;
+    while offset + width <= bytes.len() {
+        let chunk = unsafe { S::Vi8::load_from_ptr_unaligned(bytes.as_ptr().add(offset)) };
+        count += chunk.cmp_eq(needle).get_mask().count_ones() as usize;
+        offset += width;
+    }
+
+    let rest = &bytes[offset..];
+    if !rest.is_empty() {
+        let mask = S::Vi8::load_from_slice(rest).cmp_eq(needle).get_mask();
+        count += (mask & low_bits(rest.len())).count_ones() as usize;
+    }
+
+    count
+}
+
+/// Returns the index of the first occurrence of `needle` in `haystack`. An empty needle matches at 0.
+///
+/// Candidate positions are found by comparing a vector of haystack bytes against the first byte of
+/// the needle and a second vector, offset by the needle length, against its last byte. Only
+/// positions where both match are compared in full.
+#[inline(always)]
+pub fn find_substring<S: Simd>(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    let (first, last) = match (needle.first(), needle.last()) {
+        (Some(&first), Some(&last)) => (first, last),
+        _ => return Some(0),
+    };
+    if needle.len() > haystack.len() {
+        return None;
+    }
+    if needle.len() == 1 {
+        return find_byte::<S>(haystack, first);
+    }
+
+    let bytes = as_i8_slice(haystack);
+    let width = S::Vi8::WIDTH;
+    let last_offset = needle.len() - 1;
+    let candidates = haystack.len() - last_offset;
+    let first = S::Vi8::set1(first as i8);
+    let last = S::Vi8::set1(last as i8);
+
+    let verify = |mut mask: u32, offset: usize| {
+        while mask != 0 {
+            let position = offset + mask.trailing_zeros() as usize;
+            if haystack[position + 1..position + last_offset] == needle[1..last_offset] {
+                return Some(position);
+            }
+            mask &= mask - 1;
+        }
+        None
+    };
+
+    let mut offset = 0;
+    while offset + width <= candidates {
+        let (firsts, lasts) = unsafe {
+            (
+                S::Vi8::load_from_ptr_unaligned(bytes.as_ptr().add(offset)),
+                S::Vi8::load_from_ptr_unaligned(bytes.as_ptr().add(offset + last_offset)),
+            )
+        };
+        let mask = (firsts.cmp_eq(first) & lasts.cmp_eq(last)).get_mask();
+        if let Some(position) = verify(mask, offset) {
+            return Some(position);
+        }
+        offset += width;
+    }
+
+    if offset < candidates {
+        let remaining = candidates - offset;
+        let firsts = S::Vi8::load_from_slice(&bytes[offset..]);
+        let lasts = S::Vi8::load_from_slice(&bytes[offset + last_offset..]);
+        let mask = (firsts.cmp_eq(first) & lasts.cmp_eq(last)).get_mask() & low_bits(remaining);
+        return verify(mask, offset);
+    }
+
+    None
+}
@@ -0,0 +1,211 @@
+//! In-place unstable sorting of numeric slices.
+//!
+//! This is a quicksort with a median of three pivot, which hands ranges of up to four vectors to a
+//! sorting network: every vector is sorted with `sort_lanes`, then the sorted vectors are merged
+//! with `merge_sorted`. If the quicksort recurses too deeply, the range is finished with the core
+//! library sort instead, so the worst case stays O(n log n).
+//!
+//! Floats are sorted by their total order, as in `f32::total_cmp`: their bits are mapped to signed
+//! integers that compare in that order, sorted as integers, then mapped back.
+
+use crate::prelude::*;
+
+/// The number of vectors sorted by the sorting network at the leaves of the quicksort.
+const NETWORK_VECTORS: usize = 4;
+
+/// The largest engine width, so the leaves can be staged in a fixed size buffer.
+const MAX_WIDTH: usize = 32;
+
+/// Number types that can be sorted by this module.
+pub trait SimdSort: Sized + Copy {
+    /// Sorts `slice` in ascending order with the engine `S`.
+    fn sort_unstable<S: Simd>(slice: &mut [Self]);
+
+    /// Sorts `slice` in ascending order with the best engine available at runtime.
+    fn sort_unstable_runtime(slice: &mut [Self]);
+}
+
+/// Sorts `slice` in ascending order, without preserving the order of equal elements, using the best
+/// engine available at runtime. Floats are ordered like `f32::total_cmp`, so negative zero sorts
+/// before positive zero and NaNs go to either end depending on their sign.
+pub fn simd_sort_unstable<T: SimdSort>(slice: &mut [T]) {
+    T::sort_unstable_runtime(slice);
+}
+
+/// The same as `simd_sort_unstable`, except with a given engine, for the inside of a dispatched
+/// function (see [slice algorithms](crate#slice-algorithms)).
+#[inline(always)]
+pub fn sort_unstable<S: Simd, T: SimdSort>(slice: &mut [T]) {
+    T::sort_unstable::<S>(slice);
+}
+
+/// Sorts up to `NETWORK_VECTORS` vectors of keys. The keys are staged in a buffer padded with
+/// `max`, so the padding sorts to the end.
+#[inline(always)]
+fn sort_network<V: SimdBaseOps>(keys: &mut [V::Scalar], max: V::Scalar) {
+    let width = V::WIDTH;
+    debug_assert!(keys.len() <= NETWORK_VECTORS * width);
+
+    let mut buffer = [max; NETWORK_VECTORS * MAX_WIDTH];
+    buffer[..keys.len()].copy_from_slice(keys);
+    let load = |i: usize| unsafe { V::load_from_ptr_unaligned(buffer.as_ptr().add(i * width)) };
+
+    let sorted = if keys.len() <= width {
+        [load(0).sort_lanes(), load(1), load(2), load(3)]
+    } else if keys.len() <= width * 2 {
+        let (v0, v1) = load(0).sort_lanes().merge_sorted(load(1).sort_lanes());
+        [v0, v1, load(2), load(3)]
+    } else {
+        // Merge the vectors in pairs, then merge the two sorted pairs. The lowest vector overall
+        // is the lowest of the first vectors of each pair, the highest is the highest of their
+        // last vectors, and what's left in between only needs one more merge.
+        let (a0, a1) = load(0).sort_lanes().merge_sorted(load(1).sort_lanes());
+        let (b0, b1) = load(2).sort_lanes().merge_sorted(load(3).sort_lanes());
+        let (v0, low_rest) = a0.merge_sorted(b0);
+        let (high_rest, v3) = a1.merge_sorted(b1);
+        let (v1, v2) = low_rest.merge_sorted(high_rest);
+        [v0, v1, v2, v3]
+    };
+
+    for (i, vector) in sorted.iter().enumerate() {
+        unsafe { vector.copy_to_ptr_unaligned(buffer.as_mut_ptr().add(i * width)) };
+    }
+    keys.copy_from_slice(&buffer[..keys.len()]);
+}
+
+/// Moves the median of the first, middle and last keys to the front.
+#[inline(always)]
+fn move_median_to_front<T: Ord + Copy>(keys: &mut [T]) {
+    let (mid, last) = (keys.len() / 2, keys.len() - 1);
+    if keys[mid] < keys[0] {
+        keys.swap(mid, 0);
+    }
+    if keys[last] < keys[mid] {
+        keys.swap(last, mid);
+        if keys[mid] < keys[0] {
+            keys.swap(mid, 0);
+        }
+    }
+    keys.swap(0, mid);
+}
+
+/// Hoare partition around the first key. Returns `split` such that every key up to and including
+/// `split` is at most the pivot, every key after it is at least the pivot, and both sides are
+/// non-empty.
+#[inline(always)]
+fn partition<T: Ord + Copy>(keys: &mut [T]) -> usize {
+    let pivot = keys[0];
+    let mut i = 0;
+    let mut j = keys.len() - 1;
+    loop {
+        while keys[i] < pivot {
+            i += 1;
+        }
+        while keys[j] > pivot {
+            j -= 1;
+        }
+        if i >= j {
+            return j;
+        }
+        keys.swap(i, j);
+        i += 1;
+        j -= 1;
+    }
+}
+
+/// Sorts signed integer keys, where `max` is the largest value of their type.
+#[inline(always)]
+fn sort_keys<V: SimdBaseOps>(keys: &mut [V::Scalar], max: V::Scalar)
+where
+    V::Scalar: Ord,
+{
+    // A network over single lanes is just a slower insertion sort.
+    if V::WIDTH == 1 {
+        keys.sort_unstable();
+        return;
+    }
+
+    let mut keys = keys;
+    let mut depth_limit = 2 * (usize::BITS - keys.len().leading_zeros());
+    loop {
+        if keys.len() <= NETWORK_VECTORS * V::WIDTH {
+            sort_network::<V>(keys, max);
+            return;
+        }
+        if depth_limit == 0 {
+            keys.sort_unstable();
+            return;
+        }
+        depth_limit -= 1;
+
+        move_median_to_front(keys);
+        let split = partition(keys);
+        let (low, high) = keys.split_at_mut(split + 1);
+
+        // Recurse into the smaller side, so the stack depth stays logarithmic.
+        if low.len() < high.len() {
+            sort_keys::<V>(low, max);
+            keys = high;
+        } else {
+            sort_keys::<V>(high, max);
+            keys = low;
+        }
+    }
+}
+
+macro_rules! impl_sort_int {
+    ($ty:ident, $vector:ident) => {
+        impl SimdSort for $ty {
+            #[inline(always)]
+            fn sort_unstable<S: Simd>(slice: &mut [Self]) {
+                sort_keys::<S::$vector>(slice, $ty::MAX);
+            }
+
+            fn sort_unstable_runtime(slice: &mut [Self]) {
+                simd_runtime_generate!(
+                    #[allow(dead_code)]
+                    fn sort(slice: &mut [$ty]) {
+                        sort_keys::<S::$vector>(slice, $ty::MAX);
+                    }
+                );
+                sort(slice);
+            }
+        }
+    };
+}
+impl_sort_int!(i32, Vi32);
+impl_sort_int!(i64, Vi64);
+
+macro_rules! impl_sort_float {
+    ($ty:ident, $key:ident, $unsigned:ident, $vector:ident) => {
+        impl SimdSort for $ty {
+            #[inline(always)]
+            fn sort_unstable<S: Simd>(slice: &mut [Self]) {
+                // Flipping every bit but the sign of negative floats makes their bits, as signed
+                // integers, compare in the total order. Applying it again flips them back.
+                let flip =
+                    |key: &mut $key| *key ^= ((*key >> ($key::BITS - 1)) as $unsigned >> 1) as $key;
+
+                // Safe because the float and integer types have the same size and alignment.
+                let keys = unsafe {
+                    core::slice::from_raw_parts_mut(slice.as_mut_ptr() as *mut $key, slice.len())
+                };
+                keys.iter_mut().for_each(flip);
+                sort_keys::<S::$vector>(keys, $key::MAX);
+                keys.iter_mut().for_each(flip);
+            }
+
+            fn sort_unstable_runtime(slice: &mut [Self]) {
+                simd_runtime_generate!(
+                    #[allow(dead_code)]
+                    fn sort(slice: &mut [$ty]) {
+                        <$ty as SimdSort>::sort_unstable::<S>(slice);
+                    }
+                );
+                sort(slice);
+            }
+        }
+    };
+}
+impl_sort_float!(f32, i32, u32, Vi32);
+impl_sort_float!(f64, i64, u64, Vi64);
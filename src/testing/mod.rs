@@ -0,0 +1,20 @@
+//! Helpers for testing SIMD code against the scalar engine, enabled with the `testing` feature.
+//!
+//! `RandSimd` generates seeded inputs that start with the important edge values of each type, and
+//! the `simd_test!` macro checks a function generated by `simd_runtime_generate!` on every engine
+//! the CPU supports, detected at runtime.
+
+mod arbitrary;
+pub use arbitrary::{IterRandSimdForScalar, RandSimd};
+
+mod fn_tuple;
+pub use fn_tuple::{Func, Tuple};
+
+mod tester;
+pub use tester::{check_elementwise_function, check_function, elementwise_eq_tester};
+
+mod numbers;
+pub use numbers::{EqPrecision, ScalarNumber, SimdTupleIterable};
+
+mod simd_test;
+pub use simd_test::{EngineOutput, collect_inputs, engine_eq_tester};
@@ -0,0 +1,152 @@
+use core::fmt::Debug;
+
+use super::{EqPrecision, Func, ScalarNumber, Tuple, check_function};
+
+/// A value returned by a function under test, which can be compared with the value the scalar
+/// engine returned for the same inputs.
+pub trait EngineOutput {
+    /// Returns an error describing the first difference from `expected`.
+    fn check_eq(&self, expected: &Self, precision: EqPrecision) -> Result<(), String>;
+}
+
+impl<N: ScalarNumber> EngineOutput for N {
+    fn check_eq(&self, expected: &Self, precision: EqPrecision) -> Result<(), String> {
+        match expected.almost_eq(*self, precision) {
+            true => Ok(()),
+            false => Err(format!("Expected {expected}, got {self}")),
+        }
+    }
+}
+
+macro_rules! impl_exact_engine_output {
+    ($($ty:ty),*) => {
+        $(
+            impl EngineOutput for $ty {
+                fn check_eq(&self, expected: &Self, _precision: EqPrecision) -> Result<(), String> {
+                    match self == expected {
+                        true => Ok(()),
+                        false => Err(format!("Expected {expected:?}, got {self:?}")),
+                    }
+                }
+            }
+        )*
+    };
+}
+impl_exact_engine_output!((), bool, u8, u16, u32, u64, usize, isize);
+
+impl<T: EngineOutput + Debug> EngineOutput for Option<T> {
+    fn check_eq(&self, expected: &Self, precision: EqPrecision) -> Result<(), String> {
+        match (self, expected) {
+            (None, None) => Ok(()),
+            (Some(value), Some(expected)) => value.check_eq(expected, precision),
+            _ => Err(format!("Expected {expected:?}, got {self:?}")),
+        }
+    }
+}
+
+impl<T: EngineOutput> EngineOutput for [T] {
+    fn check_eq(&self, expected: &Self, precision: EqPrecision) -> Result<(), String> {
+        if self.len() != expected.len() {
+            return Err(format!(
+                "Expected {} elements, got {}",
+                expected.len(),
+                self.len()
+            ));
+        }
+        for (i, (value, expected)) in self.iter().zip(expected).enumerate() {
+            if let Err(str) = value.check_eq(expected, precision) {
+                return Err(format!("Failed for element {i}: {str}"));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T: EngineOutput, const N: usize> EngineOutput for [T; N] {
+    fn check_eq(&self, expected: &Self, precision: EqPrecision) -> Result<(), String> {
+        self[..].check_eq(&expected[..], precision)
+    }
+}
+
+impl<T: EngineOutput> EngineOutput for Vec<T> {
+    fn check_eq(&self, expected: &Self, precision: EqPrecision) -> Result<(), String> {
+        self[..].check_eq(&expected[..], precision)
+    }
+}
+
+macro_rules! impl_tuple_engine_output {
+    ($($ty:ident $index:tt),*) => {
+        impl<$($ty: EngineOutput),*> EngineOutput for ($($ty,)*) {
+            fn check_eq(&self, expected: &Self, precision: EqPrecision) -> Result<(), String> {
+                $(
+                    if let Err(str) = self.$index.check_eq(&expected.$index, precision) {
+                        return Err(format!("Failed for field {}: {str}", $index));
+                    }
+                )*
+                Ok(())
+            }
+        }
+    };
+}
+impl_tuple_engine_output!(A 0, B 1);
+impl_tuple_engine_output!(A 0, B 1, C 2);
+impl_tuple_engine_output!(A 0, B 1, C 2, D 3);
+
+/// Collects the inputs of `simd_test!`, so that every engine runs on the same ones.
+pub fn collect_inputs<Args>(inputs: impl IntoIterator<Item = Args>) -> Vec<Args> {
+    inputs.into_iter().collect()
+}
+
+/// Runs `simd_fn` on every input and panics if a result differs from the result of `scalar_fn`,
+/// naming the engine in the message.
+pub fn engine_eq_tester<Args: Tuple + Debug + Clone, R: EngineOutput>(
+    engine: &str,
+    inputs: &[Args],
+    precision: EqPrecision,
+    simd_fn: impl Func<Args, Output = R>,
+    scalar_fn: impl Func<Args, Output = R>,
+) {
+    check_function(inputs.iter().cloned(), simd_fn, |result, args| {
+        let expected = scalar_fn.call(args);
+        result
+            .check_eq(&expected, precision)
+            .map_err(|str| format!("{engine}: {str}"))
+    });
+}
+
+/// Checks a function generated by `simd_runtime_generate!` on every engine the CPU supports,
+/// asserting that it returns the same as its `_scalar` version for every input under the given
+/// `EqPrecision`. The engines are detected at runtime, so the test crate doesn't need to be built
+/// with their target features.
+///
+/// The inputs are the argument tuples, e.g. `[(&data[..], 2.0)]` for `fn scale(data: &[f32], factor:
+/// f32) -> Vec<f32>`, and the function has to return a value implementing `EngineOutput`.
+///
+/// ```ignore
+/// simd_test!(scale, [(&data[..], 2.0f32)], EqPrecision::almost(6));
+/// ```
+#[macro_export]
+macro_rules! simd_test {
+    ($fn_name:ident, $inputs:expr, $precision:expr $(,)?) => {
+        $crate::paste::item! {{
+            let inputs = $crate::testing::collect_inputs($inputs);
+            let precision: $crate::testing::EqPrecision = $precision;
+            $crate::testing::engine_eq_tester(
+                "Scalar",
+                &inputs,
+                precision,
+                [<$fn_name _generic>]::<$crate::engines::scalar::Scalar>,
+                [<$fn_name _scalar>],
+            );
+            if $crate::EngineKind::Avx2.is_available() {
+                $crate::testing::engine_eq_tester(
+                    "Avx2",
+                    &inputs,
+                    precision,
+                    [<$fn_name _generic>]::<$crate::engines::avx2::Avx2>,
+                    [<$fn_name _scalar>],
+                );
+            }
+        }}
+    };
+}
@@ -0,0 +1,67 @@
+use core::fmt::Debug;
+
+use crate::SimdBase;
+
+use super::{
+    fn_tuple::{Func, Tuple},
+    EqPrecision, ScalarNumber, SimdTupleIterable,
+};
+
+/// For each input, get the result, then, run the checker function on the result
+/// and panic if the checker errors
+pub fn check_function<Args: Tuple + Debug + Clone, R>(
+    inputs: impl Iterator<Item = Args>,
+    f: impl Func<Args, Output = R>,
+    check: impl Fn(R, Args) -> Result<(), String>,
+) {
+    for args in inputs {
+        let result = f.call(args.clone());
+        if let Err(str) = check(result, args.clone()) {
+            panic!("\nFailed for {:?}: {}", args, str);
+        }
+    }
+}
+
+/// Run `check_function` except with a scalar checker. This is to test element-wise functions.
+pub fn check_elementwise_function<
+    N: ScalarNumber,
+    Args: Tuple + Debug + Clone + SimdTupleIterable<N>,
+    R: SimdBase,
+>(
+    inputs: impl Iterator<Item = Args>,
+    f: impl Func<Args, Output = R>,
+    check: impl Fn(R::Scalar, Args::AsScalar) -> Result<(), String>,
+) {
+    check_function(inputs, f, |result, args| {
+        let scalar_iter = args.iter_scalars();
+        for (i, (scalar, expected)) in result.iter().zip(scalar_iter).enumerate() {
+            if let Err(str) = check(scalar, expected) {
+                return Err(format!("Failed for element {i}: {str}"));
+            }
+        }
+        Ok(())
+    });
+}
+
+pub fn elementwise_eq_tester<
+    N: ScalarNumber,
+    RN: ScalarNumber,
+    Args: Tuple + Debug + Clone + SimdTupleIterable<N>,
+    ScalarArg: SimdBase<Scalar = N>,
+    SimdRet: SimdBase<Scalar = RN>,
+    ScalarRet: SimdBase<Scalar = RN>,
+>(
+    inputs: impl Iterator<Item = Args>,
+    precision: EqPrecision,
+    simd_fn: impl Func<Args, Output = SimdRet>,
+    scalar_fn: impl Func<Args::AsTuple<ScalarArg>, Output = ScalarRet>,
+) {
+    check_elementwise_function(inputs, simd_fn, |result, args| {
+        let scalar_result = scalar_fn.call(Args::wrap_scalars(args))[0];
+        let equal = scalar_result.almost_eq(result, precision);
+        match equal {
+            true => Ok(()),
+            false => Err(format!("Expected {scalar_result}, got {result}")),
+        }
+    });
+}
@@ -0,0 +1,116 @@
+use rand::prelude::*;
+use rand_chacha::{ChaCha8Rng, rand_core::SeedableRng};
+
+use crate::engine_tester_impl;
+use crate::engines::scalar::Scalar;
+
+use crate::Simd;
+use crate::base64::*;
+
+const CONFIGS: [Base64Config; 4] = [
+    Base64Config::STANDARD,
+    Base64Config::STANDARD_NO_PAD,
+    Base64Config::URL_SAFE,
+    Base64Config::URL_SAFE_NO_PAD,
+];
+
+fn encode_to_vec<S: Simd>(input: &[u8], config: Base64Config) -> Vec<u8> {
+    let mut output = vec![0; encoded_len(input.len(), config)];
+    let written = encode::<S>(input, &mut output, config);
+    assert_eq!(written, output.len());
+    output
+}
+
+fn decode_to_vec<S: Simd>(input: &[u8], config: Base64Config) -> Result<Vec<u8>, Base64Error> {
+    let mut output = vec![0; decoded_len_estimate(input.len())];
+    let written = decode::<S>(input, &mut output, config)?;
+    output.truncate(written);
+    Ok(output)
+}
+
+fn base64_tester<S: Simd>() {
+    // RFC 4648 section 10
+    let vectors: [(&[u8], &[u8]); 7] = [
+        (b"", b""),
+        (b"f", b"Zg=="),
+        (b"fo", b"Zm8="),
+        (b"foo", b"Zm9v"),
+        (b"foob", b"Zm9vYg=="),
+        (b"fooba", b"Zm9vYmE="),
+        (b"foobar", b"Zm9vYmFy"),
+    ];
+    for (plain, encoded) in vectors {
+        assert_eq!(encode_to_vec::<S>(plain, Base64Config::STANDARD), encoded);
+        assert_eq!(
+            decode_to_vec::<S>(encoded, Base64Config::STANDARD),
+            Ok(plain.to_vec())
+        );
+    }
+
+    let all_bytes = (0..=255).collect::<Vec<u8>>();
+    let standard = encode_to_vec::<S>(&all_bytes, Base64Config::STANDARD);
+    let url_safe = encode_to_vec::<S>(&all_bytes, Base64Config::URL_SAFE);
+    assert!(standard.contains(&b'+') && standard.contains(&b'/'));
+    let translated = standard
+        .iter()
+        .map(|&c| match c {
+            b'+' => b'-',
+            b'/' => b'_',
+            c => c,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(url_safe, translated);
+
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    for len in 0..300 {
+        let input = (0..len)
+            .map(|_| rng.gen_range(0..=255))
+            .collect::<Vec<u8>>();
+        for config in CONFIGS {
+            let encoded = encode_to_vec::<S>(&input, config);
+            assert_eq!(encoded, encode_to_vec::<Scalar>(&input, config));
+            assert_eq!(decode_to_vec::<S>(&encoded, config), Ok(input.clone()));
+
+            // Corrupt one character, and check the error matches the reference.
+            if !encoded.is_empty() {
+                let mut corrupted = encoded.clone();
+                let index = rng.gen_range(0..corrupted.len());
+                corrupted[index] = *b"*=\0\xff-_+/".choose(&mut rng).unwrap();
+                assert_eq!(
+                    decode_to_vec::<S>(&corrupted, config),
+                    decode_to_vec::<Scalar>(&corrupted, config),
+                    "{config:?} {corrupted:?}"
+                );
+            }
+        }
+    }
+
+    let long = encode_to_vec::<S>(&[0x55; 90], Base64Config::STANDARD);
+    let mut corrupted = long.clone();
+    corrupted[70] = b'.';
+    assert_eq!(
+        decode_to_vec::<S>(&corrupted, Base64Config::STANDARD),
+        Err(Base64Error::InvalidByte(70))
+    );
+
+    let no_pad = Base64Config::STANDARD_NO_PAD;
+    assert_eq!(decode_to_vec::<S>(b"Zg", no_pad), Ok(b"f".to_vec()));
+    assert_eq!(
+        decode_to_vec::<S>(b"Zg==", no_pad),
+        Err(Base64Error::InvalidByte(2))
+    );
+    assert_eq!(
+        decode_to_vec::<S>(b"Zg", Base64Config::STANDARD),
+        Err(Base64Error::InvalidLength)
+    );
+    assert_eq!(
+        decode_to_vec::<S>(b"Zm9vY", no_pad),
+        Err(Base64Error::InvalidLength)
+    );
+    assert_eq!(
+        decode_to_vec::<S>(b"Zh==", Base64Config::STANDARD),
+        Err(Base64Error::InvalidByte(1))
+    );
+}
+
+engine_tester_impl!(base64_tester);
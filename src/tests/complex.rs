@@ -0,0 +1,103 @@
+use rand::prelude::*;
+use rand_chacha::{ChaCha8Rng, rand_core::SeedableRng};
+
+use crate::complex::*;
+use crate::engine_tester_impl;
+use crate::{Simd, SimdConsts};
+
+/// Runs the same checks for `ComplexF32` and `ComplexF64`, against plain float math on the pairs.
+macro_rules! complex_tester_for {
+    ($name:ident, $complex:ident, $ty:ident, $vector:ident, $tolerance:expr) => {
+        fn $name<S: Simd>() {
+            let width = S::$vector::WIDTH;
+            let mut rng = ChaCha8Rng::seed_from_u64(0);
+            let mut random =
+                |len: usize| -> Vec<$ty> { (0..len).map(|_| rng.gen_range(-10.0..10.0)).collect() };
+            let close = |result: $ty, expected: $ty| {
+                (result - expected).abs() <= $tolerance * expected.abs().max(1.0)
+            };
+
+            for _ in 0..1000 {
+                let (a_values, b_values, c_values) =
+                    (random(width * 2), random(width * 2), random(width * 2));
+                let a = $complex::<S>::load_interleaved(&a_values);
+                let b = $complex::<S>::load_interleaved(&b_values);
+                let c = $complex::<S>::load_interleaved(&c_values);
+
+                let mut stored = vec![0.0; width * 2];
+                a.store_interleaved(&mut stored);
+                assert_eq!(stored, a_values);
+
+                let results = [a + b, a - b, a * b, a.mul_add(b, c), a.conj(), -a];
+                for (op, result) in results.iter().enumerate() {
+                    result.store_interleaved(&mut stored);
+                    for lane in 0..width {
+                        let (ar, ai) = (a_values[lane * 2], a_values[lane * 2 + 1]);
+                        let (br, bi) = (b_values[lane * 2], b_values[lane * 2 + 1]);
+                        let (cr, ci) = (c_values[lane * 2], c_values[lane * 2 + 1]);
+                        let expected = match op {
+                            0 => (ar + br, ai + bi),
+                            1 => (ar - br, ai - bi),
+                            2 => (ar * br - ai * bi, ar * bi + ai * br),
+                            3 => (ar * br - ai * bi + cr, ar * bi + ai * br + ci),
+                            4 => (ar, -ai),
+                            _ => (-ar, -ai),
+                        };
+                        let result = (stored[lane * 2], stored[lane * 2 + 1]);
+                        assert!(
+                            close(result.0, expected.0) && close(result.1, expected.1),
+                            "op {op}: {result:?} != {expected:?}"
+                        );
+                    }
+                }
+
+                let (abs, arg) = (a.abs(), a.arg());
+                for lane in 0..width {
+                    let (re, im) = (a_values[lane * 2], a_values[lane * 2 + 1]);
+                    assert!(close(abs[lane], re.hypot(im)));
+                    assert!(
+                        close(arg[lane], im.atan2(re)),
+                        "{} != {}",
+                        arg[lane],
+                        im.atan2(re)
+                    );
+                }
+            }
+
+            // The axes, the diagonals and zero.
+            for (re, im) in [
+                (1.0, 0.0),
+                (0.0, 1.0),
+                (-1.0, 0.0),
+                (0.0, -1.0),
+                (1.0, 1.0),
+                (-1.0, 1.0),
+                (-1.0, -1.0),
+                (1.0, -1.0),
+                (0.0, 0.0),
+                (-0.0, 0.0),
+                (-0.0, -0.0),
+                (0.0, -0.0),
+                (-1.0, -0.0),
+            ] {
+                let (arg, expected) = ($complex::<S>::set1(re, im).arg(), $ty::atan2(im, re));
+                assert!(close(arg[0], expected), "{re} {im}");
+                assert_eq!(
+                    arg[0].is_sign_negative(),
+                    expected.is_sign_negative(),
+                    "{re} {im}"
+                );
+            }
+            assert_eq!($complex::<S>::zeroes().abs()[0], 0.0);
+        }
+    };
+}
+complex_tester_for!(complex_f32_tester, ComplexF32, f32, Vf32, 1e-5);
+complex_tester_for!(complex_f64_tester, ComplexF64, f64, Vf64, 1e-13);
+
+fn complex_tester<S: Simd>() {
+    complex_f32_tester::<S>();
+    complex_f64_tester::<S>();
+}
+
+engine_tester_impl!(complex_tester);
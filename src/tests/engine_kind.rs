@@ -0,0 +1,132 @@
+use crate::engines::{avx2::Avx2, scalar::Scalar};
+use crate::prelude::*;
+use crate::tests::lock_max_engine;
+use crate::{EngineKind, EngineRunner, available_engines, best_engine, max_engine, set_max_engine};
+
+#[test]
+fn engine_kind_names() {
+    for kind in EngineKind::ALL {
+        assert_eq!(EngineKind::from_name(kind.name()), Some(kind));
+    }
+    assert_eq!(EngineKind::from_name(" Scalar\n"), Some(EngineKind::Scalar));
+    assert_eq!(EngineKind::from_name("AVX2"), Some(EngineKind::Avx2));
+    assert_eq!(EngineKind::from_name("sse2"), None);
+    assert!(EngineKind::Scalar < EngineKind::Avx2);
+}
+
+fn engine_constants_tester<S: Simd>(kind: EngineKind) {
+    assert_eq!(S::NAME, kind.name());
+    assert_eq!(S::REGISTER_BITS, kind.register_bits());
+    assert_eq!(S::REQUIRED_FEATURES, kind.required_features());
+    assert_eq!(S::REGISTER_BITS % 64, 0);
+    assert!(S::Vf32::WIDTH * 32 <= S::REGISTER_BITS);
+    assert!(S::Vf64::WIDTH * 64 <= S::REGISTER_BITS);
+}
+
+#[test]
+fn engine_constants() {
+    engine_constants_tester::<Scalar>(EngineKind::Scalar);
+    engine_constants_tester::<Avx2>(EngineKind::Avx2);
+    assert_eq!(Avx2::REGISTER_BITS, 256);
+    assert_eq!(Avx2::REQUIRED_FEATURES, ["avx2", "fma"]);
+    assert!(Scalar::REQUIRED_FEATURES.is_empty());
+}
+
+#[test]
+fn engines_available() {
+    let available: Vec<EngineKind> = available_engines().collect();
+    assert_eq!(available.first(), Some(&EngineKind::Scalar));
+    assert_eq!(available.last(), Some(&best_engine()));
+    assert!(available.windows(2).all(|pair| pair[0] < pair[1]));
+    assert!(EngineKind::Scalar.is_available());
+    #[cfg(all(target_feature = "avx2", target_feature = "fma"))]
+    assert_eq!(best_engine(), EngineKind::Avx2);
+    assert!(max_engine() <= best_engine());
+}
+
+/// The detection doesn't depend on the target features of the build or on the `std` feature, which
+/// plain `cargo test` leaves off.
+#[cfg(target_arch = "x86_64")]
+#[test]
+fn best_engine_matches_cpu() {
+    let avx2 = std::is_x86_feature_detected!("avx2") && std::is_x86_feature_detected!("fma");
+    assert_eq!(EngineKind::Avx2.is_available(), avx2);
+    let best = if avx2 {
+        EngineKind::Avx2
+    } else {
+        EngineKind::Scalar
+    };
+    assert_eq!(best_engine(), best);
+}
+
+/// Runtime dispatch follows the limit set with `set_max_engine`, even after the runner of a
+/// function was resolved.
+#[test]
+fn set_max_engine_limits_dispatch() {
+    simd_runtime_generate!(
+        #[allow(dead_code)]
+        fn lane_count() -> usize {
+            S::Vf32::WIDTH
+        }
+    );
+
+    let _lock = lock_max_engine();
+    let previous = max_engine();
+    set_max_engine(EngineKind::Scalar);
+    assert_eq!(max_engine(), EngineKind::Scalar);
+    assert_eq!(lane_count(), 1);
+    assert_eq!(lane_count(), 1);
+    let resolved = lane_count_resolve();
+    assert_eq!(resolved(), 1);
+
+    if best_engine() == EngineKind::Avx2 {
+        set_max_engine(EngineKind::Avx2);
+        assert_eq!(lane_count(), 8);
+        assert_eq!(lane_count_resolve()(), 8);
+        assert_eq!(resolved(), 1);
+    }
+    set_max_engine(previous);
+}
+
+#[inline(always)]
+fn lane_sum<S: Simd>(data: &[f32]) -> (&'static str, f32) {
+    let sum = data
+        .chunks_exact(S::Vf32::WIDTH)
+        .map(|chunk| S::Vf32::load_from_slice(chunk).horizontal_add())
+        .sum();
+    (S::NAME, sum)
+}
+
+struct LaneSum<'a>(&'a [f32]);
+
+impl EngineRunner for LaneSum<'_> {
+    type Output = (&'static str, f32);
+
+    #[inline(always)]
+    fn run<S: Simd>(self) -> Self::Output {
+        lane_sum::<S>(self.0)
+    }
+}
+
+/// `dispatch!` and `EngineKind::run` run the engine they're given, as long as the CPU supports it.
+#[test]
+fn dispatch_runs_engine() {
+    let data = [1.5; 16];
+    for kind in available_engines() {
+        let width = dispatch!(kind, |S| <S as Simd>::Vf32::WIDTH);
+        let expected = match kind {
+            EngineKind::Scalar => 1,
+            EngineKind::Avx2 => 8,
+        };
+        assert_eq!(width, expected);
+        assert_eq!(
+            dispatch!(kind, |E| lane_sum::<E>(&data)),
+            (kind.name(), 24.0)
+        );
+        assert_eq!(kind.run(LaneSum(&data)), (kind.name(), 24.0));
+    }
+
+    let best = best_engine();
+    assert_eq!(dispatch!(EngineKind::Avx2, |S| S::NAME), best.name());
+    assert_eq!(dispatch!(EngineKind::Scalar, |S| S::NAME,), "scalar");
+}
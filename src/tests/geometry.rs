@@ -0,0 +1,183 @@
+use rand::prelude::*;
+use rand_chacha::{ChaCha8Rng, rand_core::SeedableRng};
+
+use crate::engine_tester_impl;
+use crate::geometry::*;
+use crate::{Simd, SimdBaseIo, SimdConsts};
+
+type Matrix = [[f32; 4]; 4];
+
+fn random_values(rng: &mut ChaCha8Rng, len: usize) -> Vec<f32> {
+    (0..len).map(|_| rng.gen_range(-1.0..1.0)).collect()
+}
+
+/// Random matrices with a dominant diagonal, so they are far from singular.
+fn random_matrices(rng: &mut ChaCha8Rng, count: usize) -> Vec<f32> {
+    let mut values = random_values(rng, count * 16);
+    for matrix in values.chunks_mut(16) {
+        for i in 0..4 {
+            matrix[i * 5] += 4.0;
+        }
+    }
+    values
+}
+
+fn random_quats(rng: &mut ChaCha8Rng, count: usize) -> Vec<f32> {
+    let mut values = random_values(rng, count * 4);
+    for q in values.chunks_mut(4) {
+        let norm = q.iter().map(|x| x * x).sum::<f32>().sqrt();
+        q.iter_mut().for_each(|x| *x /= norm);
+    }
+    values
+}
+
+fn to_matrix(values: &[f32]) -> Matrix {
+    core::array::from_fn(|r| core::array::from_fn(|c| values[r * 4 + c]))
+}
+
+fn reference_mul(a: &Matrix, b: &Matrix) -> Matrix {
+    core::array::from_fn(|r| core::array::from_fn(|c| (0..4).map(|k| a[r][k] * b[k][c]).sum()))
+}
+
+fn reference_quat_mul(a: &[f32], b: &[f32]) -> [f32; 4] {
+    let ([ax, ay, az, aw], [bx, by, bz, bw]) = ([a[0], a[1], a[2], a[3]], [b[0], b[1], b[2], b[3]]);
+    [
+        aw * bx + ax * bw + ay * bz - az * by,
+        aw * by - ax * bz + ay * bw + az * bx,
+        aw * bz + ax * by - ay * bx + az * bw,
+        aw * bw - ax * bx - ay * by - az * bz,
+    ]
+}
+
+fn reference_slerp(a: &[f32], b: &[f32], t: f32) -> [f32; 4] {
+    let mut dot: f32 = a.iter().zip(b).map(|(a, b)| a * b).sum();
+    let sign = if dot < 0.0 { -1.0 } else { 1.0 };
+    dot = (dot * sign).min(1.0);
+    let (weight_a, weight_b) = if dot > 0.9995 {
+        (1.0 - t, t)
+    } else {
+        let theta = dot.acos();
+        (
+            ((1.0 - t) * theta).sin() / theta.sin(),
+            (t * theta).sin() / theta.sin(),
+        )
+    };
+    let q: [f32; 4] = core::array::from_fn(|i| a[i] * weight_a + b[i] * weight_b * sign);
+    let norm = q.iter().map(|x| x * x).sum::<f32>().sqrt();
+    q.map(|x| x / norm)
+}
+
+fn assert_close(result: &[f32], expected: &[f32], tolerance: f32) {
+    assert_eq!(result.len(), expected.len());
+    for (i, (r, e)) in result.iter().zip(expected).enumerate() {
+        assert!(
+            (r - e).abs() <= tolerance,
+            "index {i}: {r} != {e}\n{result:?}\n{expected:?}"
+        );
+    }
+}
+
+fn geometry_tester<S: Simd>() {
+    let width = S::Vf32::WIDTH;
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+
+    for _ in 0..100 {
+        let a_values = random_matrices(&mut rng, width);
+        let b_values = random_matrices(&mut rng, width);
+        let a = Mat4::<S>::load_interleaved(&a_values);
+        let b = Mat4::<S>::load_interleaved(&b_values);
+
+        let mut stored = vec![0.0; width * 16];
+        a.store_interleaved(&mut stored);
+        assert_eq!(stored, a_values);
+
+        a.mul(&b).store_interleaved(&mut stored);
+        let expected: Vec<f32> = a_values
+            .chunks(16)
+            .zip(b_values.chunks(16))
+            .flat_map(|(a, b)| reference_mul(&to_matrix(a), &to_matrix(b)).concat())
+            .collect();
+        assert_close(&stored, &expected, 1e-4);
+
+        a.transpose().store_interleaved(&mut stored);
+        for (result, matrix) in stored.chunks(16).zip(a_values.chunks(16)) {
+            for (r, c) in (0..4).flat_map(|r| (0..4).map(move |c| (r, c))) {
+                assert_eq!(result[r * 4 + c], matrix[c * 4 + r]);
+            }
+        }
+
+        // The product of a matrix and its inverse is the identity.
+        a.mul(&a.inverse()).store_interleaved(&mut stored);
+        let identity = Mat4::<S>::identity();
+        let mut expected = vec![0.0; width * 16];
+        identity.store_interleaved(&mut expected);
+        assert_close(&stored, &expected, 1e-5);
+
+        // An affine transform is the matrix product with `w = 1`.
+        let points_values = random_values(&mut rng, width * 3);
+        let points = Vec3::<S>::load_interleaved(&points_values);
+        let mut transformed = vec![0.0; width * 3];
+        a.transform_points(&points)
+            .store_interleaved(&mut transformed);
+        let mut vectors = vec![0.0; width * 3];
+        a.transform_vectors(&points).store_interleaved(&mut vectors);
+        for (lane, matrix) in a_values.chunks(16).enumerate() {
+            let p = &points_values[lane * 3..][..3];
+            for r in 0..3 {
+                let row = &matrix[r * 4..][..4];
+                let direction = row[0] * p[0] + row[1] * p[1] + row[2] * p[2];
+                assert!((vectors[lane * 3 + r] - direction).abs() <= 1e-5);
+                assert!((transformed[lane * 3 + r] - (direction + row[3])).abs() <= 1e-5);
+            }
+        }
+
+        let qa_values = random_quats(&mut rng, width);
+        let qb_values = random_quats(&mut rng, width);
+        let qa = Quat::<S>::load_interleaved(&qa_values);
+        let qb = Quat::<S>::load_interleaved(&qb_values);
+        let mut quats = vec![0.0; width * 4];
+        qa.store_interleaved(&mut quats);
+        assert_eq!(quats, qa_values);
+
+        qa.mul(&qb).store_interleaved(&mut quats);
+        let expected: Vec<f32> = qa_values
+            .chunks(4)
+            .zip(qb_values.chunks(4))
+            .flat_map(|(a, b)| reference_quat_mul(a, b))
+            .collect();
+        assert_close(&quats, &expected, 1e-5);
+
+        // A rotation and its inverse cancel out.
+        qa.mul(&qa.conjugate()).store_interleaved(&mut quats);
+        let identity: Vec<f32> = (0..width).flat_map(|_| [0.0, 0.0, 0.0, 1.0]).collect();
+        assert_close(&quats, &identity, 1e-5);
+        Quat::<S>::identity().store_interleaved(&mut quats);
+        assert_eq!(quats, identity);
+
+        // Rotating with the quaternion matches transforming with its matrix.
+        let mut rotated = vec![0.0; width * 3];
+        qa.rotate(&points).store_interleaved(&mut rotated);
+        qa.to_mat4()
+            .transform_points(&points)
+            .store_interleaved(&mut transformed);
+        assert_close(&rotated, &transformed, 1e-5);
+
+        for t in [0.0, 0.25, 0.5, 0.9, 1.0] {
+            qa.slerp(&qb, S::Vf32::set1(t))
+                .store_interleaved(&mut quats);
+            let expected: Vec<f32> = qa_values
+                .chunks(4)
+                .zip(qb_values.chunks(4))
+                .flat_map(|(a, b)| reference_slerp(a, b, t))
+                .collect();
+            assert_close(&quats, &expected, 1e-5);
+        }
+
+        // Nearly equal quaternions take the linear path.
+        qa.slerp(&qa, S::Vf32::set1(0.3))
+            .store_interleaved(&mut quats);
+        assert_close(&quats, &qa_values, 1e-5);
+    }
+}
+
+engine_tester_impl!(geometry_tester);
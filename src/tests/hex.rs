@@ -0,0 +1,83 @@
+use rand::prelude::*;
+use rand_chacha::{ChaCha8Rng, rand_core::SeedableRng};
+
+use crate::engine_tester_impl;
+use crate::engines::scalar::Scalar;
+
+use crate::Simd;
+use crate::hex::*;
+
+fn encode_to_vec<S: Simd>(input: &[u8], case: HexCase) -> Vec<u8> {
+    let mut output = vec![0; input.len() * 2];
+    hex_encode::<S>(input, &mut output, case);
+    output
+}
+
+fn decode_to_vec<S: Simd>(input: &[u8]) -> Result<Vec<u8>, InvalidAt> {
+    let mut output = vec![0; input.len() / 2];
+    let written = hex_decode::<S>(input, &mut output)?;
+    assert_eq!(written, output.len());
+    Ok(output)
+}
+
+fn reference_encode(input: &[u8], case: HexCase) -> Vec<u8> {
+    let text = input
+        .iter()
+        .map(|byte| match case {
+            HexCase::Lower => format!("{byte:02x}"),
+            HexCase::Upper => format!("{byte:02X}"),
+        })
+        .collect::<String>();
+    text.into_bytes()
+}
+
+fn hex_tester<S: Simd>() {
+    assert_eq!(encode_to_vec::<S>(b"", HexCase::Lower), b"");
+    assert_eq!(
+        encode_to_vec::<S>(&[0x00, 0x9f, 0xa5, 0xff], HexCase::Lower),
+        b"009fa5ff"
+    );
+    assert_eq!(
+        encode_to_vec::<S>(&[0x00, 0x9f, 0xa5, 0xff], HexCase::Upper),
+        b"009FA5FF"
+    );
+    assert_eq!(
+        decode_to_vec::<S>(b"09aFA5ff"),
+        Ok(vec![0x09, 0xaf, 0xa5, 0xff])
+    );
+
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    for len in 0..200 {
+        let input = (0..len)
+            .map(|_| rng.gen_range(0..=255))
+            .collect::<Vec<u8>>();
+        for case in [HexCase::Lower, HexCase::Upper] {
+            let encoded = encode_to_vec::<S>(&input, case);
+            assert_eq!(encoded, reference_encode(&input, case));
+            assert_eq!(decode_to_vec::<S>(&encoded), Ok(input.clone()));
+
+            // Corrupt one character, and check the error matches the reference.
+            if !encoded.is_empty() {
+                let mut corrupted = encoded.clone();
+                let index = rng.gen_range(0..corrupted.len());
+                corrupted[index] = *b"/:@G`g\0\xff".choose(&mut rng).unwrap();
+                assert_eq!(decode_to_vec::<S>(&corrupted), Err(InvalidAt(index)));
+                assert_eq!(
+                    decode_to_vec::<S>(&corrupted),
+                    decode_to_vec::<Scalar>(&corrupted)
+                );
+            }
+        }
+    }
+
+    let mut long = encode_to_vec::<S>(&[0x5a; 64], HexCase::Upper);
+    long[100] = b'x';
+    long[110] = b'y';
+    assert_eq!(decode_to_vec::<S>(&long), Err(InvalidAt(100)));
+
+    let mut output = [0; 2];
+    assert_eq!(hex_decode::<S>(b"abc", &mut output), Err(InvalidAt(3)));
+    assert_eq!(hex_decode::<S>(b"xbc", &mut output), Err(InvalidAt(0)));
+}
+
+engine_tester_impl!(hex_tester);
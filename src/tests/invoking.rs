@@ -0,0 +1,214 @@
+// The argument tuples of the generated functions, like those of `fill_with`, must not warn.
+#![deny(unused_parens)]
+
+use crate::engine_tester_impl;
+#[cfg(all(target_feature = "avx2", target_feature = "fma"))]
+use crate::engines::avx2::Avx2;
+use crate::engines::scalar::Scalar;
+
+use crate::EngineKind;
+use crate::prelude::*;
+
+simd_runtime_generate!(
+    /// Scales `v` to a length of 1, with a const generic length.
+    fn normalize<const N: usize>(v: [f32; N]) -> [f32; N] {
+        let length = v.iter().map(|x| x * x).sum::<f32>().sqrt();
+        let scale = S::Vf32::set1(1.0 / length);
+        v.map(|x| (S::Vf32::set1(x) * scale)[0])
+    }
+);
+
+simd_runtime_generate!(
+    /// Sums rows of any length, with a bound closing two levels of generics at once.
+    fn sum_rows<I: IntoIterator<Item = Vec<f32>>>(rows: I) -> f32 {
+        let mut sum = 0.0;
+        for row in rows {
+            let mut chunks = row.chunks_exact(S::Vf32::WIDTH);
+            for chunk in &mut chunks {
+                sum += S::Vf32::load_from_slice(chunk).horizontal_add();
+            }
+            sum += chunks.remainder().iter().sum::<f32>();
+        }
+        sum
+    }
+);
+
+simd_runtime_generate!(
+    /// Counts the values matching a predicate, with the bounds in a where clause.
+    pub fn count_matching<'a, T, F>(data: &'a [T], predicate: F) -> usize
+    where
+        T: Copy + Into<f64> + 'a,
+        F: Fn(f64) -> bool,
+    {
+        data.iter().filter(|&&x| predicate(x.into())).count()
+    }
+);
+
+simd_runtime_generate!(
+    /// Returns the bytes of `T` in one vector of f32s, with a type parameter that isn't in the
+    /// arguments.
+    fn vector_bytes<T: Sized>() -> usize {
+        core::mem::size_of::<T>() * S::Vf32::WIDTH
+    }
+);
+
+simd_runtime_generate!(
+    /// Returns nothing, with a trailing comma after the generics.
+    fn fill_with<T: Copy>(data: &mut [T], value: T) {
+        data.fill(value);
+    }
+);
+
+fn generics_tester<S: Simd>() {
+    let v = normalize_generic::<3, S>([3.0, 0.0, 4.0]);
+    assert_eq!(v, [0.6, 0.0, 0.8]);
+
+    let rows = (0..20).map(|len| (0..len).map(|x| x as f32).collect());
+    let expected: f32 = (0..20).map(|len| (0..len).sum::<i32>() as f32).sum();
+    assert_eq!(sum_rows_generic::<_, S>(rows), expected);
+
+    let data = [1u8, 5, 9, 200];
+    assert_eq!(count_matching_generic::<_, _, S>(&data, |x| x > 4.0), 3);
+
+    assert_eq!(vector_bytes_generic::<u64, S>(), 8 * S::Vf32::WIDTH);
+
+    let mut data = [0i16; 5];
+    fill_with_generic::<_, S>(&mut data, 7);
+    assert_eq!(data, [7; 5]);
+}
+
+/// The runtime dispatched and scalar versions take the same generics.
+#[test]
+fn generics_runtime() {
+    assert_eq!(normalize([0.0, 2.0]), [0.0, 1.0]);
+    assert_eq!(normalize_scalar([2.0, 0.0]), [1.0, 0.0]);
+    assert_eq!(sum_rows(vec![vec![1.0, 2.0], vec![3.0]]), 6.0);
+    assert_eq!(sum_rows_scalar([vec![1.0; 9]]), 9.0);
+    assert_eq!(count_matching(&[1.5f32, -2.0], |x| x < 0.0), 1);
+    assert_eq!(count_matching_scalar(&[1i32, 2, 3], |x| x != 2.0), 2);
+    assert_eq!(vector_bytes_scalar::<u32>(), 4);
+    assert!(vector_bytes::<u8>() >= 1);
+
+    let resolved = sum_rows_resolve::<[Vec<f32>; 2]>();
+    assert_eq!(resolved([vec![1.0; 3], vec![2.0]]), 5.0);
+    let resolved = normalize_resolve::<1>();
+    assert_eq!(resolved([-2.0]), [-1.0]);
+    let resolved = count_matching_resolve::<u8, fn(f64) -> bool>();
+    assert_eq!(resolved(&[1, 2, 3], |x| x > 1.0), 2);
+    assert!(vector_bytes_resolve::<u16>()() >= 2);
+
+    let mut data = [0u8; 3];
+    fill_with(&mut data, 1);
+    fill_with_scalar(&mut data[1..], 2);
+    fill_with_resolve::<u8>()(&mut data[2..], 3);
+    assert_eq!(data, [1, 2, 3]);
+}
+
+engine_tester_impl!(generics_tester);
+
+simd_compiletime_select!(
+    #[allow(dead_code)]
+    fn compiletime_width() -> usize {
+        S::Vf32::WIDTH
+    }
+);
+
+simd_compiletime_select!(
+    #[allow(dead_code)]
+    fn compiletime_store(out: &mut usize) {
+        *out = S::Vf64::WIDTH;
+    }
+);
+
+/// The engine `simd_compiletime_select!` must pick in this build. The CI jobs building with
+/// `-C target-feature=+avx2,+fma` and `+avx2,-fma` set it, and the default is the scalar engine, as
+/// `.cargo/config.toml` disables AVX.
+fn expected_compiletime_engine() -> EngineKind {
+    match std::env::var("SIMDEEZ_EXPECT_COMPILETIME_ENGINE") {
+        Ok(name) => EngineKind::from_name(&name).expect("unknown engine name"),
+        Err(_) => EngineKind::Scalar,
+    }
+}
+
+/// The compile time selection uses AVX2 only when the build enables both AVX2 and FMA.
+#[test]
+fn compiletime_select_follows_target_features() {
+    let (f32_width, f64_width) = match expected_compiletime_engine() {
+        EngineKind::Avx2 => (8, 4),
+        EngineKind::Scalar => (1, 1),
+    };
+
+    assert_eq!(compiletime_width(), f32_width);
+    let mut out = 0;
+    compiletime_store(&mut out);
+    assert_eq!(out, f64_width);
+}
+
+/// AVX2 without FMA isn't enough for the AVX2 engine.
+#[cfg(all(
+    target_arch = "x86_64",
+    target_feature = "avx2",
+    not(target_feature = "fma")
+))]
+#[test]
+fn compiletime_select_requires_fma() {
+    assert_eq!(expected_compiletime_engine(), EngineKind::Scalar);
+    assert_eq!(compiletime_width(), 1);
+}
+
+simd_runtime_generate!(
+    /// Marks which body ran, with an override for the AVX2 engine.
+    fn override_marker(x: i32) -> i32 {
+        x + S::Vi32::WIDTH as i32
+    }
+    Avx2 {
+        -x * S::Vi32::WIDTH as i32
+    }
+);
+
+simd_unsafe_generate_all!(
+    /// Adds the first `N` lanes of `v`, with an AVX2 version written with intrinsics and an
+    /// override for the scalar engine too, which negates the sum to mark that it ran.
+    fn prefix_sum<const N: usize>(v: [f32; N]) -> f32
+    where
+        [f32; N]: Copy,
+    {
+        v.iter().sum()
+    }
+    Scalar {
+        -v.iter().rev().sum::<f32>() * S::Vf32::WIDTH as f32
+    }
+    Avx2 {
+        use core::arch::x86_64::*;
+        assert!(N <= 8);
+        let mut padded = [0.0; 8];
+        padded[..N].copy_from_slice(&v);
+        let v = _mm256_loadu_ps(padded.as_ptr());
+        let halves = _mm_add_ps(_mm256_castps256_ps128(v), _mm256_extractf128_ps(v, 1));
+        let pairs = _mm_add_ps(halves, _mm_movehl_ps(halves, halves));
+        _mm_cvtss_f32(_mm_add_ss(pairs, _mm_shuffle_ps(pairs, pairs, 1)))
+    }
+);
+
+/// Engines without an override run the generic body.
+#[test]
+fn overrides_fall_back_to_generic_body() {
+    assert_eq!(override_marker_generic::<Scalar>(3), 4);
+    assert_eq!(override_marker_scalar(3), 4);
+    assert!(matches!(override_marker(3), 4 | -24));
+    assert!(matches!(override_marker_resolve()(3), 4 | -24));
+}
+
+#[test]
+fn overrides_scalar() {
+    assert_eq!(prefix_sum_generic::<3, Scalar>([1.0, 2.0, 4.0]), -7.0);
+    assert_eq!(prefix_sum_scalar([0.5; 4]), -2.0);
+}
+
+#[cfg(all(target_feature = "avx2", target_feature = "fma"))]
+#[test]
+fn overrides_avx2() {
+    assert_eq!(override_marker_generic::<Avx2>(3), -24);
+    assert_eq!(prefix_sum_generic::<3, Avx2>([1.0, 2.0, 4.0]), 7.0);
+    assert_eq!(unsafe { prefix_sum_avx2([1.0; 8]) }, 8.0);
+}
@@ -0,0 +1,54 @@
+use core::num::NonZeroU32;
+
+use crate::engine_tester_impl;
+use crate::prelude::*;
+
+simd_const!(
+    /// A ramp of halves.
+    static RAMP: f32 = |lane| lane as f32 * 0.5;
+);
+
+simd_const!(const ODD_LANES: i32 = |lane| if lane % 2 == 1 { -1 } else { 0 });
+
+simd_const!(pub(crate) static REVERSED: i8 = |lane| 31 - lane as i8);
+
+// Neither a zeroed reference nor a zeroed `NonZeroU32` is valid.
+simd_const!(static NAMES: &str = |lane| if lane == 0 { "first" } else { "other" });
+simd_const!(const COUNTS: NonZeroU32 = |lane| NonZeroU32::MIN.saturating_add(lane as u32));
+
+fn simd_const_tester<S: Simd>() {
+    let ramp: S::Vf32 = RAMP.load();
+    let indices = S::Vi32::lane_indices().cast_f32();
+    let odd = ODD_LANES.load::<S::Vi32>();
+    for lane in 0..S::Vf32::WIDTH {
+        assert_eq!(ramp[lane], lane as f32 * 0.5);
+        assert_eq!(ramp[lane] * 2.0, indices[lane]);
+        assert_eq!(odd[lane], if lane % 2 == 1 { -1 } else { 0 });
+    }
+
+    let reversed: S::Vi8 = REVERSED.load();
+    assert_eq!(reversed[0], 31);
+    assert_eq!(
+        reversed.to_array()[S::Vi8::WIDTH - 1],
+        32 - S::Vi8::WIDTH as i8
+    );
+}
+
+/// The constants hold the lanes of the widest engine, aligned for loading.
+#[test]
+fn simd_const_layout() {
+    assert_eq!(RAMP.0.len(), 8);
+    assert_eq!(ODD_LANES.0.len(), 8);
+    assert_eq!(REVERSED.0.len(), 32);
+    assert_eq!(core::mem::align_of_val(&RAMP), 32);
+    assert_eq!(RAMP.0[7], 3.5);
+}
+
+/// The lanes can be of types without a valid zeroed value.
+#[test]
+fn simd_const_non_zeroable() {
+    assert_eq!(NAMES.0, ["first", "other"]);
+    assert_eq!(COUNTS.0.map(|count| count.get()), [1, 2, 3, 4, 5, 6, 7, 8]);
+}
+
+engine_tester_impl!(simd_const_tester);
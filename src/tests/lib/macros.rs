@@ -0,0 +1,422 @@
+#[macro_export]
+macro_rules! elementwise_eq_tester {
+    (< $simd_kind:ident :: $simd_ty:ident as $base_kind:ident >  :: $fn_name:ident, $inputs:expr, $precision:expr) => {{
+        let f = <<$simd_kind as Simd>::$simd_ty as $base_kind>::$fn_name;
+        let sf = <<Scalar as Simd>::$simd_ty as $base_kind>::$fn_name;
+        elementwise_eq_tester($inputs, $precision, f, sf);
+    }};
+}
+
+#[macro_export]
+macro_rules! bitshift_eq_tester {
+    ($simd_kind:ident :: $simd_ty:ident :: $fn_name:ident, $inputs:expr) => {{
+        let f = <<$simd_kind as Simd>::$simd_ty as SimdInt>::$fn_name;
+        let sf = <<Scalar as Simd>::$simd_ty as SimdInt>::$fn_name;
+        bitshift_eq_tester($inputs, f, sf);
+    }};
+}
+
+#[macro_export]
+macro_rules! const_bitshift_eq_tester {
+    ($scalar_ty:ident, $simd_kind:ident :: $simd_ty:ident :: $fn_name:ident, $inputs:expr) => {{
+        let f = |val, s| {
+            macro_rules! expand {
+                ($imm8:literal) => {
+                    <<$simd_kind as Simd>::$simd_ty as SimdInt>::$fn_name::<$imm8>(val)
+                };
+            }
+            test_constify_imm8_for_bitshift!($scalar_ty, s, expand)
+        };
+        let sf = |val, s| {
+            macro_rules! expand {
+                ($imm8:literal) => {
+                    <<Scalar as Simd>::$simd_ty as SimdInt>::$fn_name::<$imm8>(val)
+                };
+            }
+            test_constify_imm8_for_bitshift!($scalar_ty, s, expand)
+        };
+        bitshift_eq_tester($inputs, f, sf);
+    }};
+}
+
+#[macro_export]
+macro_rules! horizontal_add_tester {
+    (signed $simd_kind:ident :: $simd_ty:ident, $inputs:expr) => {{
+        let f = <<$simd_kind as Simd>::$simd_ty as SimdBaseOps>::horizontal_add;
+        horizontal_add_tester($inputs, f);
+    }};
+
+    (unsigned $simd_kind:ident :: $simd_ty:ident, $inputs:expr) => {{
+        let f = <<$simd_kind as Simd>::$simd_ty as SimdInt>::horizontal_unsigned_add;
+        unsigned_horizontal_add_tester($inputs, f);
+    }};
+}
+
+#[macro_export]
+macro_rules! with_feature_flag {
+    (Avx2, $($r:tt)+) => {
+        #[cfg(all(target_feature = "avx2", target_feature = "fma"))]
+        $($r)+
+    };
+    (Sse2, $($r:tt)+) => {
+        #[cfg(target_feature = "sse2")]
+        $($r)+
+    };
+    (Sse41, $($r:tt)+) => {
+        #[cfg(target_feature = "sse4.1")]
+        $($r)+
+    };
+    (Neon, $($r:tt)+) => {
+        #[cfg(target_feature = "neon")]
+        $($r)+
+    };
+    (Wasm, $($r:tt)+) => {
+        #[cfg(target_feature = "simd128")]
+        $($r)+
+    };
+    (Scalar, $($r:tt)+) => {
+        $($r)+
+    };
+}
+
+#[macro_export]
+macro_rules! elementwise_eq_tester_impl {
+    (@full $simd:ident, $simd_ty:ident, $simd_base:ident, $simd_fn:ident, $arg_cnt:ident, $precision:expr) => {
+        with_feature_flag!($simd,
+            paste::item! {
+                #[test]
+                fn [<$simd_fn _ $simd:lower _ $simd_ty>]() {
+                    elementwise_eq_tester!(
+                        <$simd:: [<V$simd_ty>] as $simd_base>::$simd_fn,
+                        RandSimd::$simd_ty().$arg_cnt(),
+                        $precision
+                    );
+                }
+            }
+        );
+    };
+
+    (@simdkind $simd_ty:ident, $simd_base:ident, $simd_fn:ident, $arg_cnt:ident, $precision:expr) => {
+        elementwise_eq_tester_impl!(@full Scalar, $simd_ty, $simd_base, $simd_fn, $arg_cnt, $precision);
+        elementwise_eq_tester_impl!(@full Avx2, $simd_ty, $simd_base, $simd_fn, $arg_cnt, $precision);
+    };
+
+    (SimdBaseOps, $simd_fn:ident, $arg_cnt:ident, $precision:expr) => {
+        elementwise_eq_tester_impl!(@simdkind i8, SimdBaseOps, $simd_fn, $arg_cnt, $precision);
+        elementwise_eq_tester_impl!(@simdkind i16, SimdBaseOps, $simd_fn, $arg_cnt, $precision);
+        elementwise_eq_tester_impl!(@simdkind i32, SimdBaseOps, $simd_fn, $arg_cnt, $precision);
+        elementwise_eq_tester_impl!(@simdkind i64, SimdBaseOps, $simd_fn, $arg_cnt, $precision);
+        elementwise_eq_tester_impl!(@simdkind f32, SimdBaseOps, $simd_fn, $arg_cnt, $precision);
+        elementwise_eq_tester_impl!(@simdkind f64, SimdBaseOps, $simd_fn, $arg_cnt, $precision);
+    };
+
+    (SimdInt, $simd_fn:ident, $arg_cnt:ident, $precision:expr) => {
+        elementwise_eq_tester_impl!(@simdkind i8, SimdInt, $simd_fn, $arg_cnt, $precision);
+        elementwise_eq_tester_impl!(@simdkind i16, SimdInt, $simd_fn, $arg_cnt, $precision);
+        elementwise_eq_tester_impl!(@simdkind i32, SimdInt, $simd_fn, $arg_cnt, $precision);
+        elementwise_eq_tester_impl!(@simdkind i64, SimdInt, $simd_fn, $arg_cnt, $precision);
+    };
+
+    (SimdFloat, $simd_fn:ident, $arg_cnt:ident, $precision:expr) => {
+        elementwise_eq_tester_impl!(@simdkind f32, SimdFloat, $simd_fn, $arg_cnt, $precision);
+        elementwise_eq_tester_impl!(@simdkind f64, SimdFloat, $simd_fn, $arg_cnt, $precision);
+    };
+
+    (SimdFloat32, $simd_fn:ident, $arg_cnt:ident, $precision:expr) => {
+        elementwise_eq_tester_impl!(@simdkind f32, SimdFloat32, $simd_fn, $arg_cnt, $precision);
+    };
+
+    (SimdInt32, $simd_fn:ident, $arg_cnt:ident, $precision:expr) => {
+        elementwise_eq_tester_impl!(@simdkind i32, SimdInt32, $simd_fn, $arg_cnt, $precision);
+    };
+
+    (SimdFloat64, $simd_fn:ident, $arg_cnt:ident, $precision:expr) => {
+        elementwise_eq_tester_impl!(@simdkind f64, SimdFloat64, $simd_fn, $arg_cnt, $precision);
+    };
+
+    (SimdInt64, $simd_fn:ident, $arg_cnt:ident, $precision:expr) => {
+        elementwise_eq_tester_impl!(@simdkind i64, SimdInt64, $simd_fn, $arg_cnt, $precision);
+    };
+}
+
+#[macro_export]
+macro_rules! bitshift_eq_tester_impl {
+    (@full dyn, $simd:ident, $simd_ty:ident, $simd_fn:ident) => {
+        with_feature_flag!($simd,
+            paste::item! {
+                #[test]
+                fn [<$simd_fn _ $simd:lower _ $simd_ty>]() {
+                    bitshift_eq_tester!(
+                        $simd:: [<V$simd_ty>]::$simd_fn,
+                        RandSimd::$simd_ty().one_arg_and_bitshift_arg()
+                    );
+                }
+            }
+        );
+    };
+
+    (@full const, $simd:ident, $simd_ty:ident, $simd_fn:ident) => {
+        with_feature_flag!($simd,
+            paste::item! {
+                #[test]
+                fn [<$simd_fn _ $simd:lower _ $simd_ty>]() {
+                    const_bitshift_eq_tester!(
+                        $simd_ty,
+                        $simd:: [<V$simd_ty>]::$simd_fn,
+                        RandSimd::$simd_ty().one_arg_and_bitshift_arg()
+                    );
+                }
+            }
+        );
+    };
+
+    (@simdkind $is_const:ident, $simd_ty:ident, $simd_fn:ident) => {
+        bitshift_eq_tester_impl!(@full $is_const, Scalar, $simd_ty, $simd_fn);
+        bitshift_eq_tester_impl!(@full $is_const, Avx2, $simd_ty, $simd_fn);
+    };
+
+    ($is_const:ident $simd_fn:ident) => {
+        bitshift_eq_tester_impl!(@simdkind $is_const, i8, $simd_fn);
+        bitshift_eq_tester_impl!(@simdkind $is_const, i16, $simd_fn);
+        bitshift_eq_tester_impl!(@simdkind $is_const, i32, $simd_fn);
+        bitshift_eq_tester_impl!(@simdkind $is_const, i64, $simd_fn);
+    };
+}
+
+#[macro_export]
+macro_rules! horizontal_add_tester_impl {
+    (@full $kind:ident, $simd:ident, $simd_ty:ident) => {
+        with_feature_flag!($simd,
+            paste::item! {
+                #[test]
+                fn [<$kind _horizontal_add_ $simd:lower _ $simd_ty>]() {
+                    horizontal_add_tester!(
+                        $kind
+                        $simd:: [<V$simd_ty>],
+                        RandSimd::$simd_ty().one_arg()
+                    );
+                }
+            }
+        );
+    };
+
+    (@simdkind $kind:ident, $simd_ty:ident) => {
+        horizontal_add_tester_impl!(@full $kind, Scalar, $simd_ty);
+        horizontal_add_tester_impl!(@full $kind, Avx2, $simd_ty);
+    };
+
+    (signed) => {
+        horizontal_add_tester_impl!(@simdkind signed, i8);
+        horizontal_add_tester_impl!(@simdkind signed, i16);
+        horizontal_add_tester_impl!(@simdkind signed, i32);
+        horizontal_add_tester_impl!(@simdkind signed, i64);
+        horizontal_add_tester_impl!(@simdkind signed, f32);
+        horizontal_add_tester_impl!(@simdkind signed, f64);
+    };
+
+    (unsigned) => {
+        horizontal_add_tester_impl!(@simdkind unsigned, i8);
+        horizontal_add_tester_impl!(@simdkind unsigned, i16);
+        horizontal_add_tester_impl!(@simdkind unsigned, i32);
+        horizontal_add_tester_impl!(@simdkind unsigned, i64);
+    };
+}
+
+#[macro_export]
+macro_rules! lane_ops_tester_impl {
+    (@full $simd:ident, $simd_ty:ident) => {
+        with_feature_flag!($simd,
+            paste::item! {
+                #[test]
+                fn [<lane_ops_ $simd:lower _ $simd_ty>]() {
+                    lane_ops_tester::<_, <$simd as Simd>::[<V$simd_ty>]>(RandSimd::$simd_ty().two_arg());
+                }
+            }
+        );
+    };
+
+    ($simd_ty:ident) => {
+        lane_ops_tester_impl!(@full Scalar, $simd_ty);
+        lane_ops_tester_impl!(@full Avx2, $simd_ty);
+    };
+}
+
+#[macro_export]
+macro_rules! compress_tester_impl {
+    (@full $simd:ident, $simd_ty:ident) => {
+        with_feature_flag!($simd,
+            paste::item! {
+                #[test]
+                fn [<compress_ $simd:lower _ $simd_ty>]() {
+                    compress_tester::<_, <$simd as Simd>::[<V$simd_ty>]>(RandSimd::$simd_ty().two_arg());
+                }
+            }
+        );
+    };
+
+    ($simd_ty:ident) => {
+        compress_tester_impl!(@full Scalar, $simd_ty);
+        compress_tester_impl!(@full Avx2, $simd_ty);
+    };
+}
+
+#[macro_export]
+macro_rules! prefix_scan_tester_impl {
+    (@full $simd:ident, $simd_ty:ident, $ops:expr) => {
+        with_feature_flag!($simd,
+            paste::item! {
+                #[test]
+                fn [<prefix_scan_ $simd:lower _ $simd_ty>]() {
+                    prefix_scan_tester::<_, <$simd as Simd>::[<V$simd_ty>]>(RandSimd::$simd_ty().one_arg(), &$ops);
+                }
+            }
+        );
+    };
+
+    ($simd_ty:ident, $ops:expr) => {
+        prefix_scan_tester_impl!(@full Scalar, $simd_ty, $ops);
+        prefix_scan_tester_impl!(@full Avx2, $simd_ty, $ops);
+    };
+}
+
+#[macro_export]
+macro_rules! horizontal_add_scalar_tester_impl {
+    (@full $simd:ident, $simd_ty:ident) => {
+        with_feature_flag!($simd,
+            paste::item! {
+                #[test]
+                fn [<horizontal_add_matches_scalar_ $simd:lower _ $simd_ty>]() {
+                    horizontal_add_scalar_tester::<
+                        _,
+                        <$simd as Simd>::[<V$simd_ty>],
+                        <Scalar as Simd>::[<V$simd_ty>],
+                    >();
+                }
+            }
+        );
+    };
+
+    ($simd_ty:ident) => {
+        horizontal_add_scalar_tester_impl!(@full Scalar, $simd_ty);
+        horizontal_add_scalar_tester_impl!(@full Avx2, $simd_ty);
+    };
+}
+
+#[macro_export]
+macro_rules! zip_tester_impl {
+    (@full $simd:ident, $simd_ty:ident) => {
+        with_feature_flag!($simd,
+            paste::item! {
+                #[test]
+                fn [<zip_ $simd:lower _ $simd_ty>]() {
+                    zip_tester::<_, <$simd as Simd>::[<V$simd_ty>]>(RandSimd::$simd_ty().two_arg());
+                }
+            }
+        );
+    };
+
+    ($simd_ty:ident) => {
+        zip_tester_impl!(@full Scalar, $simd_ty);
+        zip_tester_impl!(@full Avx2, $simd_ty);
+    };
+}
+
+#[macro_export]
+macro_rules! truncate_to_i32_tester_impl {
+    (@full $simd:ident) => {
+        with_feature_flag!($simd,
+            paste::item! {
+                #[test]
+                fn [<truncate_to_i32_ $simd:lower>]() {
+                    truncate_to_i32_tester::<<$simd as Simd>::Vi64>(RandSimd::i64().two_arg());
+                }
+            }
+        );
+    };
+
+    () => {
+        truncate_to_i32_tester_impl!(@full Scalar);
+        truncate_to_i32_tester_impl!(@full Avx2);
+    };
+}
+
+#[macro_export]
+macro_rules! memory_tester_impl {
+    (@full $simd:ident, $simd_ty:ident) => {
+        with_feature_flag!($simd,
+            paste::item! {
+                #[test]
+                fn [<memory_ $simd:lower _ $simd_ty>]() {
+                    memory_tester::<_, <$simd as Simd>::[<V$simd_ty>]>(RandSimd::$simd_ty().one_arg());
+                }
+            }
+        );
+    };
+
+    ($simd_ty:ident) => {
+        memory_tester_impl!(@full Scalar, $simd_ty);
+        memory_tester_impl!(@full Avx2, $simd_ty);
+    };
+}
+
+#[macro_export]
+macro_rules! array_constructors_tester_impl {
+    (@full $simd:ident, $simd_ty:ident) => {
+        with_feature_flag!($simd,
+            paste::item! {
+                #[test]
+                fn [<array_constructors_ $simd:lower _ $simd_ty>]() {
+                    array_constructors_tester::<_, <$simd as Simd>::[<V$simd_ty>]>(
+                        RandSimd::$simd_ty().one_arg(),
+                        |lane| lane as $simd_ty,
+                    );
+                }
+            }
+        );
+    };
+
+    ($simd_ty:ident) => {
+        array_constructors_tester_impl!(@full Scalar, $simd_ty);
+        array_constructors_tester_impl!(@full Avx2, $simd_ty);
+    };
+}
+
+#[macro_export]
+macro_rules! sort_lanes_tester_impl {
+    (@full $simd:ident, $simd_ty:ident) => {
+        with_feature_flag!($simd,
+            paste::item! {
+                #[test]
+                fn [<sort_lanes_ $simd:lower _ $simd_ty>]() {
+                    sort_lanes_tester::<_, <$simd as Simd>::[<V$simd_ty>]>(RandSimd::$simd_ty().two_arg());
+                }
+            }
+        );
+    };
+
+    ($simd_ty:ident) => {
+        sort_lanes_tester_impl!(@full Scalar, $simd_ty);
+        sort_lanes_tester_impl!(@full Avx2, $simd_ty);
+    };
+}
+
+#[macro_export]
+macro_rules! engine_tester_impl {
+    (@full $simd:ident, $tester:ident) => {
+        $crate::with_feature_flag!($simd,
+            $crate::paste::item! {
+                #[test]
+                fn [<$tester _ $simd:lower>]() {
+                    $tester::<$crate::engines::[<$simd:lower>]::$simd>();
+                }
+            }
+        );
+    };
+
+    ($($tester:ident),+ $(,)?) => {
+        $(
+            $crate::engine_tester_impl!(@full Scalar, $tester);
+            $crate::engine_tester_impl!(@full Avx2, $tester);
+        )+
+    };
+}
@@ -1,9 +1,8 @@
 //! Helpers for running tests
 
-mod arbitrary;
-pub use arbitrary::*;
+pub use crate::testing::*;
 
-mod fn_tuple;
+mod macros;
 
 mod tester;
 pub use tester::*;
@@ -12,3 +11,9 @@ mod numbers;
 pub use numbers::*;
 
 mod constify;
+
+/// Taken by the tests that call `set_max_engine`, as the limit is shared by every test thread.
+pub fn lock_max_engine() -> std::sync::MutexGuard<'static, ()> {
+    static LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+    LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
@@ -1,70 +1,11 @@
-use core::{fmt::Debug, ops::Add};
+use core::ops::Add;
 
-use crate::{SimdBase, SimdBaseIo};
-
-use super::{
-    fn_tuple::{Func, Tuple},
-    EqPrecision, IntScalarNumber, ScalarNumber, SimdTupleIterable,
+use crate::{
+    PrefetchNta, PrefetchT0, PrefetchT1, PrefetchT2, ScanOp, Simd, SimdBase, SimdBaseIo,
+    SimdConsts, SimdFloat, SimdInt64,
 };
 
-/// For each input, get the result, then, run the checker function on the result
-/// and panic if the checker errors
-pub fn check_function<Args: Tuple + Debug + Clone, R>(
-    inputs: impl Iterator<Item = Args>,
-    f: impl Func<Args, Output = R>,
-    check: impl Fn(R, Args) -> Result<(), String>,
-) {
-    for args in inputs {
-        let result = f.call(args.clone());
-        if let Err(str) = check(result, args.clone()) {
-            panic!("\nFailed for {:?}: {}", args, str);
-        }
-    }
-}
-
-/// Run `check_function` except with a scalar checker. This is to test element-wise functions.
-pub fn check_elementwise_function<
-    N: ScalarNumber,
-    Args: Tuple + Debug + Clone + SimdTupleIterable<N>,
-    R: SimdBase,
->(
-    inputs: impl Iterator<Item = Args>,
-    f: impl Func<Args, Output = R>,
-    check: impl Fn(R::Scalar, Args::AsScalar) -> Result<(), String>,
-) {
-    check_function(inputs, f, |result, args| {
-        let scalar_iter = args.iter_scalars();
-        for (i, (scalar, expected)) in result.iter().zip(scalar_iter).enumerate() {
-            if let Err(str) = check(scalar, expected) {
-                return Err(format!("Failed for element {i}: {str}"));
-            }
-        }
-        Ok(())
-    });
-}
-
-pub fn elementwise_eq_tester<
-    N: ScalarNumber,
-    RN: ScalarNumber,
-    Args: Tuple + Debug + Clone + SimdTupleIterable<N>,
-    ScalarArg: SimdBase<Scalar = N>,
-    SimdRet: SimdBase<Scalar = RN>,
-    ScalarRet: SimdBase<Scalar = RN>,
->(
-    inputs: impl Iterator<Item = Args>,
-    precision: EqPrecision,
-    simd_fn: impl Func<Args, Output = SimdRet>,
-    scalar_fn: impl Func<Args::AsTuple<ScalarArg>, Output = ScalarRet>,
-) {
-    check_elementwise_function(inputs, simd_fn, |result, args| {
-        let scalar_result = scalar_fn.call(Args::wrap_scalars(args))[0];
-        let equal = scalar_result.almost_eq(result, precision);
-        match equal {
-            true => Ok(()),
-            false => Err(format!("Expected {scalar_result}, got {result}")),
-        }
-    });
-}
+use super::{EqPrecision, Func, IntScalarNumber, ScalarNumber, check_function};
 
 pub fn bitshift_eq_tester<
     N: ScalarNumber + Eq,
@@ -113,6 +54,26 @@ pub fn horizontal_add_tester<
     });
 }
 
+/// Checks `horizontal_add` against the scalar engine adding up the same lanes. The lanes are small
+/// whole numbers so that every sum is exact, unlike the random inputs of `horizontal_add_tester`,
+/// which mostly sum to NaN or infinity and so can't tell a lane that is added twice.
+pub fn horizontal_add_scalar_tester<
+    N: ScalarNumber + Add<Output = N> + From<i16>,
+    S: SimdBase<Scalar = N, HorizontalAddScalar = N>,
+    Reference: SimdBase<Scalar = N, HorizontalAddScalar = N>,
+>() {
+    for k in 0..1000 {
+        let a = S::from_fn(|i| N::from(((k * 31 + i * 17) % 201) as i16 - 100));
+        let expected = (0..S::WIDTH)
+            .map(|i| Reference::set1(a[i]).horizontal_add())
+            .fold(Reference::zeroes().horizontal_add(), |sum, lane| sum + lane);
+        let result = a.horizontal_add();
+        if !expected.almost_eq(result, EqPrecision::exact()) {
+            panic!("\nFailed for {a:?}: Expected sum to be {expected}, got {result}");
+        }
+    }
+}
+
 pub fn unsigned_horizontal_add_tester<
     N: IntScalarNumber + Add<Output = N> + Default,
     SimdArg: SimdBase<Scalar = N>,
@@ -134,226 +95,401 @@ pub fn unsigned_horizontal_add_tester<
     });
 }
 
-#[macro_export]
-macro_rules! elementwise_eq_tester {
-    (< $simd_kind:ident :: $simd_ty:ident as $base_kind:ident >  :: $fn_name:ident, $inputs:expr, $precision:expr) => {{
-        let f = <<$simd_kind as Simd>::$simd_ty as $base_kind>::$fn_name;
-        let sf = <<Scalar as Simd>::$simd_ty as $base_kind>::$fn_name;
-        elementwise_eq_tester($inputs, $precision, f, sf);
-    }};
-}
-
-#[macro_export]
-macro_rules! bitshift_eq_tester {
-    ($simd_kind:ident :: $simd_ty:ident :: $fn_name:ident, $inputs:expr) => {{
-        let f = <<$simd_kind as Simd>::$simd_ty as SimdInt>::$fn_name;
-        let sf = <<Scalar as Simd>::$simd_ty as SimdInt>::$fn_name;
-        bitshift_eq_tester($inputs, f, sf);
-    }};
-}
+/// Checks the constant lane operations (extract, insert, lane shifts and align_right) against a
+/// reference built with the index operator.
+#[allow(unused_comparisons)]
+pub fn lane_ops_tester<N: ScalarNumber, S: SimdBase<Scalar = N>>(
+    inputs: impl Iterator<Item = (S, S)>,
+) {
+    let check_lanes = |name: &str, k: usize, result: S, expected: &dyn Fn(usize) -> N| {
+        for i in 0..S::WIDTH {
+            if !expected(i).almost_eq(result[i], EqPrecision::exact()) {
+                return Err(format!(
+                    "{name}::<{k}> failed for element {i}: Expected {}, got {}",
+                    expected(i),
+                    result[i]
+                ));
+            }
+        }
+        Ok(())
+    };
 
-#[macro_export]
-macro_rules! const_bitshift_eq_tester {
-    ($scalar_ty:ident, $simd_kind:ident :: $simd_ty:ident :: $fn_name:ident, $inputs:expr) => {{
-        let f = |val, s| {
-            macro_rules! expand {
-                ($imm8:literal) => {
-                    <<$simd_kind as Simd>::$simd_ty as SimdInt>::$fn_name::<$imm8>(val)
+    check_function(
+        inputs,
+        |a: S, b: S| (a, b),
+        |(a, b), _| {
+            let fill = b[0];
+
+            macro_rules! check_k {
+                ($k:literal) => {
+                    if $k <= S::WIDTH {
+                        let aligned = a.align_right::<$k>(b);
+                        let concat = |i: usize| if i < S::WIDTH { b[i] } else { a[i - S::WIDTH] };
+                        check_lanes("align_right", $k, aligned, &|i| concat(i + $k))?;
+
+                        let up = a.shift_lanes_up::<$k>(fill);
+                        check_lanes("shift_lanes_up", $k, up, &|i| {
+                            if i < $k { fill } else { a[i - $k] }
+                        })?;
+
+                        let down = a.shift_lanes_down::<$k>(fill);
+                        let expected_down =
+                            |i: usize| if i + $k < S::WIDTH { a[i + $k] } else { fill };
+                        check_lanes("shift_lanes_down", $k, down, &expected_down)?;
+                    }
                 };
             }
-            test_constify_imm8_for_bitshift!($scalar_ty, s, expand)
-        };
-        let sf = |val, s| {
-            macro_rules! expand {
-                ($imm8:literal) => {
-                    <<Scalar as Simd>::$simd_ty as SimdInt>::$fn_name::<$imm8>(val)
+
+            macro_rules! check_i {
+                ($i:literal) => {
+                    if $i < S::WIDTH {
+                        let extracted = a.extract::<$i>();
+                        if !extracted.almost_eq(a[$i], EqPrecision::exact()) {
+                            return Err(format!(
+                                "extract::<{}> failed: Expected {}, got {extracted}",
+                                $i, a[$i]
+                            ));
+                        }
+
+                        let inserted = a.insert::<$i>(fill);
+                        check_lanes("insert", $i, inserted, &|i| {
+                            if i == $i { fill } else { a[i] }
+                        })?;
+                    }
                 };
             }
-            test_constify_imm8_for_bitshift!($scalar_ty, s, expand)
-        };
-        bitshift_eq_tester($inputs, f, sf);
-    }};
-}
 
-#[macro_export]
-macro_rules! horizontal_add_tester {
-    (signed $simd_kind:ident :: $simd_ty:ident, $inputs:expr) => {{
-        let f = <<$simd_kind as Simd>::$simd_ty as SimdBaseOps>::horizontal_add;
-        horizontal_add_tester($inputs, f);
-    }};
-
-    (unsigned $simd_kind:ident :: $simd_ty:ident, $inputs:expr) => {{
-        let f = <<$simd_kind as Simd>::$simd_ty as SimdInt>::horizontal_unsigned_add;
-        unsigned_horizontal_add_tester($inputs, f);
-    }};
+            check_k!(0);
+            check_k!(1);
+            check_k!(3);
+            check_k!(4);
+            check_k!(8);
+            check_k!(16);
+            check_k!(32);
+
+            check_i!(0);
+            check_i!(1);
+            check_i!(3);
+
+            Ok(())
+        },
+    );
 }
 
-#[macro_export]
-macro_rules! with_feature_flag {
-    (Avx2, $($r:tt)+) => {
-        #[cfg(all(target_feature = "avx2", target_feature = "fma"))]
-        $($r)+
-    };
-    (Sse2, $($r:tt)+) => {
-        #[cfg(target_feature = "sse2")]
-        $($r)+
-    };
-    (Sse41, $($r:tt)+) => {
-        #[cfg(target_feature = "sse4.1")]
-        $($r)+
-    };
-    (Neon, $($r:tt)+) => {
-        #[cfg(target_feature = "neon")]
-        $($r)+
-    };
-    (Wasm, $($r:tt)+) => {
-        #[cfg(target_feature = "simd128")]
-        $($r)+
-    };
-    (Scalar, $($r:tt)+) => {
-        $($r)+
-    };
-}
+pub fn compress_tester<N: ScalarNumber, S: SimdBase<Scalar = N>>(
+    inputs: impl Iterator<Item = (S, S)>,
+) {
+    check_function(
+        inputs,
+        |a: S, b: S| (a, b),
+        |(a, b), _| {
+            let mask = a.cmp_gt(b);
+            let zero = S::zeroes()[0];
+            let selected = |i: usize| !mask[i].almost_eq(zero, EqPrecision::exact());
+
+            let mut expected_compressed = vec![zero; S::WIDTH];
+            let mut expected_expanded = vec![zero; S::WIDTH];
+            let mut expected_count = 0;
+            for i in 0..S::WIDTH {
+                if selected(i) {
+                    expected_compressed[expected_count] = a[i];
+                    expected_expanded[i] = a[expected_count];
+                    expected_count += 1;
+                }
+            }
 
-#[macro_export]
-macro_rules! elementwise_eq_tester_impl {
-    (@full $simd:ident, $simd_ty:ident, $simd_base:ident, $simd_fn:ident, $arg_cnt:ident, $precision:expr) => {
-        with_feature_flag!($simd,
-            paste::item! {
-                #[test]
-                fn [<$simd_fn _ $simd:lower _ $simd_ty>]() {
-                    elementwise_eq_tester!(
-                        <$simd:: [<V$simd_ty>] as $simd_base>::$simd_fn,
-                        RandSimd::$simd_ty().$arg_cnt(),
-                        $precision
-                    );
+            let (compressed, count) = a.compress(mask);
+            if count != expected_count {
+                return Err(format!(
+                    "compress count: Expected {expected_count}, got {count}"
+                ));
+            }
+            let expanded = a.expand(mask);
+            for i in 0..S::WIDTH {
+                if !expected_compressed[i].almost_eq(compressed[i], EqPrecision::exact()) {
+                    return Err(format!(
+                        "compress failed for element {i}: Expected {}, got {}",
+                        expected_compressed[i], compressed[i]
+                    ));
+                }
+                if !expected_expanded[i].almost_eq(expanded[i], EqPrecision::exact()) {
+                    return Err(format!(
+                        "expand failed for element {i}: Expected {}, got {}",
+                        expected_expanded[i], expanded[i]
+                    ));
                 }
             }
-        );
-    };
 
-    (@simdkind $simd_ty:ident, $simd_base:ident, $simd_fn:ident, $arg_cnt:ident, $precision:expr) => {
-        elementwise_eq_tester_impl!(@full Scalar, $simd_ty, $simd_base, $simd_fn, $arg_cnt, $precision);
-        elementwise_eq_tester_impl!(@full Avx2, $simd_ty, $simd_base, $simd_fn, $arg_cnt, $precision);
-    };
+            let mut full = vec![b[0]; S::WIDTH];
+            let mut exact = vec![b[0]; expected_count];
+            let full_count = a.compress_store(&mut full, mask);
+            let exact_count = a.compress_store(&mut exact, mask);
+            if full_count != expected_count || exact_count != expected_count {
+                return Err(format!(
+                    "compress_store count: Expected {expected_count}, got {full_count} and {exact_count}"
+                ));
+            }
+            for i in 0..expected_count {
+                let expected = expected_compressed[i];
+                if !expected.almost_eq(full[i], EqPrecision::exact())
+                    || !expected.almost_eq(exact[i], EqPrecision::exact())
+                {
+                    return Err(format!(
+                        "compress_store failed for element {i}: Expected {expected}, got {} and {}",
+                        full[i], exact[i]
+                    ));
+                }
+            }
 
-    (SimdBaseOps, $simd_fn:ident, $arg_cnt:ident, $precision:expr) => {
-        elementwise_eq_tester_impl!(@simdkind i8, SimdBaseOps, $simd_fn, $arg_cnt, $precision);
-        elementwise_eq_tester_impl!(@simdkind i16, SimdBaseOps, $simd_fn, $arg_cnt, $precision);
-        elementwise_eq_tester_impl!(@simdkind i32, SimdBaseOps, $simd_fn, $arg_cnt, $precision);
-        elementwise_eq_tester_impl!(@simdkind i64, SimdBaseOps, $simd_fn, $arg_cnt, $precision);
-        elementwise_eq_tester_impl!(@simdkind f32, SimdBaseOps, $simd_fn, $arg_cnt, $precision);
-        elementwise_eq_tester_impl!(@simdkind f64, SimdBaseOps, $simd_fn, $arg_cnt, $precision);
-    };
+            Ok(())
+        },
+    );
+}
 
-    (SimdInt, $simd_fn:ident, $arg_cnt:ident, $precision:expr) => {
-        elementwise_eq_tester_impl!(@simdkind i8, SimdInt, $simd_fn, $arg_cnt, $precision);
-        elementwise_eq_tester_impl!(@simdkind i16, SimdInt, $simd_fn, $arg_cnt, $precision);
-        elementwise_eq_tester_impl!(@simdkind i32, SimdInt, $simd_fn, $arg_cnt, $precision);
-        elementwise_eq_tester_impl!(@simdkind i64, SimdInt, $simd_fn, $arg_cnt, $precision);
-    };
+/// Checks `prefix_scan` with each of `ops`, against a running total built one lane at a time.
+/// Max and min are skipped for vectors containing NaN, since which lane wins depends on the order.
+pub fn prefix_scan_tester<N: ScalarNumber, S: SimdBase<Scalar = N>>(
+    inputs: impl Iterator<Item = (S,)>,
+    ops: &[ScanOp],
+) {
+    check_function(
+        inputs,
+        |a: S| (a,),
+        |(a,), _| {
+            let has_nan = (0..S::WIDTH).any(|i| a[i].is_float_nan());
+            for &op in ops {
+                if has_nan && matches!(op, ScanOp::Max | ScanOp::Min) {
+                    continue;
+                }
+                let result = a.prefix_scan(op);
+                let mut total = S::set1(a[0]);
+                for i in 0..S::WIDTH {
+                    if i > 0 {
+                        total = op.combine(total, S::set1(a[i]));
+                    }
+                    if !total[0].almost_eq(result[i], EqPrecision::exact()) {
+                        return Err(format!(
+                            "{op:?} failed for element {i}: Expected {}, got {}",
+                            total[0], result[i]
+                        ));
+                    }
+                }
+            }
 
-    (SimdFloat, $simd_fn:ident, $arg_cnt:ident, $precision:expr) => {
-        elementwise_eq_tester_impl!(@simdkind f32, SimdFloat, $simd_fn, $arg_cnt, $precision);
-        elementwise_eq_tester_impl!(@simdkind f64, SimdFloat, $simd_fn, $arg_cnt, $precision);
-    };
+            if ops.contains(&ScanOp::Add) {
+                let sum = a.prefix_sum();
+                let scan = a.prefix_scan(ScanOp::Add);
+                for i in 0..S::WIDTH {
+                    if !sum[i].almost_eq(scan[i], EqPrecision::exact()) {
+                        return Err(format!("prefix_sum differs from the Add scan at {i}"));
+                    }
+                }
+            }
 
-    (SimdFloat32, $simd_fn:ident, $arg_cnt:ident, $precision:expr) => {
-        elementwise_eq_tester_impl!(@simdkind f32, SimdFloat32, $simd_fn, $arg_cnt, $precision);
-    };
+            Ok(())
+        },
+    );
+}
 
-    (SimdInt32, $simd_fn:ident, $arg_cnt:ident, $precision:expr) => {
-        elementwise_eq_tester_impl!(@simdkind i32, SimdInt32, $simd_fn, $arg_cnt, $precision);
+/// Checks `sort_lanes` and `merge_sorted` against sorting the lanes one by one. Vectors with NaN
+/// lanes are skipped, since they have no order.
+pub fn sort_lanes_tester<N: ScalarNumber + PartialOrd, S: SimdBase<Scalar = N>>(
+    inputs: impl Iterator<Item = (S, S)>,
+) {
+    let sorted_lanes = |vectors: &[S]| {
+        let mut lanes = vectors.iter().flat_map(|v| v.iter()).collect::<Vec<N>>();
+        lanes.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        lanes
     };
-
-    (SimdFloat64, $simd_fn:ident, $arg_cnt:ident, $precision:expr) => {
-        elementwise_eq_tester_impl!(@simdkind f64, SimdFloat64, $simd_fn, $arg_cnt, $precision);
+    let check_lanes = |name: &str, result: &[S], expected: &[N]| {
+        let lanes = result.iter().flat_map(|v| v.iter());
+        for (i, (lane, expected)) in lanes.zip(expected).enumerate() {
+            if !expected.almost_eq(lane, EqPrecision::exact()) {
+                return Err(format!(
+                    "{name} failed for element {i}: Expected {expected}, got {lane}"
+                ));
+            }
+        }
+        Ok(())
     };
 
-    (SimdInt64, $simd_fn:ident, $arg_cnt:ident, $precision:expr) => {
-        elementwise_eq_tester_impl!(@simdkind i64, SimdInt64, $simd_fn, $arg_cnt, $precision);
-    };
+    check_function(
+        inputs,
+        |a: S, b: S| (a, b),
+        |(a, b), _| {
+            if a.iter().chain(b.iter()).any(|x| x.is_float_nan()) {
+                return Ok(());
+            }
+
+            let (a, b) = (a.sort_lanes(), b.sort_lanes());
+            check_lanes("sort_lanes", &[a], &sorted_lanes(&[a]))?;
+            check_lanes("sort_lanes", &[b], &sorted_lanes(&[b]))?;
+
+            let (low, high) = a.merge_sorted(b);
+            check_lanes("merge_sorted", &[low, high], &sorted_lanes(&[a, b]))
+        },
+    );
 }
 
-#[macro_export]
-macro_rules! bitshift_eq_tester_impl {
-    (@full dyn, $simd:ident, $simd_ty:ident, $simd_fn:ident) => {
-        with_feature_flag!($simd,
-            paste::item! {
-                #[test]
-                fn [<$simd_fn _ $simd:lower _ $simd_ty>]() {
-                    bitshift_eq_tester!(
-                        $simd:: [<V$simd_ty>]::$simd_fn,
-                        RandSimd::$simd_ty().one_arg_and_bitshift_arg()
-                    );
-                }
+/// Checks `zip`, `unzip`, `zip3` and `unzip3` against interleaving the lanes one by one, and that
+/// unzipping undoes zipping.
+pub fn zip_tester<N: ScalarNumber, S: SimdBase<Scalar = N> + SimdFloat>(
+    inputs: impl Iterator<Item = (S, S)>,
+) {
+    let check_lanes = |name: &str, result: &[S], expected: &dyn Fn(usize) -> N| {
+        let lanes = result.iter().flat_map(|v| v.iter());
+        for (i, lane) in lanes.enumerate() {
+            if !expected(i).almost_eq(lane, EqPrecision::exact()) {
+                return Err(format!(
+                    "{name} failed for element {i}: Expected {}, got {lane}",
+                    expected(i)
+                ));
             }
-        );
+        }
+        Ok(())
     };
 
-    (@full const, $simd:ident, $simd_ty:ident, $simd_fn:ident) => {
-        with_feature_flag!($simd,
-            paste::item! {
-                #[test]
-                fn [<$simd_fn _ $simd:lower _ $simd_ty>]() {
-                    const_bitshift_eq_tester!(
-                        $simd_ty,
-                        $simd:: [<V$simd_ty>]::$simd_fn,
-                        RandSimd::$simd_ty().one_arg_and_bitshift_arg()
-                    );
+    check_function(
+        inputs,
+        |a: S, b: S| (a, b),
+        |(a, b), _| {
+            let width = S::WIDTH;
+            let c = a + b;
+            let inputs = [a, b, c];
+            let lane = |i: usize| inputs[i / width][i % width];
+
+            let (low, high) = a.zip(b);
+            check_lanes("zip", &[low, high], &|i| lane(i % 2 * width + i / 2))?;
+            let (x, y, z) = a.zip3(b, c);
+            check_lanes("zip3", &[x, y, z], &|i| lane(i % 3 * width + i / 3))?;
+
+            let (even, odd) = a.unzip(b);
+            check_lanes("unzip", &[even, odd], &|i| lane(i % width * 2 + i / width))?;
+            let (x, y, z) = a.unzip3(b, c);
+            check_lanes("unzip3", &[x, y, z], &|i| lane(i % width * 3 + i / width))?;
+
+            let (even, odd) = low.unzip(high);
+            check_lanes("unzip of zip", &[even, odd], &lane)?;
+            let (x, y, z) = x.zip3(y, z);
+            check_lanes("zip3 of unzip3", &[x, y, z], &lane)
+        },
+    );
+}
+
+/// Checks `truncate_to_i32` against casting the lanes of both vectors one by one.
+pub fn truncate_to_i32_tester<S: SimdBase<Scalar = i64> + SimdInt64>(
+    inputs: impl Iterator<Item = (S, S)>,
+) {
+    check_function(
+        inputs,
+        |a: S, b: S| (a, b),
+        |(a, b), _| {
+            let result = a.truncate_to_i32(b);
+            let lanes = a.iter().chain(b.iter()).map(|lane| lane as i32);
+            for (i, expected) in lanes.take(<S::Engine as Simd>::Vi32::WIDTH).enumerate() {
+                if result[i] != expected {
+                    return Err(format!(
+                        "truncate_to_i32 failed for element {i}: Expected {expected}, got {}",
+                        result[i]
+                    ));
                 }
             }
-        );
-    };
-
-    (@simdkind $is_const:ident, $simd_ty:ident, $simd_fn:ident) => {
-        bitshift_eq_tester_impl!(@full $is_const, Scalar, $simd_ty, $simd_fn);
-        bitshift_eq_tester_impl!(@full $is_const, Avx2, $simd_ty, $simd_fn);
-    };
-
-    ($is_const:ident $simd_fn:ident) => {
-        bitshift_eq_tester_impl!(@simdkind $is_const, i8, $simd_fn);
-        bitshift_eq_tester_impl!(@simdkind $is_const, i16, $simd_fn);
-        bitshift_eq_tester_impl!(@simdkind $is_const, i32, $simd_fn);
-        bitshift_eq_tester_impl!(@simdkind $is_const, i64, $simd_fn);
-    };
+            Ok(())
+        },
+    );
 }
 
-#[macro_export]
-macro_rules! horizontal_add_tester_impl {
-    (@full $kind:ident, $simd:ident, $simd_ty:ident) => {
-        with_feature_flag!($simd,
-            paste::item! {
-                #[test]
-                fn [<$kind _horizontal_add_ $simd:lower _ $simd_ty>]() {
-                    horizontal_add_tester!(
-                        $kind
-                        $simd:: [<V$simd_ty>],
-                        RandSimd::$simd_ty().one_arg()
-                    );
+/// Aligned storage for one vector of any engine.
+#[repr(C, align(32))]
+struct AlignedLanes<N>([N; 32]);
+
+/// Checks that `copy_to_ptr_streaming` followed by `store_fence` stores every lane, also across a
+/// run of vectors, and that `prefetch` with every hint never faults, even for invalid pointers.
+pub fn memory_tester<N: ScalarNumber, S: SimdBase<Scalar = N>>(inputs: impl Iterator<Item = (S,)>) {
+    let prefetch_all = |ptr: *const N| {
+        S::prefetch::<PrefetchT0>(ptr);
+        S::prefetch::<PrefetchT1>(ptr);
+        S::prefetch::<PrefetchT2>(ptr);
+        S::prefetch::<PrefetchNta>(ptr);
+    };
+    prefetch_all(core::ptr::null());
+    prefetch_all(usize::MAX as *const N);
+
+    check_function(
+        inputs,
+        |a: S| (a,),
+        |(a,), _| {
+            let mut lanes = AlignedLanes([a[0]; 32]);
+            let ptr = lanes.0.as_mut_ptr();
+            prefetch_all(ptr);
+            // Every vector starts a multiple of its own size into the storage, so stays aligned.
+            let count = 32 / S::WIDTH;
+            let doubled = a + a;
+            unsafe {
+                for i in 0..count {
+                    doubled.copy_to_ptr_streaming(ptr.add(i * S::WIDTH));
                 }
+                S::store_fence();
+                a.copy_to_ptr_streaming(ptr);
+                S::store_fence();
             }
-        );
-    };
 
-    (@simdkind $kind:ident, $simd_ty:ident) => {
-        horizontal_add_tester_impl!(@full $kind, Scalar, $simd_ty);
-        horizontal_add_tester_impl!(@full $kind, Avx2, $simd_ty);
-    };
+            let reloaded = unsafe { S::load_from_ptr_aligned(ptr) };
+            for i in 0..S::WIDTH {
+                if !a[i].almost_eq(lanes.0[i], EqPrecision::exact())
+                    || !a[i].almost_eq(reloaded[i], EqPrecision::exact())
+                {
+                    return Err(format!(
+                        "copy_to_ptr_streaming failed for element {i}: Expected {}, got {}",
+                        a[i], lanes.0[i]
+                    ));
+                }
+            }
+            for i in S::WIDTH..count * S::WIDTH {
+                let expected = doubled[i % S::WIDTH];
+                if !expected.almost_eq(lanes.0[i], EqPrecision::exact()) {
+                    return Err(format!(
+                        "copy_to_ptr_streaming failed for element {i}: Expected {expected}, got {}",
+                        lanes.0[i]
+                    ));
+                }
+            }
+            Ok(())
+        },
+    );
+}
 
-    (signed) => {
-        horizontal_add_tester_impl!(@simdkind signed, i8);
-        horizontal_add_tester_impl!(@simdkind signed, i16);
-        horizontal_add_tester_impl!(@simdkind signed, i32);
-        horizontal_add_tester_impl!(@simdkind signed, i64);
-        horizontal_add_tester_impl!(@simdkind signed, f32);
-        horizontal_add_tester_impl!(@simdkind signed, f64);
+/// Checks `from_array`, `to_array` and `from_fn` against the lanes of each input, and
+/// `lane_indices` against `index`, which converts a lane index to `N`.
+pub fn array_constructors_tester<N: ScalarNumber, S: SimdBase<Scalar = N>>(
+    inputs: impl Iterator<Item = (S,)>,
+    index: impl Fn(usize) -> N,
+) {
+    let check_lanes = |name: &str, result: S, expected: &dyn Fn(usize) -> N| {
+        for i in 0..S::WIDTH {
+            if !expected(i).almost_eq(result[i], EqPrecision::exact()) {
+                return Err(format!(
+                    "{name} failed for element {i}: Expected {}, got {}",
+                    expected(i),
+                    result[i]
+                ));
+            }
+        }
+        Ok(())
     };
 
-    (unsigned) => {
-        horizontal_add_tester_impl!(@simdkind unsigned, i8);
-        horizontal_add_tester_impl!(@simdkind unsigned, i16);
-        horizontal_add_tester_impl!(@simdkind unsigned, i32);
-        horizontal_add_tester_impl!(@simdkind unsigned, i64);
-    };
+    check_lanes("lane_indices", S::lane_indices(), &index).unwrap_or_else(|err| panic!("{err}"));
+
+    check_function(
+        inputs,
+        |a: S| (a,),
+        |(a,), _| {
+            let array = a.to_array();
+            check_lanes("to_array", a, &|i| array[i])?;
+            check_lanes("from_array", S::from_array(array), &|i| a[i])?;
+            check_lanes("from_fn", S::from_fn(|i| a[i]), &|i| a[i])?;
+            let reversed = S::from_fn(|i| a[S::WIDTH - 1 - i]);
+            check_lanes("from_fn", reversed, &|i| a[S::WIDTH - 1 - i])
+        },
+    );
 }
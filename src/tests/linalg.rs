@@ -0,0 +1,102 @@
+use rand::prelude::*;
+use rand_chacha::{ChaCha8Rng, rand_core::SeedableRng};
+
+use crate::Simd;
+use crate::engine_tester_impl;
+use crate::linalg::*;
+
+// Small integers keep every product and sum exact, so the results can be compared exactly no
+// matter how the sums are split up.
+fn random_vec<T: LinalgFloat + From<i8>>(rng: &mut ChaCha8Rng, len: usize) -> Vec<T> {
+    (0..len).map(|_| T::from(rng.gen_range(-8..=8))).collect()
+}
+
+fn reference_gemm<T: LinalgFloat>(
+    (m, n, k): (usize, usize, usize),
+    alpha: T,
+    a: &[T],
+    b: &[T],
+    beta: T,
+    c: &mut [T],
+) {
+    for i in 0..m {
+        for j in 0..n {
+            let mut sum = T::ZERO;
+            for p in 0..k {
+                sum = sum + a[i * k + p] * b[p * n + j];
+            }
+            c[i * n + j] = alpha * sum + beta * c[i * n + j];
+        }
+    }
+}
+
+fn linalg_tester_for<S: Simd, T: LinalgFloat + From<i8>>(nan: T) {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let two = T::from(2);
+    let minus_one = T::from(-1);
+
+    for len in 0..100 {
+        let x = random_vec::<T>(&mut rng, len);
+        let y = random_vec::<T>(&mut rng, len);
+        let expected = x.iter().zip(&y).fold(T::ZERO, |acc, (&x, &y)| acc + x * y);
+        assert_eq!(dot::<S, T>(&x, &y), expected);
+
+        let mut result = y.clone();
+        axpy::<S, T>(two, &x, &mut result);
+        let expected = x.iter().zip(&y).map(|(&x, &y)| two * x + y);
+        assert!(result.iter().copied().eq(expected));
+
+        let mut result = x.clone();
+        scale::<S, T>(minus_one, &mut result);
+        assert!(result.iter().copied().eq(x.iter().map(|&x| minus_one * x)));
+    }
+
+    for (rows, cols) in [(0, 5), (5, 0), (1, 1), (3, 17), (4, 8), (9, 33), (13, 100)] {
+        let a = random_vec::<T>(&mut rng, rows * cols);
+        let x = random_vec::<T>(&mut rng, cols);
+        let y = random_vec::<T>(&mut rng, rows);
+        let mut expected = y.clone();
+        reference_gemm((rows, 1, cols), two, &a, &x, minus_one, &mut expected);
+        let mut result = y.clone();
+        gemv::<S, T>(two, &a, &x, minus_one, &mut result);
+        assert_eq!(result, expected, "{rows}x{cols}");
+    }
+
+    // Sizes that cross the block and microkernel boundaries.
+    for (m, n, k) in [
+        (0, 3, 3),
+        (3, 0, 3),
+        (3, 3, 0),
+        (1, 1, 1),
+        (5, 7, 3),
+        (4, 16, 9),
+        (67, 261, 259),
+    ] {
+        let a = random_vec::<T>(&mut rng, m * k);
+        let b = random_vec::<T>(&mut rng, k * n);
+        let c = random_vec::<T>(&mut rng, m * n);
+        for (alpha, beta) in [(T::ONE, T::ZERO), (two, minus_one), (T::ZERO, two)] {
+            let mut expected = c.clone();
+            reference_gemm((m, n, k), alpha, &a, &b, beta, &mut expected);
+            let mut result = c.clone();
+            gemm::<S, T>(m, n, k, alpha, &a, &b, beta, &mut result);
+            assert_eq!(result, expected, "{m}x{n}x{k} {alpha:?} {beta:?}");
+        }
+    }
+
+    // C isn't read when beta is zero.
+    let a = random_vec::<T>(&mut rng, 4);
+    let b = random_vec::<T>(&mut rng, 4);
+    let mut expected = vec![T::ZERO; 4];
+    reference_gemm((2, 2, 2), T::ONE, &a, &b, T::ZERO, &mut expected);
+    let mut result = vec![nan; 4];
+    gemm::<S, T>(2, 2, 2, T::ONE, &a, &b, T::ZERO, &mut result);
+    assert_eq!(result, expected);
+}
+
+fn linalg_tester<S: Simd>() {
+    linalg_tester_for::<S, f32>(f32::NAN);
+    linalg_tester_for::<S, f64>(f64::NAN);
+}
+
+engine_tester_impl!(linalg_tester);
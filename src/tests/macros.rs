@@ -0,0 +1,171 @@
+use simdeez_macros::{simd_compiletime, simd_generic, simd_runtime};
+
+use crate::engine_tester_impl;
+use crate::tests::lock_max_engine;
+use crate::{EngineKind, Simd, SimdBaseIo, SimdConsts, best_engine, max_engine, set_max_engine};
+
+/// Sums a pair of slices, with a pattern in the arguments.
+#[simd_runtime]
+fn sum_pair((a, b): (&[f32], &[f32]), mut scale: f32) -> f32 {
+    scale *= S::Vf32::set1(1.0)[0];
+    a.iter().chain(b).sum::<f32>() * scale
+}
+
+#[simd_runtime]
+fn runtime_width() -> usize {
+    S::Vf32::WIDTH
+}
+
+#[simd_compiletime]
+fn compiletime_width() -> usize {
+    S::Vf32::WIDTH
+}
+
+#[simd_generic]
+fn generic_width<T: Copy>(value: T) -> (T, usize) {
+    (value, S::Vf64::WIDTH)
+}
+
+/// Reads the values through a raw pointer.
+#[simd_runtime]
+unsafe fn sum_raw(data: *const i32, len: usize) -> i32 {
+    core::slice::from_raw_parts(data, len).iter().sum()
+}
+
+#[derive(Debug, PartialEq)]
+struct Counter {
+    lanes: Vec<usize>,
+}
+
+impl Counter {
+    #[simd_runtime]
+    fn record(&mut self, extra: usize) {
+        self.lanes.push(S::Vi32::WIDTH + extra);
+    }
+
+    #[simd_runtime]
+    fn total(&self) -> usize {
+        self.lanes.iter().sum::<usize>() + S::Vi32::zeroes()[0] as usize
+    }
+
+    #[simd_runtime]
+    fn into_lanes(mut self) -> Vec<usize> {
+        self.lanes.push(S::Vi8::WIDTH);
+        self.lanes
+    }
+}
+
+#[simd_runtime]
+impl Counter {
+    fn new() -> Counter {
+        Counter {
+            lanes: vec![S::Vf32::WIDTH],
+        }
+    }
+
+    fn doubled<const N: usize>(&self) -> [usize; N] {
+        core::array::from_fn(|i| self.lanes.get(i).copied().unwrap_or(0) * 2)
+    }
+}
+
+trait Lanes {
+    fn lanes(&self, scale: usize) -> usize;
+}
+
+#[simd_runtime]
+impl Lanes for Counter {
+    fn lanes(&self, scale: usize) -> usize {
+        S::Vf64::WIDTH * scale + self.lanes.len()
+    }
+}
+
+fn macros_tester<S: Simd>() {
+    let width = S::Vf32::WIDTH;
+    assert_eq!(sum_pair_generic::<S>((&[1.0, 2.0], &[3.0]), 2.0), 12.0);
+    assert_eq!(compiletime_width_generic::<S>(), width);
+    assert_eq!(generic_width::<_, S>('x'), ('x', S::Vf64::WIDTH));
+    let data = [1, 2, 3];
+    assert_eq!(unsafe { sum_raw_generic::<S>(data.as_ptr(), 3) }, 6);
+
+    let mut counter = Counter::new_generic::<S>();
+    counter.record_generic::<S>(1);
+    assert_eq!(counter.lanes, [width, width + 1]);
+    assert_eq!(counter.total_generic::<S>(), 2 * width + 1);
+    assert_eq!(
+        counter.doubled_generic::<3, S>(),
+        [2 * width, 2 * width + 2, 0]
+    );
+    assert_eq!(counter.lanes_generic::<S>(3), 3 * S::Vf64::WIDTH + 2);
+    assert_eq!(
+        counter.into_lanes_generic::<S>(),
+        [width, width + 1, S::Vi8::WIDTH]
+    );
+}
+
+/// The dispatching and scalar versions forward to the generic one.
+#[test]
+fn macros_dispatch() {
+    assert_eq!(sum_pair_scalar((&[1.0], &[2.0]), 1.0), 3.0);
+    assert_eq!(sum_pair((&[1.0], &[2.0]), 1.0), 3.0);
+    assert_eq!(compiletime_width_scalar(), 1);
+    assert_eq!(generic_width_scalar(5u8), (5, 1));
+    assert_eq!(unsafe { sum_raw_scalar([4, 5].as_ptr(), 2) }, 9);
+
+    let mut counter = Counter::new_scalar();
+    counter.record_scalar(0);
+    assert_eq!(counter.total_scalar(), 2);
+    assert_eq!(counter.doubled_scalar::<1>(), [2]);
+    assert_eq!(counter.lanes_scalar(1), 3);
+    assert_eq!(counter.into_lanes_scalar(), [1, 1, 1]);
+
+    let mut counter = Counter::new();
+    counter.record(0);
+    assert_eq!(counter.lanes[0], counter.lanes[1]);
+    assert_eq!(counter.total(), 2 * counter.lanes[0]);
+    assert_eq!(counter.doubled::<1>(), [2 * counter.lanes[0]]);
+    assert_eq!(counter.lanes(0), 2);
+    assert_eq!(counter.into_lanes().len(), 3);
+    assert_eq!(unsafe { sum_raw([1, 1].as_ptr(), 2) }, 2);
+}
+
+/// A free function without generic parameters keeps the engine it resolved until `set_max_engine`
+/// is called, methods pick it on every call, and the `_resolve` functions return the version of
+/// the engine picked when they are called.
+#[test]
+fn macros_resolve() {
+    let _lock = lock_max_engine();
+    let previous = max_engine();
+    set_max_engine(EngineKind::Scalar);
+    assert_eq!(runtime_width(), 1);
+    assert_eq!(runtime_width(), 1);
+    let resolved = runtime_width_resolve();
+    assert_eq!(resolved(), 1);
+    let record = Counter::record_resolve();
+    let mut counter = Counter { lanes: Vec::new() };
+    record(&mut counter, 0);
+    counter.record(1);
+    assert_eq!(counter.lanes, [1, 2]);
+    assert_eq!(Counter::doubled_resolve::<3>()(&counter), [2, 4, 0]);
+    assert_eq!(unsafe { sum_raw_resolve()([2, 3].as_ptr(), 2) }, 5);
+    assert_eq!(sum_pair_resolve()((&[1.0], &[2.0]), 2.0), 6.0);
+
+    if best_engine() == EngineKind::Avx2 {
+        set_max_engine(EngineKind::Avx2);
+        assert_eq!(runtime_width(), 8);
+        assert_eq!(runtime_width_resolve()(), 8);
+        assert_eq!(resolved(), 1);
+        record(&mut counter, 0);
+        counter.record(0);
+        assert_eq!(counter.lanes, [1, 2, 1, 8]);
+    }
+    set_max_engine(previous);
+}
+
+#[cfg(all(target_feature = "avx2", target_feature = "fma"))]
+#[test]
+fn macros_compiletime_avx2() {
+    assert_eq!(compiletime_width(), 8);
+    assert_eq!(unsafe { generic_width_avx2(1.5) }, (1.5, 4));
+}
+
+engine_tester_impl!(macros_tester);
@@ -2,3 +2,37 @@ mod lib;
 use lib::*;
 
 mod run;
+
+mod base64;
+
+mod complex;
+
+mod engine_kind;
+
+mod geometry;
+
+mod hex;
+
+mod invoking;
+
+mod lane_tables;
+
+mod linalg;
+
+mod macros;
+
+mod noise;
+
+mod parse;
+
+mod rng;
+
+mod scan;
+
+mod search;
+
+mod sort;
+
+mod testing;
+
+mod utf8;
@@ -0,0 +1,104 @@
+use crate::engine_tester_impl;
+use crate::engines::scalar::Scalar;
+
+use crate::noise::*;
+use crate::{Simd, SimdBaseIo};
+
+const NOISE_TYPES: [NoiseType; 3] = [NoiseType::Simplex, NoiseType::Perlin, NoiseType::Cellular];
+
+fn settings(noise: NoiseType, octaves: u32) -> NoiseSettings {
+    NoiseSettings {
+        seed: 1337,
+        frequency: 0.173,
+        octaves,
+        ..NoiseSettings::new(noise)
+    }
+}
+
+fn grid<S: Simd, const D: usize>(settings: &NoiseSettings, size: [usize; D]) -> Vec<f32> {
+    let mut output = vec![f32::NAN; size.iter().product()];
+    fill_grid::<S, D>(settings, [-3.5; D], size, &mut output);
+    output
+}
+
+fn noise_tester_for<S: Simd, const D: usize>(size: [usize; D]) {
+    for noise in NOISE_TYPES {
+        for octaves in [1, 4] {
+            let settings = settings(noise, octaves);
+            let result = grid::<S, D>(&settings, size);
+
+            // The output is within range, and matches the scalar engine up to rounding.
+            let expected = grid::<Scalar, D>(&settings, size);
+            for (i, (&r, &e)) in result.iter().zip(&expected).enumerate() {
+                let in_range = match noise {
+                    NoiseType::Cellular => (0.0..2.0).contains(&r),
+                    _ => (-1.0..=1.0).contains(&r),
+                };
+                assert!(in_range, "{noise:?} {octaves} {i}: {r}");
+                assert!((r - e).abs() < 1e-4, "{noise:?} {octaves} {i}: {r} != {e}");
+            }
+
+            // Sampling the same points one by one gives the same results.
+            let mut points = vec![Vec::new(); D];
+            for i in 0..result.len() {
+                let mut rest = i;
+                for (axis, points) in points.iter_mut().enumerate() {
+                    points.push(-3.5 + (rest % size[axis]) as f32);
+                    rest /= size[axis];
+                }
+            }
+            let points: [&[f32]; D] = core::array::from_fn(|axis| &points[axis][..]);
+            let mut output = vec![f32::NAN; result.len()];
+            fill_points::<S, D>(&settings, points, &mut output);
+            assert_eq!(output, result);
+
+            // A different seed gives different noise.
+            let reseeded = grid::<S, D>(
+                &NoiseSettings {
+                    seed: 7,
+                    ..settings
+                },
+                size,
+            );
+            assert!(reseeded.iter().zip(&result).any(|(a, b)| a != b));
+        }
+    }
+
+    // Perlin noise is zero on the lattice points.
+    let point = [S::Vf32::set1(3.0); D];
+    assert_eq!(perlin::<S, D>(point, 5)[0], 0.0);
+}
+
+fn noise_tester<S: Simd>() {
+    noise_tester_for::<S, 2>([37, 11]);
+    noise_tester_for::<S, 3>([13, 5, 4]);
+    noise_tester_for::<S, 4>([9, 3, 2, 2]);
+}
+
+/// The runtime dispatched helpers match the scalar engine up to rounding.
+#[test]
+fn noise_runtime() {
+    let settings = settings(NoiseType::Simplex, 3);
+    let expected = grid::<Scalar, 2>(&settings, [40, 3]);
+
+    let mut output = vec![f32::NAN; 40 * 3];
+    fill_grid_2d(settings, [-3.5; 2], [40, 3], &mut output);
+    assert!(
+        output
+            .iter()
+            .zip(&expected)
+            .all(|(r, e)| (r - e).abs() < 1e-4)
+    );
+
+    let x: Vec<f32> = (0..120).map(|i| -3.5 + (i % 40) as f32).collect();
+    let y: Vec<f32> = (0..120).map(|i| -3.5 + (i / 40) as f32).collect();
+    fill_points_2d(settings, &x, &y, &mut output);
+    assert!(
+        output
+            .iter()
+            .zip(&expected)
+            .all(|(r, e)| (r - e).abs() < 1e-4)
+    );
+}
+
+engine_tester_impl!(noise_tester);
@@ -0,0 +1,146 @@
+use rand::prelude::*;
+use rand_chacha::{ChaCha8Rng, rand_core::SeedableRng};
+
+use crate::Simd;
+use crate::engine_tester_impl;
+use crate::parse::*;
+
+fn random_digits(rng: &mut ChaCha8Rng, max_len: usize) -> String {
+    let len = rng.gen_range(1..=max_len);
+    (0..len)
+        .map(|_| char::from(b'0' + rng.gen_range(0..10)))
+        .collect()
+}
+
+fn parse_tester<S: Simd>() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+
+    for _ in 0..2000 {
+        let digits = random_digits(&mut rng, 45);
+        let sign = ["", "-", "+"].choose(&mut rng).unwrap();
+        let text = format!("{sign}{digits}");
+
+        let expected = digits
+            .parse::<u64>()
+            .map_err(|_| ParseNumberError::Overflow);
+        assert_eq!(parse::<S, u64>(digits.as_bytes()), expected, "{digits}");
+        let expected = digits
+            .parse::<u16>()
+            .map_err(|_| ParseNumberError::Overflow);
+        assert_eq!(parse::<S, u16>(digits.as_bytes()), expected, "{digits}");
+
+        let expected = text.parse::<i64>().map_err(|_| ParseNumberError::Overflow);
+        assert_eq!(parse::<S, i64>(text.as_bytes()), expected, "{text}");
+        let expected = text.parse::<i8>().map_err(|_| ParseNumberError::Overflow);
+        assert_eq!(parse::<S, i8>(text.as_bytes()), expected, "{text}");
+
+        let fraction = random_digits(&mut rng, 25);
+        let exponent: i32 = rng.gen_range(-350..350);
+        let floats = [
+            format!("{sign}{digits}"),
+            format!("{sign}{digits}.{fraction}"),
+            format!("{sign}.{fraction}"),
+            format!("{sign}{digits}."),
+            format!(
+                "{sign}{}.{}e{exponent}",
+                &digits[..1],
+                &fraction[..fraction.len().min(6)]
+            ),
+            format!(
+                "{sign}{}E+{}",
+                &digits[..digits.len().min(3)],
+                exponent.abs() % 30
+            ),
+        ];
+        for text in floats {
+            let expected = text.parse::<f64>().unwrap();
+            let result = parse::<S, f64>(text.as_bytes()).unwrap();
+            assert_eq!(result.to_bits(), expected.to_bits(), "{text}");
+
+            let expected = text.parse::<f32>().unwrap();
+            let result = parse::<S, f32>(text.as_bytes()).unwrap();
+            assert_eq!(result.to_bits(), expected.to_bits(), "{text}");
+        }
+    }
+
+    assert_eq!(parse::<S, u64>(b"18446744073709551615"), Ok(u64::MAX));
+    assert_eq!(
+        parse::<S, u64>(b"18446744073709551616"),
+        Err(ParseNumberError::Overflow)
+    );
+    assert_eq!(parse::<S, i64>(b"-9223372036854775808"), Ok(i64::MIN));
+    assert_eq!(
+        parse::<S, i64>(b"9223372036854775808"),
+        Err(ParseNumberError::Overflow)
+    );
+    assert_eq!(
+        parse::<S, u32>(b"00000000000000000000000000000000000042"),
+        Ok(42)
+    );
+    assert_eq!(parse::<S, u32>(b""), Err(ParseNumberError::NoDigits));
+    assert_eq!(parse::<S, u32>(b"-1"), Err(ParseNumberError::NoDigits));
+    assert_eq!(parse::<S, i32>(b"-"), Err(ParseNumberError::NoDigits));
+    assert_eq!(
+        parse::<S, i32>(b"12a"),
+        Err(ParseNumberError::UnexpectedByte)
+    );
+    assert_eq!(parse::<S, f64>(b"."), Err(ParseNumberError::NoDigits));
+    assert_eq!(parse_prefix::<S, f64>(b"1.5e"), Ok((1.5, 3)));
+    assert_eq!(parse_prefix::<S, f64>(b"-2e-3x"), Ok((-2e-3, 5)));
+    assert_eq!(parse_prefix::<S, u8>(b"255,"), Ok((255, 3)));
+
+    assert_eq!(
+        find_digit::<S>(b"abcdefghijklmnopqrstuvwxyzabcdefghij7"),
+        Some(36)
+    );
+    assert_eq!(find_digit::<S>(b"no digits"), None);
+}
+
+fn parse_list_tester<S: Simd>() {
+    let input = b"1,-22,333,4444,55555,";
+    let mut output = [0i32; 8];
+    assert_eq!(
+        parse_list::<S, i32>(input, b',', &mut output),
+        Ok((5, input.len()))
+    );
+    assert_eq!(output[..5], [1, -22, 333, 4444, 55555]);
+
+    // A short output stops early, and the rest can be parsed from the returned position.
+    let mut output = [0i32; 2];
+    assert_eq!(parse_list::<S, i32>(input, b',', &mut output), Ok((2, 6)));
+    assert_eq!(
+        parse_list::<S, i32>(&input[6..], b',', &mut output),
+        Ok((2, 9))
+    );
+    assert_eq!(output, [333, 4444]);
+
+    let mut output = [0.0f64; 4];
+    assert_eq!(
+        parse_list::<S, f64>(b"0.5 1e3 -7", b' ', &mut output),
+        Ok((3, 10))
+    );
+    assert_eq!(output[..3], [0.5, 1e3, -7.0]);
+
+    let mut output = [0u8; 4];
+    let error = parse_list::<S, u8>(b"1;2;300;4", b';', &mut output);
+    assert_eq!(
+        error,
+        Err(ListParseError {
+            position: 4,
+            error: ParseNumberError::Overflow
+        })
+    );
+    let error = parse_list::<S, u8>(b"1;2x;3", b';', &mut output);
+    assert_eq!(
+        error,
+        Err(ListParseError {
+            position: 2,
+            error: ParseNumberError::UnexpectedByte
+        })
+    );
+
+    let numbers: Result<Vec<u64>, _> = split_numbers::<S, u64>(b"10\n20\n30", b'\n').collect();
+    assert_eq!(numbers, Ok(vec![10, 20, 30]));
+}
+
+engine_tester_impl!(parse_tester, parse_list_tester);
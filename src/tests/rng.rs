@@ -0,0 +1,195 @@
+use crate::engine_tester_impl;
+use crate::engines::scalar::Scalar;
+
+use crate::rng::*;
+use crate::{Simd, SimdConsts};
+
+const SEED: u64 = 0x5eed;
+
+/// The first outputs of the reference `pcg32-demo`, which seeds with 42 and stream 54.
+#[test]
+fn pcg32_reference_values() {
+    let mut rng = Pcg32::<Scalar>::from_stream(42, 54);
+    let expected = [
+        0xa15c02b7u32,
+        0x7b47f409,
+        0xba1d3330,
+        0x83d2f293,
+        0xbfa4784b,
+        0xcbed606e,
+    ];
+    for expected in expected {
+        assert_eq!(rng.next_u32_lanes()[0] as u32, expected);
+    }
+}
+
+/// Xoshiro256++ on plain integers, started from the same state as stream 0.
+#[test]
+fn xoshiro_reference_values() {
+    let mut splitmix = SEED;
+    let mut s = [0; 4].map(|_: u64| {
+        splitmix = splitmix.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let z = (splitmix ^ (splitmix >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        let z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    });
+
+    let mut rng = Xoshiro256PlusPlus::<Scalar>::new(SEED);
+    for _ in 0..100 {
+        let expected = s[0].wrapping_add(s[3]).rotate_left(23).wrapping_add(s[0]);
+        let t = s[1] << 17;
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(45);
+        assert_eq!(rng.next_u64()[0] as u64, expected);
+    }
+}
+
+/// The kinds of output, each collected from its own generator.
+const KINDS: usize = 6;
+
+macro_rules! rng_outputs_for {
+    ($name:ident, $rng:ident) => {
+        /// Returns the bits of the lanes of `calls` outputs of one kind, with every normal lane as
+        /// a pair of values.
+        fn $name<S: Simd>(kind: usize, first_stream: u64, calls: usize) -> Vec<Vec<Vec<u64>>> {
+            let mut rng = $rng::<S>::from_stream(SEED, first_stream);
+            (0..calls)
+                .map(|_| match kind {
+                    0 => {
+                        let v = rng.next_i32();
+                        (0..S::Vi32::WIDTH)
+                            .map(|i| vec![v[i] as u32 as u64])
+                            .collect()
+                    }
+                    1 => {
+                        let v = rng.next_u64();
+                        (0..S::Vi64::WIDTH).map(|i| vec![v[i] as u64]).collect()
+                    }
+                    2 => {
+                        let v = rng.next_f32();
+                        (0..S::Vf32::WIDTH)
+                            .map(|i| vec![v[i].to_bits() as u64])
+                            .collect()
+                    }
+                    3 => {
+                        let v = rng.next_f64();
+                        (0..S::Vf64::WIDTH).map(|i| vec![v[i].to_bits()]).collect()
+                    }
+                    4 => {
+                        let (z0, z1) = rng.next_normal_f32();
+                        (0..S::Vf32::WIDTH)
+                            .map(|i| vec![z0[i].to_bits() as u64, z1[i].to_bits() as u64])
+                            .collect()
+                    }
+                    _ => {
+                        let (z0, z1) = rng.next_normal_f64();
+                        (0..S::Vf64::WIDTH)
+                            .map(|i| vec![z0[i].to_bits(), z1[i].to_bits()])
+                            .collect()
+                    }
+                })
+                .collect()
+        }
+    };
+}
+rng_outputs_for!(xoshiro_outputs, Xoshiro256PlusPlus);
+rng_outputs_for!(pcg32_outputs, Pcg32);
+
+type Outputs = fn(usize, u64, usize) -> Vec<Vec<Vec<u64>>>;
+
+/// Concatenates the values of every stream, where lane `j` belongs to stream `j % streams`.
+fn by_stream(outputs: &[Vec<Vec<u64>>], streams: usize) -> Vec<Vec<u64>> {
+    let mut values = vec![Vec::new(); streams];
+    for vector in outputs {
+        for (lane, value) in vector.iter().enumerate() {
+            values[lane % streams].extend(value);
+        }
+    }
+    values
+}
+
+/// Every stream of an engine gives exactly the same values as the same stream on the scalar
+/// engine, for every kind of output.
+fn reproducible_tester<S: Simd>(outputs: Outputs, scalar: Outputs) {
+    let calls = 50;
+    let first_stream = 3;
+    for kind in 0..KINDS {
+        let streams = by_stream(&outputs(kind, first_stream, calls), S::Vi64::WIDTH);
+        for (k, values) in streams.iter().enumerate() {
+            let reference = scalar(kind, first_stream + k as u64, values.len());
+            assert_eq!(
+                values,
+                &by_stream(&reference, 1)[0][..values.len()],
+                "kind {kind}, stream {k}"
+            );
+        }
+    }
+}
+
+/// The normals match Box-Muller computed with the standard library, for the same uniform values,
+/// and all the outputs have the expected mean and variance.
+fn distribution_tester<S: Simd, R: SimdRng<S>>(mut rng: R, mut uniforms: R) {
+    let calls = 2000;
+    let mut sums = [0.0f64; 4];
+    let mut count = [0usize; 4];
+    for _ in 0..calls {
+        // Both generators take the same values from their streams, so `uniforms` has to skip the
+        // values `rng` takes for the other outputs below.
+        let (z0, z1) = rng.next_normal_f64();
+        let (u1, u2) = (uniforms.next_f64(), uniforms.next_f64());
+        uniforms.next_normal_f32();
+        uniforms.next_f32();
+        uniforms.next_f64();
+        for i in 0..S::Vf64::WIDTH {
+            let radius = (-2.0 * (1.0 - u1[i]).ln()).sqrt();
+            let angle = 2.0 * core::f64::consts::PI * u2[i] - core::f64::consts::FRAC_PI_4;
+            assert!(
+                (z0[i] - radius * angle.cos()).abs() < 1e-12,
+                "{} {}",
+                z0[i],
+                radius * angle.cos()
+            );
+            assert!((z1[i] - radius * angle.sin()).abs() < 1e-12);
+            sums[0] += z0[i] + z1[i];
+            sums[1] += z0[i] * z0[i] + z1[i] * z1[i];
+            count[0] += 2;
+        }
+
+        let (z0, z1) = rng.next_normal_f32();
+        for i in 0..S::Vf32::WIDTH {
+            assert!(z0[i].is_finite() && z1[i].is_finite());
+            sums[2] += (z0[i] * z0[i] + z1[i] * z1[i]) as f64;
+            count[2] += 2;
+        }
+
+        let (f32s, f64s) = (rng.next_f32(), rng.next_f64());
+        for i in 0..S::Vf32::WIDTH {
+            assert!((0.0..1.0).contains(&f32s[i]));
+            sums[3] += f32s[i] as f64;
+            count[3] += 1;
+        }
+        for i in 0..S::Vf64::WIDTH {
+            assert!((0.0..1.0).contains(&f64s[i]));
+        }
+    }
+    assert!((sums[0] / count[0] as f64).abs() < 0.05);
+    assert!((sums[1] / count[0] as f64 - 1.0).abs() < 0.05);
+    assert!((sums[2] / count[2] as f64 - 1.0).abs() < 0.05);
+    assert!((sums[3] / count[3] as f64 - 0.5).abs() < 0.02);
+}
+
+fn rng_tester<S: Simd>() {
+    reproducible_tester::<S>(xoshiro_outputs::<S>, xoshiro_outputs::<Scalar>);
+    reproducible_tester::<S>(pcg32_outputs::<S>, pcg32_outputs::<Scalar>);
+    distribution_tester::<S, _>(
+        Xoshiro256PlusPlus::<S>::new(SEED),
+        Xoshiro256PlusPlus::<S>::new(SEED),
+    );
+    distribution_tester::<S, _>(Pcg32::<S>::new(SEED), Pcg32::<S>::new(SEED));
+}
+
+engine_tester_impl!(rng_tester);
@@ -83,6 +83,72 @@ elementwise_eq_tester_impl!(
     EqPrecision::exact()
 );
 elementwise_eq_tester_impl!(SimdInt64, cast_f64, one_arg, EqPrecision::exact());
+truncate_to_i32_tester_impl!();
 
 horizontal_add_tester_impl!(signed);
 horizontal_add_tester_impl!(unsigned);
+horizontal_add_scalar_tester_impl!(f32);
+horizontal_add_scalar_tester_impl!(f64);
+
+lane_ops_tester_impl!(i8);
+lane_ops_tester_impl!(i16);
+lane_ops_tester_impl!(i32);
+lane_ops_tester_impl!(i64);
+lane_ops_tester_impl!(f32);
+lane_ops_tester_impl!(f64);
+
+/// The scalar engine has a single lane, so any other index is out of range.
+#[test]
+#[should_panic(expected = "I == 0")]
+fn scalar_extract_out_of_range() {
+    <Scalar as Simd>::Vf32::set1(1.0).extract::<1>();
+}
+
+#[test]
+#[should_panic(expected = "I == 0")]
+fn scalar_insert_out_of_range() {
+    <Scalar as Simd>::Vi64::zeroes().insert::<3>(1);
+}
+
+compress_tester_impl!(i8);
+compress_tester_impl!(i16);
+compress_tester_impl!(i32);
+compress_tester_impl!(i64);
+compress_tester_impl!(f32);
+compress_tester_impl!(f64);
+
+const INT_SCANS: [ScanOp; 4] = [ScanOp::Add, ScanOp::Max, ScanOp::Min, ScanOp::Xor];
+// Float sums depend on the order of the additions, so they are checked on integer values in the
+// slice scan tests instead.
+const FLOAT_SCANS: [ScanOp; 3] = [ScanOp::Max, ScanOp::Min, ScanOp::Xor];
+
+prefix_scan_tester_impl!(i8, INT_SCANS);
+prefix_scan_tester_impl!(i16, INT_SCANS);
+prefix_scan_tester_impl!(i32, INT_SCANS);
+prefix_scan_tester_impl!(i64, INT_SCANS);
+prefix_scan_tester_impl!(f32, FLOAT_SCANS);
+prefix_scan_tester_impl!(f64, FLOAT_SCANS);
+
+sort_lanes_tester_impl!(i8);
+sort_lanes_tester_impl!(i16);
+sort_lanes_tester_impl!(i32);
+sort_lanes_tester_impl!(i64);
+sort_lanes_tester_impl!(f32);
+sort_lanes_tester_impl!(f64);
+
+zip_tester_impl!(f32);
+zip_tester_impl!(f64);
+
+memory_tester_impl!(i8);
+memory_tester_impl!(i16);
+memory_tester_impl!(i32);
+memory_tester_impl!(i64);
+memory_tester_impl!(f32);
+memory_tester_impl!(f64);
+
+array_constructors_tester_impl!(i8);
+array_constructors_tester_impl!(i16);
+array_constructors_tester_impl!(i32);
+array_constructors_tester_impl!(i64);
+array_constructors_tester_impl!(f32);
+array_constructors_tester_impl!(f64);
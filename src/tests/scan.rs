@@ -0,0 +1,106 @@
+use rand::prelude::*;
+use rand_chacha::{ChaCha8Rng, rand_core::SeedableRng};
+
+use crate::engine_tester_impl;
+use crate::scan::*;
+use crate::{ScanOp, Simd};
+
+const OPS: [ScanOp; 4] = [ScanOp::Add, ScanOp::Max, ScanOp::Min, ScanOp::Xor];
+
+fn combine_i32(op: ScanOp, a: i32, b: i32) -> i32 {
+    match op {
+        ScanOp::Add => a.wrapping_add(b),
+        ScanOp::Max => a.max(b),
+        ScanOp::Min => a.min(b),
+        ScanOp::Xor => a ^ b,
+    }
+}
+
+fn combine_i8(op: ScanOp, a: i8, b: i8) -> i8 {
+    combine_i32(op, a as i32, b as i32) as i8
+}
+
+fn scan_tester<S: Simd>() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    for len in 0..150 {
+        let input = (0..len)
+            .map(|_| rng.gen_range(i32::MIN..=i32::MAX))
+            .collect::<Vec<i32>>();
+        let bytes = (0..len)
+            .map(|_| rng.gen_range(i8::MIN..=i8::MAX))
+            .collect::<Vec<i8>>();
+        // Small integers, so the float sums are exact in any order.
+        let floats = (0..len)
+            .map(|_| rng.gen_range(-1000..1000) as f64)
+            .collect::<Vec<f64>>();
+        let init = rng.gen_range(i32::MIN..=i32::MAX);
+
+        for op in OPS {
+            let mut expected = input.clone();
+            for i in 1..len {
+                expected[i] = combine_i32(op, expected[i - 1], input[i]);
+            }
+            // The output may be longer than the input, and the rest must be left alone.
+            let mut output = vec![7; len + 3];
+            inclusive_scan::<S::Vi32>(&input, &mut output, op);
+            assert_eq!(output[..len], expected, "{op:?}");
+            assert_eq!(output[len..], [7; 3]);
+
+            let mut total = init;
+            let expected = input
+                .iter()
+                .map(|&x| {
+                    let before = total;
+                    total = combine_i32(op, total, x);
+                    before
+                })
+                .collect::<Vec<_>>();
+            let mut output = vec![7; len + 3];
+            let result = exclusive_scan::<S::Vi32>(&input, &mut output, init, op);
+            assert_eq!(output[..len], expected, "{op:?}");
+            assert_eq!(output[len..], [7; 3]);
+            assert_eq!(result, total);
+
+            let mut expected = bytes.clone();
+            for i in 1..len {
+                expected[i] = combine_i8(op, expected[i - 1], bytes[i]);
+            }
+            let mut output = vec![0; len];
+            inclusive_scan::<S::Vi8>(&bytes, &mut output, op);
+            assert_eq!(output, expected, "{op:?}");
+        }
+
+        for op in [ScanOp::Add, ScanOp::Max, ScanOp::Min] {
+            let mut expected = floats.clone();
+            for i in 1..len {
+                expected[i] = match op {
+                    ScanOp::Add => expected[i - 1] + floats[i],
+                    ScanOp::Max => expected[i - 1].max(floats[i]),
+                    _ => expected[i - 1].min(floats[i]),
+                };
+            }
+            let mut output = vec![0.0; len];
+            inclusive_scan::<S::Vf64>(&floats, &mut output, op);
+            assert_eq!(output, expected, "{op:?}");
+        }
+    }
+
+    // Delta decoding: an exclusive sum of the deltas gives every position, and the total gives the end.
+    let deltas = [3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5];
+    let mut positions = [0; 11];
+    let end = exclusive_scan::<S::Vi32>(&deltas, &mut positions, 100, ScanOp::Add);
+    assert_eq!(
+        positions,
+        [100, 103, 104, 108, 109, 114, 123, 125, 131, 136, 139]
+    );
+    assert_eq!(end, 144);
+
+    let mut empty = [];
+    assert_eq!(
+        exclusive_scan::<S::Vi32>(&[], &mut empty, 5, ScanOp::Max),
+        5
+    );
+    inclusive_scan::<S::Vi32>(&[], &mut empty, ScanOp::Add);
+}
+
+engine_tester_impl!(scan_tester);
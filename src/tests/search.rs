@@ -0,0 +1,74 @@
+use rand::prelude::*;
+use rand_chacha::{ChaCha8Rng, rand_core::SeedableRng};
+
+use crate::Simd;
+use crate::engine_tester_impl;
+use crate::search::*;
+
+/// Haystacks of every length up to a few vectors long, drawn from a small alphabet so that
+/// matches, repeated matches and near misses are all common.
+fn haystacks() -> impl Iterator<Item = Vec<u8>> {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    (0..100).flat_map(move |len| {
+        let alphabet: &[u8] = if len % 2 == 0 { b"ab" } else { b"abcd\0\xff" };
+        (0..4)
+            .map(|_| {
+                (0..len)
+                    .map(|_| *alphabet.choose(&mut rng).unwrap())
+                    .collect()
+            })
+            .collect::<Vec<_>>()
+    })
+}
+
+fn search_tester<S: Simd>() {
+    let needles = b"ac\0\xffz";
+    for haystack in haystacks() {
+        for &needle in needles {
+            let expected = haystack.iter().position(|&b| b == needle);
+            assert_eq!(
+                find_byte::<S>(&haystack, needle),
+                expected,
+                "{haystack:?} {needle}"
+            );
+
+            let expected = haystack.iter().rposition(|&b| b == needle);
+            assert_eq!(
+                rfind_byte::<S>(&haystack, needle),
+                expected,
+                "{haystack:?} {needle}"
+            );
+
+            let expected = haystack.iter().filter(|&&b| b == needle).count();
+            assert_eq!(
+                count_byte::<S>(&haystack, needle),
+                expected,
+                "{haystack:?} {needle}"
+            );
+
+            let expected = haystack.iter().position(|&b| b == needle || b == b'd');
+            assert_eq!(find_any_of2::<S>(&haystack, needle, b'd'), expected);
+
+            let expected = haystack
+                .iter()
+                .position(|&b| b == needle || b == b'd' || b == 0);
+            assert_eq!(find_any_of3::<S>(&haystack, needle, b'd', 0), expected);
+        }
+
+        let substrings: [&[u8]; 6] = [b"", b"b", b"ab", b"aba", b"abba", b"bbbbbbbbbb"];
+        for needle in substrings {
+            let expected = if needle.is_empty() {
+                Some(0)
+            } else {
+                haystack.windows(needle.len()).position(|w| w == needle)
+            };
+            assert_eq!(
+                find_substring::<S>(&haystack, needle),
+                expected,
+                "{haystack:?} {needle:?}"
+            );
+        }
+    }
+}
+
+engine_tester_impl!(search_tester);
@@ -0,0 +1,92 @@
+use rand::prelude::*;
+use rand_chacha::{ChaCha8Rng, rand_core::SeedableRng};
+
+use crate::Simd;
+use crate::engine_tester_impl;
+use crate::sort::*;
+
+/// Lengths around the network size and partition boundaries, plus a few large ones.
+fn lengths() -> impl Iterator<Item = usize> {
+    (0..300).chain([1000, 4096, 10_000])
+}
+
+fn check_ints<S: Simd, T: SimdSort + Ord + core::fmt::Debug>(
+    rng: &mut ChaCha8Rng,
+    value: impl Fn(&mut ChaCha8Rng) -> T,
+) {
+    for len in lengths() {
+        let input = (0..len).map(|_| value(rng)).collect::<Vec<T>>();
+        let mut expected = input.clone();
+        expected.sort_unstable();
+
+        let mut sorted = input.clone();
+        sort_unstable::<S, T>(&mut sorted);
+        assert_eq!(sorted, expected, "{input:?}");
+
+        let mut sorted = input.clone();
+        simd_sort_unstable(&mut sorted);
+        assert_eq!(sorted, expected);
+    }
+}
+
+fn sort_tester<S: Simd>() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    check_ints::<S, i32>(&mut rng, |rng| rng.gen_range(i32::MIN..=i32::MAX));
+    check_ints::<S, i64>(&mut rng, |rng| rng.gen_range(i64::MIN..=i64::MAX));
+    // Lots of duplicates, including the padding value.
+    check_ints::<S, i32>(&mut rng, |rng| {
+        *[i32::MIN, -1, 0, 7, i32::MAX].choose(rng).unwrap()
+    });
+    check_ints::<S, i64>(&mut rng, |rng| rng.gen_range(0..3));
+
+    // Already sorted and reversed inputs are the classic quicksort worst cases.
+    let mut ascending = (0..5000).collect::<Vec<i32>>();
+    sort_unstable::<S, i32>(&mut ascending);
+    assert!(ascending.windows(2).all(|w| w[0] <= w[1]));
+    let mut descending = (0..5000).rev().collect::<Vec<i64>>();
+    sort_unstable::<S, i64>(&mut descending);
+    assert_eq!(descending, (0..5000).collect::<Vec<i64>>());
+
+    let specials = [
+        f64::NAN,
+        -f64::NAN,
+        f64::INFINITY,
+        f64::NEG_INFINITY,
+        0.0,
+        -0.0,
+        f64::MIN_POSITIVE,
+        -f64::MIN_POSITIVE,
+        f64::MAX,
+        f64::MIN,
+        1.5,
+        -1.5,
+    ];
+    for len in lengths() {
+        let input = (0..len)
+            .map(|_| {
+                if rng.gen_range(0..4) == 0 {
+                    *specials.choose(&mut rng).unwrap()
+                } else {
+                    rng.gen_range(-1e6..1e6)
+                }
+            })
+            .collect::<Vec<f64>>();
+
+        let mut expected = input.clone();
+        expected.sort_unstable_by(f64::total_cmp);
+        let mut sorted = input.clone();
+        sort_unstable::<S, f64>(&mut sorted);
+        let bits = |v: &[f64]| v.iter().map(|x| x.to_bits()).collect::<Vec<_>>();
+        assert_eq!(bits(&sorted), bits(&expected));
+
+        let input = input.iter().map(|&x| x as f32).collect::<Vec<f32>>();
+        let mut expected = input.clone();
+        expected.sort_unstable_by(f32::total_cmp);
+        let mut sorted = input.clone();
+        sort_unstable::<S, f32>(&mut sorted);
+        let bits = |v: &[f32]| v.iter().map(|x| x.to_bits()).collect::<Vec<_>>();
+        assert_eq!(bits(&sorted), bits(&expected));
+    }
+}
+
+engine_tester_impl!(sort_tester);
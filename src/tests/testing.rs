@@ -0,0 +1,116 @@
+use rand::prelude::*;
+use rand_chacha::{ChaCha8Rng, rand_core::SeedableRng};
+
+use crate::prelude::*;
+use crate::{EngineKind, simd_test};
+
+use super::EqPrecision;
+
+simd_runtime_generate!(
+    #[allow(dead_code)]
+    fn sum_squares(data: &[f32]) -> f32 {
+        let mut sum = S::Vf32::zeroes();
+        let chunks = data.chunks_exact(S::Vf32::WIDTH);
+        let rest = chunks.remainder();
+        for chunk in chunks {
+            let v = S::Vf32::load_from_slice(chunk);
+            sum = v.mul_add(v, sum);
+        }
+        sum.horizontal_add() + rest.iter().map(|x| x * x).sum::<f32>()
+    }
+);
+
+simd_runtime_generate!(
+    #[allow(dead_code)]
+    fn scale(data: &[f64], factor: f64) -> Vec<f64> {
+        let mut output = vec![0.0; data.len()];
+        let width = S::Vf64::WIDTH;
+        let split = data.len() - data.len() % width;
+        for (chunk, out) in data[..split]
+            .chunks_exact(width)
+            .zip(output.chunks_exact_mut(width))
+        {
+            (S::Vf64::load_from_slice(chunk) * factor).copy_to_slice(out);
+        }
+        for i in split..data.len() {
+            output[i] = data[i] * factor;
+        }
+        output
+    }
+);
+
+simd_runtime_generate!(
+    #[allow(dead_code)]
+    fn position_max(data: &[i32]) -> Option<usize> {
+        let max = data.iter().max()?;
+        let max = S::Vi32::set1(*max);
+        let width = S::Vi32::WIDTH;
+        let split = data.len() - data.len() % width;
+        for (i, chunk) in data[..split].chunks_exact(width).enumerate() {
+            let equal = S::Vi32::load_from_slice(chunk).cmp_eq(max);
+            if let Some(lane) = (0..width).find(|&lane| equal[lane] != 0) {
+                return Some(i * width + lane);
+            }
+        }
+        data[split..]
+            .iter()
+            .position(|x| *x == max[0])
+            .map(|lane| split + lane)
+    }
+);
+
+fn random_f32s(rng: &mut ChaCha8Rng, len: usize) -> Vec<f32> {
+    (0..len).map(|_| rng.gen_range(-10.0..10.0)).collect()
+}
+
+#[test]
+fn simd_test_matches_scalar() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let floats: Vec<Vec<f32>> = (0..50).map(|len| random_f32s(&mut rng, len)).collect();
+    simd_test!(
+        sum_squares,
+        floats.iter().map(|data| (&data[..],)),
+        EqPrecision::almost(5)
+    );
+
+    let doubles: Vec<Vec<f64>> = floats
+        .iter()
+        .map(|data| data.iter().map(|&x| x as f64).collect())
+        .collect();
+    simd_test!(
+        scale,
+        doubles.iter().map(|data| (&data[..], -1.5)),
+        EqPrecision::exact()
+    );
+
+    let ints: Vec<Vec<i32>> = (0..50)
+        .map(|len| (0..len).map(|_| rng.gen_range(0..20)).collect())
+        .collect();
+    simd_test!(
+        position_max,
+        ints.iter().map(|data| (&data[..],)),
+        EqPrecision::exact()
+    );
+}
+
+/// Engines that differ from the scalar engine are reported, whenever the CPU supports them and
+/// whatever the target features of the build.
+#[test]
+fn simd_test_reports_mismatch() {
+    simd_runtime_generate!(
+        #[allow(dead_code)]
+        fn lane_count() -> usize {
+            S::Vf32::WIDTH
+        }
+    );
+
+    let result = std::panic::catch_unwind(|| {
+        simd_test!(lane_count, [()], EqPrecision::exact());
+    });
+    if EngineKind::Avx2.is_available() {
+        let message = result.unwrap_err().downcast::<String>().unwrap();
+        assert!(message.contains("Avx2: Expected 1, got 8"), "{message}");
+    } else {
+        assert!(result.is_ok());
+    }
+}
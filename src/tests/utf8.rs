@@ -0,0 +1,81 @@
+use rand::prelude::*;
+use rand_chacha::{ChaCha8Rng, rand_core::SeedableRng};
+
+use crate::Simd;
+use crate::engine_tester_impl;
+use crate::utf8::*;
+
+/// Valid text mixing every sequence length, including the boundary code points.
+fn valid_text(rng: &mut ChaCha8Rng, chars: usize) -> Vec<u8> {
+    const CHARS: [char; 12] = [
+        'a',
+        '\0',
+        '\x7f',
+        '\u{80}',
+        '\u{7ff}',
+        '\u{800}',
+        '\u{d7ff}',
+        '\u{e000}',
+        '\u{ffff}',
+        '\u{10000}',
+        '\u{10ffff}',
+        'é',
+    ];
+    let mut text = String::new();
+    for _ in 0..chars {
+        if rng.gen_bool(0.5) {
+            text.push(*CHARS.choose(rng).unwrap());
+        } else {
+            text.push(char::from_u32(rng.gen_range(0..0x11_0000)).unwrap_or('x'));
+        }
+    }
+    text.into_bytes()
+}
+
+fn utf8_tester<S: Simd>() {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    for chars in (0..80).chain([200, 1000]) {
+        for _ in 0..8 {
+            let mut text = valid_text(&mut rng, chars);
+            assert_eq!(validate_utf8_generic::<S>(&text), Ok(()), "{text:?}");
+
+            // Corrupt a random byte, or cut the text short, and compare with the standard library.
+            if !text.is_empty() {
+                if rng.gen_bool(0.5) {
+                    let index = rng.gen_range(0..text.len());
+                    text[index] = rng.gen_range(0..=255);
+                } else {
+                    text.truncate(rng.gen_range(0..text.len()));
+                }
+            }
+
+            let expected = core::str::from_utf8(&text)
+                .map(|_| ())
+                .map_err(|e| (e.valid_up_to(), e.error_len()));
+            let result =
+                validate_utf8_generic::<S>(&text).map_err(|e| (e.valid_up_to(), e.error_len()));
+            assert_eq!(result, expected, "{text:?}");
+        }
+    }
+
+    let invalid: [&[u8]; 10] = [
+        b"\x80",
+        b"\xc0\x80",
+        b"\xe0\x80\x80",
+        b"\xed\xa0\x80",
+        b"\xf0\x80\x80\x80",
+        b"\xf4\x90\x80\x80",
+        b"\xf8",
+        b"\xe2\x82",
+        b"\xf0\x9f\x98\x80\x80",
+        b"abc\xe2\x82\xacdef\xff",
+    ];
+    for text in invalid {
+        let expected = core::str::from_utf8(text).unwrap_err();
+        let result = validate_utf8_generic::<S>(text).unwrap_err();
+        assert_eq!(result.valid_up_to(), expected.valid_up_to(), "{text:?}");
+        assert_eq!(result.error_len(), expected.error_len(), "{text:?}");
+    }
+}
+
+engine_tester_impl!(utf8_tester);
@@ -0,0 +1,235 @@
+//! UTF-8 validation over byte slices.
+//!
+//! This uses the lookup algorithm from "Validating UTF-8 In Less Than One Instruction Per Byte"
+//! (Keiser and Lemire). Every byte is classified together with the byte before it by looking up
+//! their nibbles in three 16 entry tables, which catches every error involving two bytes. The
+//! remaining errors are missing or extra continuation bytes, which are found by checking that a
+//! continuation byte follows a lead byte two or three positions back exactly when one is expected.
+
+use core::fmt;
+
+use crate::prelude::*;
+
+/// The position of the first invalid sequence in a byte slice, with the same meaning as
+/// `core::str::Utf8Error`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Utf8ErrorPosition {
+    valid_up_to: usize,
+    error_len: Option<usize>,
+}
+
+impl Utf8ErrorPosition {
+    /// The length of the prefix of the input that is valid UTF-8.
+    pub fn valid_up_to(&self) -> usize {
+        self.valid_up_to
+    }
+
+    /// The length of the invalid sequence after the valid prefix, or `None` if the input ended in
+    /// the middle of a sequence that might have been valid.
+    pub fn error_len(&self) -> Option<usize> {
+        self.error_len
+    }
+}
+
+impl fmt::Display for Utf8ErrorPosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.error_len {
+            Some(len) => write!(
+                f,
+                "invalid utf-8 sequence of {} bytes from index {}",
+                len, self.valid_up_to
+            ),
+            None => write!(
+                f,
+                "incomplete utf-8 byte sequence from index {}",
+                self.valid_up_to
+            ),
+        }
+    }
+}
+
+// Error flags for a pair of bytes. Each table below holds the flags that are possible given one
+// nibble of the pair, so a pair is invalid if a flag survives all three lookups.
+const TOO_SHORT: i8 = 1 << 0;
+const TOO_LONG: i8 = 1 << 1;
+const OVERLONG_3: i8 = 1 << 2;
+const TOO_LARGE: i8 = 1 << 3;
+const SURROGATE: i8 = 1 << 4;
+const OVERLONG_2: i8 = 1 << 5;
+const TOO_LARGE_1000: i8 = 1 << 6;
+const OVERLONG_4: i8 = 1 << 6;
+const TWO_CONTS: i8 = 1 << 7;
+const CARRY: i8 = TOO_SHORT | TOO_LONG | TWO_CONTS;
+
+/// Indexed by the high nibble of the first byte of the pair.
+const BYTE_1_HIGH: [i8; 16] = [
+    // 0xxx: ASCII
+    TOO_LONG,
+    TOO_LONG,
+    TOO_LONG,
+    TOO_LONG,
+    TOO_LONG,
+    TOO_LONG,
+    TOO_LONG,
+    TOO_LONG,
+    // 10xx: continuation
+    TWO_CONTS,
+    TWO_CONTS,
+    TWO_CONTS,
+    TWO_CONTS,
+    // 1100: two byte lead, always overlong
+    TOO_SHORT | OVERLONG_2,
+    // 1101: two byte lead
+    TOO_SHORT,
+    // 1110: three byte lead
+    TOO_SHORT | OVERLONG_3 | SURROGATE,
+    // 1111: four byte lead
+    TOO_SHORT | TOO_LARGE | TOO_LARGE_1000 | OVERLONG_4,
+];
+
+/// Indexed by the low nibble of the first byte of the pair.
+const BYTE_1_LOW: [i8; 16] = [
+    CARRY | OVERLONG_3 | OVERLONG_2 | OVERLONG_4,
+    CARRY | OVERLONG_2,
+    CARRY,
+    CARRY,
+    CARRY | TOO_LARGE,
+    CARRY | TOO_LARGE | TOO_LARGE_1000,
+    CARRY | TOO_LARGE | TOO_LARGE_1000,
+    CARRY | TOO_LARGE | TOO_LARGE_1000,
+    CARRY | TOO_LARGE | TOO_LARGE_1000,
+    CARRY | TOO_LARGE | TOO_LARGE_1000,
+    CARRY | TOO_LARGE | TOO_LARGE_1000,
+    CARRY | TOO_LARGE | TOO_LARGE_1000,
+    CARRY | TOO_LARGE | TOO_LARGE_1000,
+    CARRY | TOO_LARGE | TOO_LARGE_1000 | SURROGATE,
+    CARRY | TOO_LARGE | TOO_LARGE_1000,
+    CARRY | TOO_LARGE | TOO_LARGE_1000,
+];
+
+/// Indexed by the high nibble of the second byte of the pair.
+const BYTE_2_HIGH: [i8; 16] = [
+    // 0xxx: ASCII
+    TOO_SHORT,
+    TOO_SHORT,
+    TOO_SHORT,
+    TOO_SHORT,
+    TOO_SHORT,
+    TOO_SHORT,
+    TOO_SHORT,
+    TOO_SHORT,
+    // 1000: continuation
+    TOO_LONG | OVERLONG_2 | TWO_CONTS | OVERLONG_3 | TOO_LARGE_1000 | OVERLONG_4,
+    // 1001: continuation
+    TOO_LONG | OVERLONG_2 | TWO_CONTS | OVERLONG_3 | TOO_LARGE,
+    // 101x: continuation
+    TOO_LONG | OVERLONG_2 | TWO_CONTS | SURROGATE | TOO_LARGE,
+    TOO_LONG | OVERLONG_2 | TWO_CONTS | SURROGATE | TOO_LARGE,
+    // 11xx: lead
+    TOO_SHORT,
+    TOO_SHORT,
+    TOO_SHORT,
+    TOO_SHORT,
+];
+
+/// The largest engine width, so partial blocks can be staged in a fixed size buffer.
+const MAX_WIDTH: usize = 32;
+
+/// Checks the bytes at `ptr` against the three bytes before each of them, returning a vector that
+/// is nonzero in every lane where an error was found. The three bytes before `ptr` must be readable.
+#[inline(always)]
+unsafe fn check_block<S: Simd>(ptr: *const i8) -> S::Vi8 {
+    let input = S::Vi8::load_from_ptr_unaligned(ptr);
+    let prev1 = S::Vi8::load_from_ptr_unaligned(ptr.sub(1));
+    let prev2 = S::Vi8::load_from_ptr_unaligned(ptr.sub(2));
+    let prev3 = S::Vi8::load_from_ptr_unaligned(ptr.sub(3));
+
+    // Fast path: nothing can be wrong if the block and the bytes leading into it are ASCII.
+    let zero = S::Vi8::zeroes();
+    if (input | prev1 | prev2 | prev3).cmp_lt(zero).get_mask() == 0 {
+        return zero;
+    }
+
+    let special = prev1.shr_const::<4>().lookup_16(&BYTE_1_HIGH)
+        & (prev1 & 0x0F).lookup_16(&BYTE_1_LOW)
+        & input.shr_const::<4>().lookup_16(&BYTE_2_HIGH);
+
+    // A byte must be a continuation if it is the third byte after a three or four byte lead, or
+    // the fourth byte after a four byte lead. The tables report a pair of continuations as
+    // TWO_CONTS, which must match this exactly. Flipping the sign bit turns the unsigned
+    // comparisons against 0xE0 and 0xF0 into signed ones.
+    let sign = S::Vi8::set1(i8::MIN);
+    let is_third = (prev2 ^ sign).cmp_gt(S::Vi8::set1(0xDFu8 as i8 ^ i8::MIN));
+    let is_fourth = (prev3 ^ sign).cmp_gt(S::Vi8::set1(0xEFu8 as i8 ^ i8::MIN));
+    let must_be_continuation = (is_third | is_fourth) & TWO_CONTS;
+
+    must_be_continuation ^ special
+}
+
+/// Finds the exact error once the vectorized check has failed at or after `offset`.
+/// Everything before `offset` is already known to be valid, apart from a sequence that may
+/// straddle it, so the scalar validator restarts from the last lead byte in the three bytes before.
+#[inline(never)]
+fn locate_error(input: &[u8], offset: usize) -> Result<(), Utf8ErrorPosition> {
+    let offset = offset.min(input.len());
+    let lead = (offset.saturating_sub(3)..offset)
+        .rev()
+        .find(|&i| (input[i] as i8) >= -64);
+    let start = match lead {
+        Some(i) if input[i] >= 0xC0 => i,
+        _ => offset,
+    };
+
+    core::str::from_utf8(&input[start..])
+        .map(|_| ())
+        .map_err(|error| Utf8ErrorPosition {
+            valid_up_to: start + error.valid_up_to(),
+            error_len: error.error_len(),
+        })
+}
+
+simd_runtime_generate!(
+    /// Checks that `input` is valid UTF-8, returning the position of the first error otherwise.
+    pub fn validate_utf8(input: &[u8]) -> Result<(), Utf8ErrorPosition> {
+        // Safe because u8 and i8 have the same size and alignment.
+        let bytes =
+            unsafe { core::slice::from_raw_parts(input.as_ptr() as *const i8, input.len()) };
+        let width = S::Vi8::WIDTH;
+        debug_assert!(width <= MAX_WIDTH);
+
+        let mut offset = 0;
+        while offset < bytes.len() {
+            let errors = if offset >= 3 && offset + width <= bytes.len() {
+                unsafe { check_block::<S>(bytes.as_ptr().add(offset)) }
+            } else {
+                // The first and last blocks are copied into a zeroed buffer, so the bytes before
+                // the start and after the end read as ASCII.
+                let begin = offset.saturating_sub(3);
+                let end = (offset + width).min(bytes.len());
+                let mut buffer = [0i8; MAX_WIDTH + 3];
+                let dst = 3 - (offset - begin);
+                buffer[dst..dst + end - begin].copy_from_slice(&bytes[begin..end]);
+                unsafe { check_block::<S>(buffer.as_ptr().add(3)) }
+            };
+
+            if errors.cmp_neq(S::Vi8::zeroes()).get_mask() != 0 {
+                return locate_error(input, offset);
+            }
+            offset += width;
+        }
+
+        // A sequence cut off by the end of the input only shows up in the padding of a partial
+        // block, so check the last three bytes for leads that need more bytes than are left.
+        let leads_needing_more = [0xC0, 0xE0, 0xF0];
+        let incomplete = input
+            .iter()
+            .rev()
+            .zip(leads_needing_more)
+            .any(|(&byte, lead)| byte >= lead);
+        if incomplete {
+            return locate_error(input, input.len().saturating_sub(3));
+        }
+
+        Ok(())
+    }
+);